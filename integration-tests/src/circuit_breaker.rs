@@ -103,6 +103,88 @@ fn sell_in_omnipool_should_fail_when_max_trade_limit_per_block_exceeded() {
 	});
 }
 
+#[test]
+fn sell_asset_for_lrna_in_omnipool_should_record_asset_inflow() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+
+		let sell_amount = 1_000 * UNITS;
+
+		assert_ok!(Tokens::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			DAI,
+			sell_amount,
+			0,
+		));
+
+		//Act
+		assert_ok!(Omnipool::sell(
+			hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			LRNA,
+			sell_amount,
+			0
+		));
+
+		//Assert
+		let trade_volume = CircuitBreaker::allowed_trade_volume_limit_per_asset(DAI).unwrap();
+		assert_eq!(trade_volume.volume_in, sell_amount);
+		assert_eq!(trade_volume.volume_out, 0);
+		assert!(CircuitBreaker::allowed_trade_volume_limit_per_asset(LRNA).is_none());
+	});
+}
+
+#[test]
+fn sell_asset_for_lrna_in_omnipool_should_fail_when_max_trade_limit_per_block_exceeded() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+
+		let dai_balance_in_omnipool = Tokens::free_balance(DAI, &Omnipool::protocol_account());
+		let trade_volume_limit = CircuitBreaker::trade_volume_limit_per_asset(DAI);
+		let num_of_sells = 4;
+		let sell_amount = CircuitBreaker::calculate_limit(dai_balance_in_omnipool, trade_volume_limit)
+			.unwrap()
+			.checked_div(num_of_sells)
+			.unwrap()
+			.checked_add(1)
+			.unwrap();
+
+		assert_ok!(Tokens::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			DAI,
+			sell_amount * num_of_sells,
+			0,
+		));
+
+		//We need to split to avoid max in ratio
+		for _ in 1..num_of_sells {
+			assert_ok!(Omnipool::sell(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				DAI,
+				LRNA,
+				sell_amount,
+				0
+			));
+		}
+
+		//Act and assert
+		assert_noop!(
+			Omnipool::sell(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				DAI,
+				LRNA,
+				sell_amount,
+				0
+			),
+			pallet_circuit_breaker::Error::<hydradx_runtime::Runtime>::TokenInfluxLimitReached
+		);
+	});
+}
+
 #[test]
 fn sell_lrna_in_omnipool_should_fail_when_min_trade_limit_per_block_exceeded() {
 	Hydra::execute_with(|| {
@@ -348,6 +430,83 @@ fn add_liquidity_to_omnipool_should_not_fail_when_liquidity_limit_per_block_exce
 	});
 }
 
+#[test]
+fn add_liquidity_to_omnipool_should_not_fail_when_liquidity_limit_per_block_exceeded_but_account_in_whitelist() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+
+		let hdx_balance_in_omnipool = Balances::free_balance(&Omnipool::protocol_account());
+		let liquidity_limit = CircuitBreaker::add_liquidity_limit_per_asset(CORE_ASSET_ID).unwrap();
+		let added_liquidity = CircuitBreaker::calculate_limit(hdx_balance_in_omnipool, liquidity_limit)
+			.unwrap()
+			.checked_add(1)
+			.unwrap();
+
+		assert_ok!(Balances::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			added_liquidity,
+			0,
+		));
+
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			None,
+		));
+
+		set_relaychain_block_number(300);
+
+		//Act and assert
+		assert_ok!(Omnipool::add_liquidity(
+			hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+			CORE_ASSET_ID,
+			added_liquidity,
+		));
+	});
+}
+
+#[test]
+fn add_liquidity_to_omnipool_should_fail_when_liquidity_limit_per_block_exceeded_and_whitelist_entry_expired() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+
+		let hdx_balance_in_omnipool = Balances::free_balance(&Omnipool::protocol_account());
+		let liquidity_limit = CircuitBreaker::add_liquidity_limit_per_asset(CORE_ASSET_ID).unwrap();
+		let added_liquidity = CircuitBreaker::calculate_limit(hdx_balance_in_omnipool, liquidity_limit)
+			.unwrap()
+			.checked_add(1)
+			.unwrap();
+
+		assert_ok!(Balances::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			added_liquidity,
+			0,
+		));
+
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			Some(hydradx_runtime::System::block_number() + 1),
+		));
+
+		set_relaychain_block_number(300);
+
+		//Act and assert
+		assert_noop!(
+			Omnipool::add_liquidity(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				CORE_ASSET_ID,
+				added_liquidity,
+			),
+			pallet_circuit_breaker::Error::<hydradx_runtime::Runtime>::MaxLiquidityLimitPerBlockReached
+		);
+	});
+}
+
 #[test]
 fn remove_liquidity_to_omnipool_should_work_when_liquidity_limit_per_block_not_exceeded() {
 	Hydra::execute_with(|| {
@@ -577,6 +736,213 @@ fn remove_liquidity_to_omnipool_should_not_fail_when_liquidity_limit_per_block_e
 	});
 }
 
+#[test]
+fn sell_in_omnipool_should_work_when_price_change_limit_per_block_not_exceeded() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+		set_dai_price_change_limit();
+
+		let dai_balance_in_omnipool = Tokens::free_balance(DAI, &Omnipool::protocol_account());
+		let sell_amount = dai_balance_in_omnipool / 1_000;
+
+		assert_ok!(Tokens::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			DAI,
+			sell_amount,
+			0,
+		));
+
+		//Act and assert
+		assert_ok!(Omnipool::sell(
+			hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			CORE_ASSET_ID,
+			sell_amount,
+			0
+		));
+	});
+}
+
+#[test]
+fn sell_in_omnipool_should_fail_when_price_change_limit_per_block_exceeded() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+		set_dai_price_change_limit();
+
+		// selling 1% of the reserve moves the price by ~2%
+		let dai_balance_in_omnipool = Tokens::free_balance(DAI, &Omnipool::protocol_account());
+		let sell_amount = dai_balance_in_omnipool / 100;
+
+		assert_ok!(Tokens::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			DAI,
+			sell_amount,
+			0,
+		));
+
+		//Act and assert
+		assert_noop!(
+			Omnipool::sell(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				DAI,
+				CORE_ASSET_ID,
+				sell_amount,
+				0
+			),
+			pallet_circuit_breaker::Error::<hydradx_runtime::Runtime>::MaxPriceDecreasePerBlockReached
+		);
+	});
+}
+
+#[test]
+fn sell_in_omnipool_should_fail_when_consequent_sells_exceed_price_change_limit_per_block() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+		set_dai_price_change_limit();
+
+		// each sell moves the price by ~0.8%
+		let dai_balance_in_omnipool = Tokens::free_balance(DAI, &Omnipool::protocol_account());
+		let sell_amount = dai_balance_in_omnipool * 4 / 1_000;
+
+		assert_ok!(Tokens::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			DAI,
+			3 * sell_amount,
+			0,
+		));
+
+		assert_ok!(Omnipool::sell(
+			hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			CORE_ASSET_ID,
+			sell_amount,
+			0
+		));
+
+		//Act and assert
+		assert_noop!(
+			Omnipool::sell(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				DAI,
+				CORE_ASSET_ID,
+				sell_amount,
+				0
+			),
+			pallet_circuit_breaker::Error::<hydradx_runtime::Runtime>::MaxPriceDecreasePerBlockReached
+		);
+
+		// opening price is reset in the next block
+		set_relaychain_block_number(hydradx_runtime::System::block_number() + 1);
+
+		assert_ok!(Omnipool::sell(
+			hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			CORE_ASSET_ID,
+			sell_amount,
+			0
+		));
+	});
+}
+
+#[test]
+fn sell_lrna_in_omnipool_should_fail_when_price_change_limit_per_block_exceeded() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+		set_dai_price_change_limit();
+
+		// selling LRNA for DAI raises the price of DAI
+		let dai_hub_reserve = Omnipool::load_asset_state(DAI).unwrap().hub_reserve;
+		let sell_amount = dai_hub_reserve / 50;
+
+		assert_ok!(Tokens::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			LRNA,
+			sell_amount,
+			0,
+		));
+
+		//Act and assert
+		assert_noop!(
+			Omnipool::sell(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				LRNA,
+				DAI,
+				sell_amount,
+				0
+			),
+			pallet_circuit_breaker::Error::<hydradx_runtime::Runtime>::MaxPriceIncreasePerBlockReached
+		);
+	});
+}
+
+#[test]
+fn remaining_allowance_should_decrease_when_asset_is_sold_in_omnipool() {
+	Hydra::execute_with(|| {
+		//Arrange
+		init_omnipool();
+
+		let dai_reserve = Omnipool::load_asset_state(DAI).unwrap().reserve;
+		let allowance = CircuitBreaker::remaining_allowance_in_block(DAI, dai_reserve).unwrap();
+		let inflow = allowance.inflow.unwrap();
+		let outflow = allowance.outflow.unwrap();
+		let sell_amount = inflow / 4;
+
+		assert_ok!(Tokens::set_balance(
+			RawOrigin::Root.into(),
+			ALICE.into(),
+			DAI,
+			2 * inflow,
+			0,
+		));
+
+		//Act
+		//We need to split to avoid max in ratio
+		for _ in 0..3 {
+			assert_ok!(Omnipool::sell(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				DAI,
+				CORE_ASSET_ID,
+				sell_amount,
+				0
+			));
+		}
+
+		//Assert
+		let dai_reserve = Omnipool::load_asset_state(DAI).unwrap().reserve;
+		let allowance = CircuitBreaker::remaining_allowance_in_block(DAI, dai_reserve).unwrap();
+		assert_eq!(allowance.inflow, Some(inflow - 3 * sell_amount));
+		assert_eq!(allowance.outflow, Some(outflow + 3 * sell_amount));
+
+		assert_noop!(
+			Omnipool::sell(
+				hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+				DAI,
+				CORE_ASSET_ID,
+				allowance.inflow.unwrap() + 1,
+				0
+			),
+			pallet_circuit_breaker::Error::<hydradx_runtime::Runtime>::TokenInfluxLimitReached
+		);
+	});
+}
+
+fn set_dai_price_change_limit() {
+	// 1%
+	assert_ok!(CircuitBreaker::set_price_change_limit(
+		RawOrigin::Root.into(),
+		DAI,
+		Some((100, 10_000)),
+	));
+}
+
 fn init_omnipool() {
 	assert_ok!(hydradx_runtime::Omnipool::set_tvl_cap(
 		hydradx_runtime::RuntimeOrigin::root(),
@@ -0,0 +1,199 @@
+#![cfg(test)]
+use crate::polkadot_test_net::*;
+
+use frame_support::{assert_ok, weights::Weight, PalletId};
+use orml_traits::currency::MultiCurrency;
+use polkadot_xcm::{latest::prelude::*, VersionedXcm};
+use pretty_assertions::assert_eq;
+use primitives::Balance;
+use sp_runtime::traits::AccountIdConversion;
+use xcm_emulator::TestExt;
+
+const ALICE_INITIAL_BALANCE_ON_ACALA: Balance = 100_000 * UNITS;
+
+/// Location of the native asset of Acala as seen from both Acala and HydraDX.
+fn acala_native_location() -> MultiLocation {
+	MultiLocation::new(1, X2(Parachain(ACALA_PARA_ID), GeneralIndex(0)))
+}
+
+fn hydra_native_location() -> MultiLocation {
+	MultiLocation::new(1, X2(Parachain(HYDRA_PARA_ID), GeneralIndex(0)))
+}
+
+/// Register the native asset of Acala as DAI and initialize the omnipool.
+fn init_hydra() {
+	Hydra::execute_with(|| {
+		init_omnipool();
+
+		assert_ok!(hydradx_runtime::AssetRegistry::set_location(
+			hydradx_runtime::RuntimeOrigin::root(),
+			DAI, // we pretend that the incoming tokens are DAI
+			hydradx_runtime::AssetLocation(acala_native_location())
+		));
+	});
+}
+
+/// Send `give_amount` of the Acala native asset to HydraDX, exchange it for HDX there and deposit the result to BOB.
+fn send_exchange_from_acala(give_amount: Balance, want_amount: Balance, maximal: bool) {
+	Acala::execute_with(|| {
+		assert_ok!(hydradx_runtime::Balances::set_balance(
+			hydradx_runtime::RuntimeOrigin::root(),
+			AccountId::from(ALICE).into(),
+			ALICE_INITIAL_BALANCE_ON_ACALA,
+			0,
+		));
+
+		let give: MultiAsset = (acala_native_location(), give_amount).into();
+		let want: MultiAsset = (hydra_native_location(), want_amount).into();
+
+		let message = Xcm(vec![
+			WithdrawAsset(give.clone().into()),
+			DepositReserveAsset {
+				assets: Wild(AllCounted(1)),
+				dest: MultiLocation::new(1, X1(Parachain(HYDRA_PARA_ID))),
+				xcm: Xcm(vec![
+					BuyExecution {
+						fees: give,
+						weight_limit: Unlimited,
+					},
+					ExchangeAsset {
+						give: Wild(AllCounted(1)),
+						want: want.into(),
+						maximal,
+					},
+					DepositAsset {
+						assets: Wild(AllCounted(2)),
+						beneficiary: Junction::AccountId32 { id: BOB, network: None }.into(),
+					},
+				]),
+			},
+		]);
+
+		assert_ok!(hydradx_runtime::PolkadotXcm::execute(
+			hydradx_runtime::RuntimeOrigin::signed(ALICE.into()),
+			Box::new(VersionedXcm::from(message)),
+			Weight::from_ref_time(399_600_000_000),
+		));
+
+		assert_eq!(
+			hydradx_runtime::Balances::free_balance(&AccountId::from(ALICE)),
+			ALICE_INITIAL_BALANCE_ON_ACALA - give_amount
+		);
+	});
+}
+
+fn temp_account_should_be_empty() {
+	let temp_account: AccountId = PalletId(*b"xcm/exch").into_account_truncating();
+	assert_eq!(hydradx_runtime::Currencies::free_balance(HDX, &temp_account), 0);
+	assert_eq!(hydradx_runtime::Currencies::free_balance(DAI, &temp_account), 0);
+}
+
+#[test]
+fn hydra_should_sell_received_asset_in_omnipool_when_exchange_is_maximal() {
+	// Arrange
+	TestNet::reset();
+	init_hydra();
+
+	// Act
+	send_exchange_from_acala(50_000 * UNITS, UNITS, true);
+
+	// Assert
+	Hydra::execute_with(|| {
+		assert!(
+			hydradx_runtime::Currencies::free_balance(HDX, &AccountId::from(BOB)) > BOB_INITIAL_NATIVE_BALANCE + UNITS
+		);
+		// all of the given asset is sold
+		assert_eq!(
+			hydradx_runtime::Currencies::free_balance(DAI, &AccountId::from(BOB)),
+			1_000 * UNITS * 1_000_000
+		);
+		temp_account_should_be_empty();
+	});
+}
+
+#[test]
+fn hydra_should_buy_wanted_asset_in_omnipool_when_exchange_is_not_maximal() {
+	// Arrange
+	TestNet::reset();
+	init_hydra();
+
+	// Act
+	send_exchange_from_acala(50_000 * UNITS, UNITS, false);
+
+	// Assert
+	Hydra::execute_with(|| {
+		assert_eq!(
+			hydradx_runtime::Currencies::free_balance(HDX, &AccountId::from(BOB)),
+			BOB_INITIAL_NATIVE_BALANCE + UNITS
+		);
+		// unspent part of the given asset is returned
+		assert!(hydradx_runtime::Currencies::free_balance(DAI, &AccountId::from(BOB)) > 1_000 * UNITS * 1_000_000);
+		temp_account_should_be_empty();
+	});
+}
+
+#[test]
+fn exchange_should_fail_when_min_amount_is_not_reached() {
+	// Arrange
+	TestNet::reset();
+	init_hydra();
+
+	// Act
+	send_exchange_from_acala(50_000 * UNITS, 1_000 * UNITS, true);
+
+	// Assert
+	Hydra::execute_with(|| {
+		assert_eq!(
+			hydradx_runtime::Currencies::free_balance(HDX, &AccountId::from(BOB)),
+			BOB_INITIAL_NATIVE_BALANCE
+		);
+		assert_eq!(
+			hydradx_runtime::Currencies::free_balance(DAI, &AccountId::from(BOB)),
+			1_000 * UNITS * 1_000_000
+		);
+		temp_account_should_be_empty();
+	});
+}
+
+#[test]
+fn exchange_should_fail_when_circuit_breaker_limit_is_reached() {
+	// Arrange
+	TestNet::reset();
+	init_hydra();
+
+	let bob_hdx_balance = Hydra::execute_with(|| {
+		let limit = (1, 10_000);
+		assert_ok!(hydradx_runtime::CircuitBreaker::set_trade_volume_limit(
+			hydradx_runtime::RuntimeOrigin::root(),
+			DAI,
+			limit,
+		));
+
+		// use up almost all of the DAI trade volume allowed in this block
+		let dai_balance_in_omnipool =
+			hydradx_runtime::Tokens::free_balance(DAI, &hydradx_runtime::Omnipool::protocol_account());
+		let sell_amount =
+			hydradx_runtime::CircuitBreaker::calculate_limit(dai_balance_in_omnipool, limit).unwrap() - 1_000 * UNITS;
+		assert_ok!(hydradx_runtime::Omnipool::sell(
+			hydradx_runtime::RuntimeOrigin::signed(BOB.into()),
+			DAI,
+			HDX,
+			sell_amount,
+			0,
+		));
+
+		hydradx_runtime::Currencies::free_balance(HDX, &AccountId::from(BOB))
+	});
+
+	// Act
+	send_exchange_from_acala(50_000 * UNITS, UNITS, true);
+
+	// Assert
+	Hydra::execute_with(|| {
+		assert_eq!(
+			hydradx_runtime::Currencies::free_balance(HDX, &AccountId::from(BOB)),
+			bob_hdx_balance
+		);
+		temp_account_should_be_empty();
+	});
+}
@@ -3,6 +3,7 @@ mod circuit_breaker;
 mod cross_chain_transfer;
 mod dust;
 mod dust_removal_whitelist;
+mod exchange_asset;
 mod non_native_fee;
 mod omnipool_init;
 mod omnipool_liquidity_mining;
@@ -10,4 +11,5 @@ mod omnipool_price_provider;
 mod oracle;
 mod otc;
 mod polkadot_test_net;
+mod router;
 mod vesting;
@@ -26,6 +26,7 @@ use warehouse_liquidity_mining::{
 };
 
 use orml_traits::MultiCurrency;
+use pallet_omnipool::types::OwnedPosition;
 use polkadot_primitives::v2::BlockNumber;
 use primitives::AssetId;
 use sp_runtime::{
@@ -538,6 +539,78 @@ fn withdraw_shares_should_work_when_deposit_exists() {
 	});
 }
 
+#[test]
+fn omnipool_positions_of_should_include_positions_deposited_to_liquidity_mining() {
+	TestNet::reset();
+
+	Hydra::execute_with(|| {
+		let global_farm_id = 1;
+		let yield_farm_id = 2;
+
+		//Arrange
+		init_omnipool();
+
+		seed_lm_pot();
+		//necessary for oracle to have a price.
+		do_lrna_hdx_trade();
+
+		//NOTE: necessary to get oracle price.
+		hydradx_run_to_block(100);
+		set_relaychain_block_number(100);
+		create_global_farm();
+
+		set_relaychain_block_number(200);
+		create_yield_farm(global_farm_id, ETH);
+
+		set_relaychain_block_number(300);
+
+		assert_ok!(hydradx_runtime::Currencies::update_balance(
+			hydradx_runtime::RuntimeOrigin::root(),
+			CHARLIE.into(),
+			ETH,
+			10_000 * UNITS as i128,
+		));
+
+		let position_id = omnipool_add_liquidity(CHARLIE.into(), ETH, 1_000 * UNITS);
+		let deposited_position_id = omnipool_add_liquidity(CHARLIE.into(), ETH, 2_000 * UNITS);
+
+		set_relaychain_block_number(400);
+		let deposit_id = 1;
+		assert_ok!(hydradx_runtime::OmnipoolLiquidityMining::deposit_shares(
+			RuntimeOrigin::signed(CHARLIE.into()),
+			global_farm_id,
+			yield_farm_id,
+			deposited_position_id
+		));
+
+		//Act
+		let positions = hydradx_runtime::omnipool_positions_of::<hydradx_runtime::Runtime, hydradx_runtime::Uniques>(
+			&CHARLIE.into(),
+		);
+
+		//Assert
+		assert_eq!(
+			positions,
+			vec![
+				OwnedPosition {
+					position_id,
+					deposit_id: None,
+					position: hydradx_runtime::Omnipool::positions(position_id).unwrap(),
+				},
+				OwnedPosition {
+					position_id: deposited_position_id,
+					deposit_id: Some(deposit_id),
+					position: hydradx_runtime::Omnipool::positions(deposited_position_id).unwrap(),
+				},
+			]
+		);
+		assert!(
+			hydradx_runtime::omnipool_positions_of::<hydradx_runtime::Runtime, hydradx_runtime::Uniques>(&BOB.into())
+				.is_empty()
+		);
+	});
+}
+
 fn init_omnipool() {
 	let native_price = FixedU128::from_inner(1201500000000000);
 	let stable_price = FixedU128::from_inner(45_000_000_000);
@@ -0,0 +1,203 @@
+#![cfg(test)]
+
+use crate::polkadot_test_net::*;
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+use frame_system::RawOrigin;
+use hydradx_runtime::{Currencies, Omnipool, Router, RuntimeOrigin, Tokens, OTC};
+use hydradx_traits::pools::SpotPriceProvider;
+use orml_traits::MultiCurrency;
+use pallet_route_executor::Trade;
+use primitives::{AccountId, AssetId, Balance};
+use sp_runtime::FixedPointNumber;
+use xcm_emulator::TestExt;
+
+const ORDER_AMOUNT_OUT: Balance = 5 * UNITS;
+const ALICE_DAI_BALANCE: Balance = 1_000_000 * UNITS;
+
+/// Initialize the omnipool and give Alice enough DAI to buy HDX in amounts above the OTC minimum.
+fn init_omnipool_and_fund_alice() {
+	init_omnipool();
+
+	assert_ok!(Tokens::set_balance(
+		RawOrigin::Root.into(),
+		ALICE.into(),
+		DAI,
+		ALICE_DAI_BALANCE,
+		0,
+	));
+}
+
+fn route(order: Option<u32>) -> BoundedVec<Trade<AssetId, u32>, hydradx_runtime::MaxRouteLength> {
+	BoundedVec::truncate_from(vec![Trade {
+		asset_in: DAI,
+		asset_out: HDX,
+		order,
+	}])
+}
+
+/// Place an order selling HDX for DAI at given percentage of the omnipool spot price.
+fn place_hdx_order(price_percentage: u128) -> Balance {
+	let spot_price = Omnipool::spot_price(DAI, HDX).unwrap();
+	let amount_in = spot_price.saturating_mul_int(ORDER_AMOUNT_OUT) * price_percentage / 100;
+
+	assert_ok!(OTC::place_order(
+		RuntimeOrigin::signed(BOB.into()),
+		DAI,
+		HDX,
+		amount_in,
+		ORDER_AMOUNT_OUT,
+		false,
+	));
+
+	amount_in
+}
+
+#[test]
+fn sell_should_trade_in_omnipool_when_route_is_empty() {
+	TestNet::reset();
+
+	Hydra::execute_with(|| {
+		init_omnipool_and_fund_alice();
+
+		let amount_in = 20 * UNITS;
+		let quote = Omnipool::quote_sell(DAI, HDX, amount_in).unwrap();
+		let hdx_balance = Currencies::free_balance(HDX, &AccountId::from(ALICE));
+
+		assert_ok!(Router::sell(
+			RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			HDX,
+			amount_in,
+			quote.amount_out,
+			BoundedVec::default(),
+		));
+
+		assert_eq!(
+			Currencies::free_balance(HDX, &AccountId::from(ALICE)),
+			hdx_balance + quote.amount_out
+		);
+		assert_eq!(
+			Currencies::free_balance(DAI, &AccountId::from(ALICE)),
+			ALICE_DAI_BALANCE - amount_in
+		);
+	});
+}
+
+#[test]
+fn buy_should_trade_in_omnipool_when_route_is_empty() {
+	TestNet::reset();
+
+	Hydra::execute_with(|| {
+		init_omnipool_and_fund_alice();
+
+		let amount_out = UNITS;
+		let quote = Omnipool::quote_buy(HDX, DAI, amount_out).unwrap();
+		let hdx_balance = Currencies::free_balance(HDX, &AccountId::from(ALICE));
+
+		assert_ok!(Router::buy(
+			RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			HDX,
+			amount_out,
+			quote.amount_in,
+			BoundedVec::default(),
+		));
+
+		assert_eq!(
+			Currencies::free_balance(HDX, &AccountId::from(ALICE)),
+			hdx_balance + amount_out
+		);
+		assert_eq!(
+			Currencies::free_balance(DAI, &AccountId::from(ALICE)),
+			ALICE_DAI_BALANCE - quote.amount_in
+		);
+	});
+}
+
+#[test]
+fn sell_should_fill_otc_order_when_order_price_is_better() {
+	TestNet::reset();
+
+	Hydra::execute_with(|| {
+		init_omnipool_and_fund_alice();
+
+		let order_amount_in = place_hdx_order(90);
+
+		let amount_in = 200_000 * UNITS;
+		let quote = Omnipool::quote_sell(DAI, HDX, amount_in - order_amount_in).unwrap();
+		let hdx_balance = Currencies::free_balance(HDX, &AccountId::from(ALICE));
+
+		assert_ok!(Router::sell(
+			RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			HDX,
+			amount_in,
+			ORDER_AMOUNT_OUT + quote.amount_out,
+			route(Some(0)),
+		));
+
+		assert!(OTC::orders(0).is_none());
+		assert_eq!(
+			Currencies::free_balance(HDX, &AccountId::from(ALICE)),
+			hdx_balance + ORDER_AMOUNT_OUT + quote.amount_out
+		);
+		assert_eq!(
+			Currencies::free_balance(DAI, &AccountId::from(BOB)),
+			1_000 * UNITS * 1_000_000 + order_amount_in
+		);
+	});
+}
+
+#[test]
+fn sell_should_not_fill_otc_order_when_order_price_is_worse() {
+	TestNet::reset();
+
+	Hydra::execute_with(|| {
+		init_omnipool_and_fund_alice();
+
+		place_hdx_order(110);
+
+		let amount_in = 200_000 * UNITS;
+		let quote = Omnipool::quote_sell(DAI, HDX, amount_in).unwrap();
+		let hdx_balance = Currencies::free_balance(HDX, &AccountId::from(ALICE));
+
+		assert_ok!(Router::sell(
+			RuntimeOrigin::signed(ALICE.into()),
+			DAI,
+			HDX,
+			amount_in,
+			quote.amount_out,
+			route(Some(0)),
+		));
+
+		assert!(OTC::orders(0).is_some());
+		assert_eq!(
+			Currencies::free_balance(HDX, &AccountId::from(ALICE)),
+			hdx_balance + quote.amount_out
+		);
+	});
+}
+
+#[test]
+fn sell_should_fail_when_min_amount_out_is_not_reached() {
+	TestNet::reset();
+
+	Hydra::execute_with(|| {
+		init_omnipool_and_fund_alice();
+
+		let amount_in = 20 * UNITS;
+		let quote = Omnipool::quote_sell(DAI, HDX, amount_in).unwrap();
+
+		assert_noop!(
+			Router::sell(
+				RuntimeOrigin::signed(ALICE.into()),
+				DAI,
+				HDX,
+				amount_in,
+				quote.amount_out + 1,
+				BoundedVec::default(),
+			),
+			pallet_route_executor::Error::<hydradx_runtime::Runtime>::MinAmountOutNotReached
+		);
+	});
+}
@@ -3,7 +3,8 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use crate::service::{FullBackend, FullClient, HydraDXExecutorDispatch, TestingHydraDXExecutorDispatch};
-use common_runtime::{AccountId, Balance, Block, BlockNumber, Hash, Header, Index};
+use common_runtime::{AccountId, AssetId, Balance, Block, BlockNumber, Hash, Header, Index};
+use primitives::ItemId;
 use sc_client_api::{Backend as BackendT, BlockchainEvents, KeyIterator};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
@@ -23,6 +24,8 @@ pub trait RuntimeApiCollection:
 	+ sp_block_builder::BlockBuilder<Block>
 	+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index>
 	+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+	+ pallet_omnipool_runtime_api::OmnipoolApi<Block, AccountId, AssetId, Balance, ItemId>
+	+ pallet_circuit_breaker_runtime_api::CircuitBreakerApi<Block, AssetId, Balance>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -38,6 +41,8 @@ where
 		+ sp_block_builder::BlockBuilder<Block>
 		+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Index>
 		+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
+		+ pallet_omnipool_runtime_api::OmnipoolApi<Block, AccountId, AssetId, Balance, ItemId>
+		+ pallet_circuit_breaker_runtime_api::CircuitBreakerApi<Block, AssetId, Balance>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>,
@@ -7,7 +7,8 @@
 
 use std::sync::Arc;
 
-use hydradx_runtime::{opaque::Block, AccountId, Balance, Index};
+use hydradx_runtime::{opaque::Block, AccountId, AssetId, Balance, Index};
+use primitives::ItemId;
 pub use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
@@ -36,9 +37,13 @@ where
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+	C::Api: pallet_omnipool_rpc::OmnipoolRuntimeApi<Block, AccountId, AssetId, Balance, ItemId>,
+	C::Api: pallet_circuit_breaker_rpc::CircuitBreakerRuntimeApi<Block, AssetId, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + Sync + Send + 'static,
 {
+	use pallet_circuit_breaker_rpc::{CircuitBreaker, CircuitBreakerApiServer};
+	use pallet_omnipool_rpc::{Omnipool, OmnipoolApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -50,7 +55,9 @@ where
 	} = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(Omnipool::new(client.clone()).into_rpc())?;
+	module.merge(CircuitBreaker::new(client).into_rpc())?;
 
 	Ok(module)
 }
@@ -0,0 +1,115 @@
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the Circuit Breaker pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_circuit_breaker_runtime_api::{CircuitBreakerApi as CircuitBreakerRuntimeApi, RemainingAllowance};
+
+#[rpc(client, server)]
+pub trait CircuitBreakerApi<BlockHash, AssetId, Balance> {
+	/// Amounts of `asset_id` that can still be traded, added and removed in the next block.
+	#[method(name = "circuitBreaker_remainingAllowance")]
+	fn remaining_allowance(&self, asset_id: AssetId, at: Option<BlockHash>) -> RpcResult<RemainingAllowance<Balance>>;
+}
+
+/// Provides RPC methods to query limits of the circuit breaker.
+pub struct CircuitBreaker<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> CircuitBreaker<C, Block> {
+	/// Create new `CircuitBreaker` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The remaining allowance cannot be calculated.
+	AllowanceError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::AllowanceError => 2,
+		}
+	}
+}
+
+#[async_trait]
+impl<C, Block, AssetId, Balance> CircuitBreakerApiServer<<Block as BlockT>::Hash, AssetId, Balance>
+	for CircuitBreaker<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: CircuitBreakerRuntimeApi<Block, AssetId, Balance>,
+	AssetId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn remaining_allowance(
+		&self,
+		asset_id: AssetId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<RemainingAllowance<Balance>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.remaining_allowance(at, asset_id)
+			.map_err(runtime_error_into_rpc_err)?
+			.map_err(|e| allowance_error_into_rpc_err(format!("{:?}", e)))
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query circuit breaker.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Converts a dispatch error returned by the runtime into an RPC error.
+fn allowance_error_into_rpc_err(err: String) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::AllowanceError.into(),
+		"Remaining allowance cannot be calculated.",
+		Some(err),
+	))
+	.into()
+}
@@ -0,0 +1,35 @@
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the Circuit Breaker pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::DispatchError;
+
+pub use pallet_circuit_breaker::RemainingAllowance;
+
+sp_api::decl_runtime_apis! {
+	/// API to query limits of assets enforced by the circuit breaker.
+	pub trait CircuitBreakerApi<AssetId, Balance>
+	where
+		AssetId: Codec,
+		Balance: Codec,
+	{
+		/// Amounts of `asset_id` that can still be traded, added and removed in the next block.
+		fn remaining_allowance(asset_id: AssetId) -> Result<RemainingAllowance<Balance>, DispatchError>;
+	}
+}
@@ -129,6 +129,91 @@ benchmarks! {
 		assert_eq!(LiquidityRemoveLimitPerAsset::<T>::get(asset_id), trade_limit);
 	}
 
+	set_window_limits {
+		let asset_id = T::AssetId::from(2u32);
+		let window_limits = Some(WindowLimits {
+			blocks: 10,
+			trade_volume_limit: Some((crate::MAX_LIMIT_VALUE, 1)),
+			add_liquidity_limit: Some((crate::MAX_LIMIT_VALUE, 1)),
+			remove_liquidity_limit: Some((crate::MAX_LIMIT_VALUE, 1)),
+		});
+
+	}: _(RawOrigin::Root, asset_id, window_limits)
+	verify {
+		assert_eq!(WindowLimitsPerAsset::<T>::get(asset_id), window_limits);
+	}
+
+	resume_asset {
+		let asset_id = T::AssetId::from(2u32);
+		HaltedAssets::<T>::insert(asset_id, HaltedAsset {
+			halted_at: frame_system::Pallet::<T>::block_number(),
+			previous_state: HaltStateOf::<T>::default(),
+		});
+
+	}: _(RawOrigin::Root, asset_id)
+	verify {
+		assert!(!HaltedAssets::<T>::contains_key(asset_id));
+	}
+
+	note_limit_hit {
+		let asset_id = T::AssetId::from(2u32);
+		LimitHitsPerAsset::<T>::insert(asset_id, LimitHits {
+			count: T::MaxLimitHits::get().saturating_sub(1),
+			period_start: frame_system::Pallet::<T>::block_number(),
+		});
+
+		let caller: T::AccountId = account("caller", 0, 1);
+		T::Currency::make_free_balance_be(
+			&caller,
+			T::Currency::minimum_balance().saturating_add(T::LimitHitFee::get()).saturating_mul(T::Balance::from(2u128)),
+		);
+
+	}: {
+		Pallet::<T>::charge_limit_hit_fee(&caller)?;
+		Pallet::<T>::note_limit_hit(asset_id)
+	}
+	verify {}
+
+	set_price_change_limit {
+		let asset_id = T::AssetId::from(2u32);
+		let price_change_limit = Some((crate::MAX_LIMIT_VALUE, 1));
+
+	}: _(RawOrigin::Root, asset_id, price_change_limit)
+	verify {
+		assert_eq!(PriceChangeLimitPerAsset::<T>::get(asset_id), price_change_limit);
+	}
+
+	ensure_price_change_limit {
+		let asset_id = T::AssetId::from(2u32);
+		let reserve = T::Balance::from(1_000_000u32);
+		let hub_reserve = T::Balance::from(2_000_000u32);
+		PriceChangeLimitPerAsset::<T>::insert(asset_id, Some((crate::MAX_LIMIT_VALUE, 1)));
+
+	}: {
+		Pallet::<T>::ensure_price_change_limit(asset_id, reserve, hub_reserve, reserve, hub_reserve)?;
+	}
+	verify {
+		assert!(OpeningPricePerAsset::<T>::contains_key(asset_id));
+	}
+
+	add_to_whitelist {
+		let who: T::AccountId = account("user", 0, 1);
+		let expires_at = Some(frame_system::Pallet::<T>::block_number() + T::BlockNumber::from(10u32));
+
+	}: _(RawOrigin::Root, who.clone(), expires_at)
+	verify {
+		assert_eq!(Whitelist::<T>::get(who), Some(expires_at));
+	}
+
+	remove_from_whitelist {
+		let who: T::AccountId = account("user", 0, 1);
+		Whitelist::<T>::insert(&who, None::<T::BlockNumber>);
+
+	}: _(RawOrigin::Root, who.clone())
+	verify {
+		assert!(!Whitelist::<T>::contains_key(who));
+	}
+
 	ensure_add_liquidity_limit {
 		let user: T::AccountId = account("user", 0, 1);
 		let asset_id = T::AssetId::from(2u32);
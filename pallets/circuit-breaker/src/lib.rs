@@ -18,18 +18,28 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Decode, Encode};
-use frame_support::dispatch::Weight;
-use frame_support::traits::{Contains, EnsureOrigin};
+use frame_support::dispatch::{DispatchClass, DispatchInfo, Pays, PostDispatchInfo, Weight};
+use frame_support::traits::{Contains, Currency, EnsureOrigin, ExistenceRequirement};
 use frame_support::{ensure, pallet_prelude::DispatchResult, traits::Get};
 use frame_system::ensure_signed_or_root;
 use frame_system::pallet_prelude::OriginFor;
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_core::MaxEncodedLen;
-use sp_runtime::traits::{AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Zero};
-use sp_runtime::{ArithmeticError, DispatchError, RuntimeDebug};
-
+use sp_runtime::traits::{
+	AtLeast32BitUnsigned, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, SaturatedConversion, Saturating, Zero,
+};
+use sp_runtime::traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension};
+use sp_runtime::transaction_validity::TransactionValidityError;
+use sp_runtime::{ArithmeticError, DispatchError, FixedPointNumber, FixedU128, RuntimeDebug};
+use sp_std::marker::PhantomData;
+
+pub mod traits;
 pub mod weights;
 
+use traits::{AssetHaltHandler, LimitedAssets};
+
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarking;
 
@@ -90,6 +100,13 @@ where
 		self.check_influx_limit()?;
 		Ok(())
 	}
+
+	/// Scale down both volumes by given fraction (numerator, denominator).
+	pub fn decay(&mut self, remaining: (u32, u32)) -> DispatchResult {
+		self.volume_in = Pallet::<T>::calculate_limit(self.volume_in, remaining)?;
+		self.volume_out = Pallet::<T>::calculate_limit(self.volume_out, remaining)?;
+		Ok(())
+	}
 }
 
 #[derive(Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Eq, PartialEq)]
@@ -118,6 +135,81 @@ where
 		);
 		Ok(())
 	}
+
+	/// Scale down the liquidity by given fraction (numerator, denominator).
+	pub fn decay(&mut self, remaining: (u32, u32)) -> DispatchResult {
+		self.liquidity = Pallet::<T>::calculate_limit(self.liquidity, remaining)?;
+		Ok(())
+	}
+}
+
+/// Limits of an asset enforced over a rolling window of blocks, on top of the per-block limits.
+/// Limits are represented as fractions (nominator, denominator) of the liquidity at the start of the window.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Eq, PartialEq)]
+pub struct WindowLimits {
+	/// Length of the window in blocks
+	pub blocks: u32,
+	/// Max net trade volume in the window. If set to None, the limit is not enforced.
+	pub trade_volume_limit: Option<(u32, u32)>,
+	/// Max liquidity added in the window. If set to None, the limit is not enforced.
+	pub add_liquidity_limit: Option<(u32, u32)>,
+	/// Max liquidity removed in the window. If set to None, the limit is not enforced.
+	pub remove_liquidity_limit: Option<(u32, u32)>,
+}
+
+/// Trade volumes of an asset accumulated in a rolling window.
+///
+/// On each update, volumes decay proportionally to the part of the window elapsed since the last update.
+/// They are fully forgotten when the whole window passes without an update.
+#[derive(Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Eq, PartialEq)]
+#[scale_info(skip_type_params(T))]
+pub struct WindowTradeVolume<T: Config> {
+	pub volume: TradeVolumeLimit<T>,
+	pub updated_at: T::BlockNumber,
+}
+
+/// Liquidity added or removed of an asset accumulated in a rolling window.
+///
+/// On each update, liquidity decays proportionally to the part of the window elapsed since the last update.
+/// It is fully forgotten when the whole window passes without an update.
+#[derive(Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Eq, PartialEq)]
+#[scale_info(skip_type_params(T))]
+pub struct WindowLiquidity<T: Config> {
+	pub liquidity: LiquidityLimit<T>,
+	pub updated_at: T::BlockNumber,
+}
+
+/// Limit hits of an asset counted since the start of the current period.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Eq, PartialEq)]
+pub struct LimitHits<BlockNumber> {
+	pub count: u32,
+	pub period_start: BlockNumber,
+}
+
+/// Asset halted after repeated limit hits, with its state from before the halt.
+#[derive(Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, Eq, PartialEq)]
+pub struct HaltedAsset<BlockNumber, State> {
+	pub halted_at: BlockNumber,
+	pub previous_state: State,
+}
+
+/// State of a halted asset saved by `AssetHaltHandler`.
+pub type HaltStateOf<T> = <<T as Config>::AssetHaltHandler as AssetHaltHandler<<T as Config>::AssetId>>::State;
+
+/// Amounts of an asset that can still be traded, added or removed within the per-block and rolling window limits.
+/// None means that the limit is not enforced for the asset.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RemainingAllowance<Balance> {
+	/// Net amount that can still be sold to the pool
+	pub inflow: Option<Balance>,
+	/// Net amount that can still be bought from the pool
+	pub outflow: Option<Balance>,
+	/// Amount of liquidity that can still be added
+	pub add_liquidity: Option<Balance>,
+	/// Amount of liquidity that can still be removed
+	pub remove_liquidity: Option<Balance>,
 }
 
 // Re-export pallet items so that they can be accessed from the crate namespace.
@@ -141,6 +233,7 @@ pub mod pallet {
 			let _ = <AllowedTradeVolumeLimitPerAsset<T>>::clear(u32::MAX, None);
 			let _ = <AllowedAddLiquidityAmountPerAsset<T>>::clear(u32::MAX, None);
 			let _ = <AllowedRemoveLiquidityAmountPerAsset<T>>::clear(u32::MAX, None);
+			let _ = <OpeningPricePerAsset<T>>::clear(u32::MAX, None);
 		}
 
 		fn integrity_test() {
@@ -162,6 +255,13 @@ pub mod pallet {
 					"Circuit Breaker: Max remove liquidity limit per block is set to invalid value."
 				);
 			}
+
+			if let Some(price_change_limit) = T::DefaultMaxPriceChangePerBlock::get() {
+				assert!(
+					Self::validate_limit(price_change_limit).is_ok(),
+					"Circuit Breaker: Max price change per block is set to invalid value."
+				);
+			}
 		}
 	}
 
@@ -217,9 +317,43 @@ pub mod pallet {
 		#[pallet::constant]
 		type DefaultMaxRemoveLiquidityLimitPerBlock: Get<Option<(u32, u32)>>;
 
+		/// The maximum percentage a spot price of an asset can move in a block, measured from the price at the
+		/// start of the block.
+		/// Represented as an optional non-zero fraction (nominator, denominator) with the max value being 10_000.
+		/// If set to None, the limits are not enforced.
+		#[pallet::constant]
+		type DefaultMaxPriceChangePerBlock: Get<Option<(u32, u32)>>;
+
 		/// Omnipool's hub asset id. The limits are not tracked for this asset.
 		type OmnipoolHubAsset: Get<Self::AssetId>;
 
+		/// Handler restricting trading of assets halted after repeated limit hits.
+		type AssetHaltHandler: AssetHaltHandler<Self::AssetId>;
+
+		/// Provides assets of calls whose failures are counted as limit hits.
+		type LimitedAssets: LimitedAssets<<Self as frame_system::Config>::RuntimeCall, Self::AssetId>;
+
+		/// Number of limit hits of an asset within `LimitHitsPeriod` after which the asset is halted.
+		/// If set to zero, assets are never halted.
+		#[pallet::constant]
+		type MaxLimitHits: Get<u32>;
+
+		/// Length of the period in blocks in which limit hits of an asset are counted.
+		#[pallet::constant]
+		type LimitHitsPeriod: Get<Self::BlockNumber>;
+
+		/// Currency in which the limit hit fee is paid.
+		type Currency: Currency<Self::AccountId, Balance = Self::Balance>;
+
+		/// Fee paid by the signer of a call for each limit hit counted from the call, on top of the transaction fee.
+		/// It is charged from every signer whose call fails on a limit, including accidental hits.
+		/// Limit hits are not counted if the fee cannot be paid. No fee is charged when `MaxLimitHits` is zero.
+		#[pallet::constant]
+		type LimitHitFee: Get<Self::Balance>;
+
+		/// Account receiving limit hit fees.
+		type LimitHitFeeReceiver: Get<Self::AccountId>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -285,6 +419,66 @@ pub mod pallet {
 	pub type AllowedRemoveLiquidityAmountPerAsset<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AssetId, LiquidityLimit<T>>;
 
+	/// Default maximum price change per block
+	#[pallet::type_value]
+	pub fn DefaultPriceChangeLimit<T: Config>() -> Option<(u32, u32)> {
+		T::DefaultMaxPriceChangePerBlock::get()
+	}
+
+	#[pallet::storage]
+	/// Price change limits of assets per block.
+	/// If not set, returns the default limit.
+	#[pallet::getter(fn price_change_limit_per_asset)]
+	pub type PriceChangeLimitPerAsset<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, Option<(u32, u32)>, ValueQuery, DefaultPriceChangeLimit<T>>;
+
+	#[pallet::storage]
+	/// Spot prices of assets at the start of the block, recorded before the first trade in the block
+	#[pallet::getter(fn opening_price_per_asset)]
+	pub type OpeningPricePerAsset<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, FixedU128>;
+
+	#[pallet::storage]
+	/// Rolling window limits of assets set by set_window_limits.
+	/// If not set, only per-block limits are enforced.
+	#[pallet::getter(fn window_limits_per_asset)]
+	pub type WindowLimitsPerAsset<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, WindowLimits>;
+
+	#[pallet::storage]
+	/// Trade volumes per asset in the rolling window
+	#[pallet::getter(fn allowed_trade_volume_limit_per_window)]
+	pub type AllowedTradeVolumeLimitPerWindow<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, WindowTradeVolume<T>>;
+
+	#[pallet::storage]
+	/// Add liquidity volumes per asset in the rolling window
+	#[pallet::getter(fn allowed_add_liquidity_limit_per_window)]
+	pub type AllowedAddLiquidityAmountPerWindow<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, WindowLiquidity<T>>;
+
+	#[pallet::storage]
+	/// Remove liquidity volumes per asset in the rolling window
+	#[pallet::getter(fn allowed_remove_liquidity_limit_per_window)]
+	pub type AllowedRemoveLiquidityAmountPerWindow<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, WindowLiquidity<T>>;
+
+	#[pallet::storage]
+	/// Limit hits per asset counted in the current period
+	#[pallet::getter(fn limit_hits_per_asset)]
+	pub type LimitHitsPerAsset<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, LimitHits<T::BlockNumber>>;
+
+	#[pallet::storage]
+	/// Assets halted after repeated limit hits, with the block in which they were halted
+	/// and the state restored when they are resumed.
+	#[pallet::getter(fn halted_assets)]
+	pub type HaltedAssets<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, HaltedAsset<T::BlockNumber, HaltStateOf<T>>>;
+
+	#[pallet::storage]
+	/// Accounts whitelisted by `TechnicalOrigin`, with an optional block number at which the entry expires.
+	/// Expired entries are ignored but kept until removed.
+	#[pallet::getter(fn whitelist)]
+	pub type Whitelist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Option<T::BlockNumber>>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -303,6 +497,29 @@ pub mod pallet {
 			asset_id: T::AssetId,
 			liquidity_limit: Option<(u32, u32)>,
 		},
+		/// Rolling window limits of an asset were changed.
+		WindowLimitsChanged {
+			asset_id: T::AssetId,
+			window_limits: Option<WindowLimits>,
+		},
+		/// Price change limit of an asset was changed.
+		PriceChangeLimitChanged {
+			asset_id: T::AssetId,
+			price_change_limit: Option<(u32, u32)>,
+		},
+		/// A call was rejected because a limit of an asset was reached.
+		LimitHitRecorded { asset_id: T::AssetId, hits: u32 },
+		/// Asset was halted after its limits were hit repeatedly.
+		AssetHalted { asset_id: T::AssetId },
+		/// Halted asset was resumed.
+		AssetResumed { asset_id: T::AssetId },
+		/// Account was added to the whitelist or its expiry was changed.
+		AccountWhitelisted {
+			who: T::AccountId,
+			expires_at: Option<T::BlockNumber>,
+		},
+		/// Account was removed from the whitelist.
+		AccountRemovedFromWhitelist { who: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -320,6 +537,24 @@ pub mod pallet {
 		MaxLiquidityLimitPerBlockReached,
 		/// Asset is not allowed to have a limit
 		NotAllowed,
+		/// Invalid length of a rolling window. Window must be at least one block long.
+		InvalidWindowLength,
+		/// Token trade outflow per rolling window has been reached
+		TokenOutflowWindowLimitReached,
+		/// Token trade influx per rolling window has been reached
+		TokenInfluxWindowLimitReached,
+		/// Maximum pool's liquidity limit per rolling window has been reached
+		MaxLiquidityLimitPerWindowReached,
+		/// Asset is not halted
+		AssetNotHalted,
+		/// Maximum price increase of an asset per block has been reached
+		MaxPriceIncreasePerBlockReached,
+		/// Maximum price decrease of an asset per block has been reached
+		MaxPriceDecreasePerBlockReached,
+		/// Account is not whitelisted
+		AccountNotWhitelisted,
+		/// Expiry of a whitelist entry must be in the future
+		InvalidExpiry,
 	}
 
 	#[pallet::call]
@@ -423,6 +658,174 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Set rolling window limits for an asset.
+		///
+		/// Window limits are enforced in addition to the per-block limits. Volumes accumulated in the window
+		/// decay proportionally to the blocks elapsed since the last update. Setting new limits resets
+		/// the accumulated volumes.
+		///
+		/// Parameters:
+		/// - `origin`: The dispatch origin for this call. Must be `TechnicalOrigin`
+		/// - `asset_id`: The identifier of an asset
+		/// - `window_limits`: Optional window length and limits represented as percentages
+		///
+		/// Emits `WindowLimitsChanged` event when successful.
+		///
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_window_limits())]
+		pub fn set_window_limits(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			window_limits: Option<WindowLimits>,
+		) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
+
+			ensure!(asset_id != T::OmnipoolHubAsset::get(), Error::<T>::NotAllowed);
+
+			if let Some(limits) = window_limits {
+				ensure!(!limits.blocks.is_zero(), Error::<T>::InvalidWindowLength);
+
+				for limit in [
+					limits.trade_volume_limit,
+					limits.add_liquidity_limit,
+					limits.remove_liquidity_limit,
+				]
+				.into_iter()
+				.flatten()
+				{
+					Self::validate_limit(limit)?;
+				}
+			}
+
+			<WindowLimitsPerAsset<T>>::set(asset_id, window_limits);
+			<AllowedTradeVolumeLimitPerWindow<T>>::remove(asset_id);
+			<AllowedAddLiquidityAmountPerWindow<T>>::remove(asset_id);
+			<AllowedRemoveLiquidityAmountPerWindow<T>>::remove(asset_id);
+
+			Self::deposit_event(Event::WindowLimitsChanged {
+				asset_id,
+				window_limits,
+			});
+
+			Ok(())
+		}
+
+		/// Resume an asset halted after repeated limit hits.
+		///
+		/// Lifts the trading restrictions of the asset, restores its state from before the halt
+		/// and resets its limit hits.
+		///
+		/// Parameters:
+		/// - `origin`: The dispatch origin for this call. Must be `TechnicalOrigin`
+		/// - `asset_id`: The identifier of a halted asset
+		///
+		/// Emits `AssetResumed` event when successful.
+		///
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as Config>::WeightInfo::resume_asset().saturating_add(T::AssetHaltHandler::on_resume_weight()))]
+		pub fn resume_asset(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
+
+			let halted_asset = Self::halted_assets(asset_id).ok_or(Error::<T>::AssetNotHalted)?;
+
+			T::AssetHaltHandler::on_resume(asset_id, halted_asset.previous_state)?;
+
+			<HaltedAssets<T>>::remove(asset_id);
+			<LimitHitsPerAsset<T>>::remove(asset_id);
+
+			Self::deposit_event(Event::AssetResumed { asset_id });
+
+			Ok(())
+		}
+
+		/// Set price change limit for an asset.
+		///
+		/// Parameters:
+		/// - `origin`: The dispatch origin for this call. Must be `TechnicalOrigin`
+		/// - `asset_id`: The identifier of an asset
+		/// - `price_change_limit`: Optional price change limit per block represented as a percentage
+		///
+		/// Emits `PriceChangeLimitChanged` event when successful.
+		///
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_price_change_limit())]
+		pub fn set_price_change_limit(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			price_change_limit: Option<(u32, u32)>,
+		) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
+
+			ensure!(asset_id != T::OmnipoolHubAsset::get(), Error::<T>::NotAllowed);
+
+			if let Some(limit) = price_change_limit {
+				Self::validate_limit(limit)?;
+			}
+
+			<PriceChangeLimitPerAsset<T>>::insert(asset_id, price_change_limit);
+
+			Self::deposit_event(Event::PriceChangeLimitChanged {
+				asset_id,
+				price_change_limit,
+			});
+
+			Ok(())
+		}
+
+		/// Add an account to the whitelist or change the expiry of its entry.
+		///
+		/// Parameters:
+		/// - `origin`: The dispatch origin for this call. Must be `TechnicalOrigin`
+		/// - `who`: Account to whitelist
+		/// - `expires_at`: Optional block number from which the account is no longer whitelisted
+		///
+		/// Emits `AccountWhitelisted` event when successful.
+		///
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as Config>::WeightInfo::add_to_whitelist())]
+		pub fn add_to_whitelist(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
+
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					expires_at > frame_system::Pallet::<T>::block_number(),
+					Error::<T>::InvalidExpiry
+				);
+			}
+
+			<Whitelist<T>>::insert(&who, expires_at);
+
+			Self::deposit_event(Event::AccountWhitelisted { who, expires_at });
+
+			Ok(())
+		}
+
+		/// Remove an account from the whitelist.
+		///
+		/// Parameters:
+		/// - `origin`: The dispatch origin for this call. Must be `TechnicalOrigin`
+		/// - `who`: Whitelisted account
+		///
+		/// Emits `AccountRemovedFromWhitelist` event when successful.
+		///
+		#[pallet::call_index(7)]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_from_whitelist())]
+		pub fn remove_from_whitelist(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
+
+			ensure!(<Whitelist<T>>::contains_key(&who), Error::<T>::AccountNotWhitelisted);
+
+			<Whitelist<T>>::remove(&who);
+
+			Self::deposit_event(Event::AccountRemovedFromWhitelist { who });
+
+			Ok(())
+		}
 	}
 }
 
@@ -544,6 +947,177 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Number of blocks of the window remaining since `updated_at`, or None if the whole window has passed.
+	fn remaining_window_blocks(updated_at: T::BlockNumber, window_blocks: u32) -> Option<u32> {
+		Self::remaining_window_blocks_at(updated_at, window_blocks, frame_system::Pallet::<T>::block_number())
+	}
+
+	/// Number of blocks of the window remaining at `block_number` since `updated_at`,
+	/// or None if the whole window has passed.
+	fn remaining_window_blocks_at(
+		updated_at: T::BlockNumber,
+		window_blocks: u32,
+		block_number: T::BlockNumber,
+	) -> Option<u32> {
+		let elapsed: u32 = block_number.saturating_sub(updated_at).saturated_into();
+
+		window_blocks
+			.checked_sub(elapsed)
+			.filter(|remaining| !remaining.is_zero())
+	}
+
+	fn ensure_and_update_trade_volume_window_limit(
+		asset_id: T::AssetId,
+		asset_reserve: T::Balance,
+		amount_in: T::Balance,
+		amount_out: T::Balance,
+	) -> DispatchResult {
+		// ignore Omnipool's hub asset
+		if asset_id == T::OmnipoolHubAsset::get() {
+			return Ok(());
+		}
+
+		let (window_blocks, trade_volume_limit) = match Pallet::<T>::window_limits_per_asset(asset_id) {
+			Some(WindowLimits {
+				blocks,
+				trade_volume_limit: Some(limit),
+				..
+			}) => (blocks, limit),
+			_ => return Ok(()),
+		};
+
+		let window = Pallet::<T>::allowed_trade_volume_limit_per_window(asset_id).and_then(|window| {
+			Self::remaining_window_blocks(window.updated_at, window_blocks).map(|remaining| (window, remaining))
+		});
+
+		let mut window = match window {
+			Some((mut window, remaining)) => {
+				window.volume.decay((remaining, window_blocks))?;
+				window.updated_at = frame_system::Pallet::<T>::block_number();
+				window
+			}
+			None => WindowTradeVolume::<T> {
+				volume: TradeVolumeLimit::<T> {
+					limit: Self::calculate_limit(asset_reserve, trade_volume_limit)?,
+					volume_in: Zero::zero(),
+					volume_out: Zero::zero(),
+				},
+				updated_at: frame_system::Pallet::<T>::block_number(),
+			},
+		};
+		window.volume.update_amounts(amount_in, amount_out)?;
+		window
+			.volume
+			.check_outflow_limit()
+			.map_err(|_| Error::<T>::TokenOutflowWindowLimitReached)?;
+		window
+			.volume
+			.check_influx_limit()
+			.map_err(|_| Error::<T>::TokenInfluxWindowLimitReached)?;
+
+		<AllowedTradeVolumeLimitPerWindow<T>>::insert(asset_id, window);
+
+		Ok(())
+	}
+
+	/// Decay liquidity accumulated in the window, or start a new window if the previous one has passed,
+	/// and add `liquidity` to it.
+	fn update_liquidity_window(
+		window: Option<WindowLiquidity<T>>,
+		window_blocks: u32,
+		liquidity_limit: (u32, u32),
+		initial_liquidity: T::Balance,
+		liquidity: T::Balance,
+	) -> Result<WindowLiquidity<T>, DispatchError> {
+		let window = window.and_then(|window| {
+			Self::remaining_window_blocks(window.updated_at, window_blocks).map(|remaining| (window, remaining))
+		});
+
+		let mut window = match window {
+			Some((mut window, remaining)) => {
+				window.liquidity.decay((remaining, window_blocks))?;
+				window.updated_at = frame_system::Pallet::<T>::block_number();
+				window
+			}
+			None => WindowLiquidity::<T> {
+				liquidity: LiquidityLimit::<T> {
+					limit: Self::calculate_limit(initial_liquidity, liquidity_limit)?,
+					liquidity: Zero::zero(),
+				},
+				updated_at: frame_system::Pallet::<T>::block_number(),
+			},
+		};
+		window.liquidity.update_amount(liquidity)?;
+		window
+			.liquidity
+			.check_limit()
+			.map_err(|_| Error::<T>::MaxLiquidityLimitPerWindowReached)?;
+
+		Ok(window)
+	}
+
+	fn ensure_and_update_add_liquidity_window_limit(
+		asset_id: T::AssetId,
+		initial_liquidity: T::Balance,
+		added_liquidity: T::Balance,
+	) -> DispatchResult {
+		if asset_id == T::OmnipoolHubAsset::get() {
+			return Ok(());
+		}
+
+		if let Some(WindowLimits {
+			blocks,
+			add_liquidity_limit: Some(limit),
+			..
+		}) = Pallet::<T>::window_limits_per_asset(asset_id)
+		{
+			let window = Self::update_liquidity_window(
+				Pallet::<T>::allowed_add_liquidity_limit_per_window(asset_id),
+				blocks,
+				limit,
+				initial_liquidity,
+				added_liquidity,
+			)?;
+
+			<AllowedAddLiquidityAmountPerWindow<T>>::insert(asset_id, window);
+		}
+
+		Ok(())
+	}
+
+	fn ensure_and_update_remove_liquidity_window_limit(
+		asset_id: T::AssetId,
+		initial_liquidity: T::Balance,
+		removed_liquidity: T::Balance,
+	) -> DispatchResult {
+		if asset_id == T::OmnipoolHubAsset::get() {
+			return Ok(());
+		}
+
+		if let Some(WindowLimits {
+			blocks,
+			remove_liquidity_limit: Some(limit),
+			..
+		}) = Pallet::<T>::window_limits_per_asset(asset_id)
+		{
+			let window = Self::update_liquidity_window(
+				Pallet::<T>::allowed_remove_liquidity_limit_per_window(asset_id),
+				blocks,
+				limit,
+				initial_liquidity,
+				removed_liquidity,
+			)?;
+
+			<AllowedRemoveLiquidityAmountPerWindow<T>>::insert(asset_id, window);
+		}
+
+		Ok(())
+	}
+
+	fn spot_price(reserve: T::Balance, hub_reserve: T::Balance) -> Option<FixedU128> {
+		FixedU128::checked_from_rational(hub_reserve.saturated_into::<u128>(), reserve.saturated_into::<u128>())
+	}
+
 	pub fn validate_limit(limit: (u32, u32)) -> DispatchResult {
 		let (numerator, denominator) = (limit.0, limit.1);
 		ensure!(
@@ -580,10 +1154,65 @@ impl<T: Config> Pallet<T> {
 		Pallet::<T>::initialize_trade_limit(asset_in, asset_in_reserve)?;
 		Pallet::<T>::initialize_trade_limit(asset_out, asset_out_reserve)?;
 		Pallet::<T>::ensure_and_update_trade_volume_limit(asset_in, amount_in, asset_out, amount_out)?;
+		Pallet::<T>::ensure_and_update_trade_volume_window_limit(asset_in, asset_in_reserve, amount_in, Zero::zero())?;
+		Pallet::<T>::ensure_and_update_trade_volume_window_limit(
+			asset_out,
+			asset_out_reserve,
+			Zero::zero(),
+			amount_out,
+		)?;
 
 		Ok(T::WeightInfo::ensure_pool_state_change_limit())
 	}
 
+	/// Ensure the spot price of an asset after a trade is within the price change limit of the asset.
+	///
+	/// The price change is measured from the price before the first trade of the asset in the block.
+	pub fn ensure_price_change_limit(
+		asset_id: T::AssetId,
+		reserve_before: T::Balance,
+		hub_reserve_before: T::Balance,
+		reserve_after: T::Balance,
+		hub_reserve_after: T::Balance,
+	) -> Result<Weight, DispatchError> {
+		// ignore Omnipool's hub asset
+		if asset_id == T::OmnipoolHubAsset::get() {
+			return Ok(T::WeightInfo::ensure_price_change_limit());
+		}
+
+		if let Some(limit) = Pallet::<T>::price_change_limit_per_asset(asset_id) {
+			let opening_price = match Pallet::<T>::opening_price_per_asset(asset_id) {
+				Some(price) => Some(price),
+				None => Self::spot_price(reserve_before, hub_reserve_before).map(|price| {
+					<OpeningPricePerAsset<T>>::insert(asset_id, price);
+					price
+				}),
+			};
+
+			// prices can't be calculated for empty pools
+			if let (Some(opening_price), Some(price)) =
+				(opening_price, Self::spot_price(reserve_after, hub_reserve_after))
+			{
+				let price_change = price.max(opening_price).saturating_sub(price.min(opening_price));
+				let max_price_change = opening_price.saturating_mul(
+					FixedU128::checked_from_rational(limit.0, limit.1).ok_or(ArithmeticError::DivisionByZero)?,
+				);
+
+				if price_change > max_price_change {
+					// price of an asset sold to the pool decreases, price of an asset bought from the pool increases
+					return Err(if price > opening_price {
+						Error::<T>::MaxPriceIncreasePerBlockReached
+					} else {
+						Error::<T>::MaxPriceDecreasePerBlockReached
+					}
+					.into());
+				}
+			}
+		}
+
+		Ok(T::WeightInfo::ensure_price_change_limit())
+	}
+
 	pub fn ensure_add_liquidity_limit(
 		origin: OriginFor<T>,
 		asset_id: T::AssetId,
@@ -597,6 +1226,7 @@ impl<T: Config> Pallet<T> {
 
 		Pallet::<T>::calculate_and_store_liquidity_limits(asset_id, initial_liquidity)?;
 		Pallet::<T>::ensure_and_update_add_liquidity_limit(asset_id, added_liquidity)?;
+		Pallet::<T>::ensure_and_update_add_liquidity_window_limit(asset_id, initial_liquidity, added_liquidity)?;
 
 		Ok(T::WeightInfo::ensure_add_liquidity_limit())
 	}
@@ -614,10 +1244,290 @@ impl<T: Config> Pallet<T> {
 
 		Pallet::<T>::calculate_and_store_liquidity_limits(asset_id, initial_liquidity)?;
 		Pallet::<T>::ensure_and_update_remove_liquidity_limit(asset_id, removed_liquidity)?;
+		Pallet::<T>::ensure_and_update_remove_liquidity_window_limit(asset_id, initial_liquidity, removed_liquidity)?;
 
 		Ok(T::WeightInfo::ensure_remove_liquidity_limit())
 	}
 
+	/// Calculate the headroom of an asset for the next block.
+	///
+	/// Amounts accumulated in the per-block limits are reset at the end of each block, so the per-block part
+	/// is calculated from `asset_reserve`, the current reserve of the asset in the pool.
+	/// The result is capped by what is left of the rolling window limits, decayed to the next block.
+	pub fn remaining_allowance(
+		asset_id: T::AssetId,
+		asset_reserve: T::Balance,
+	) -> Result<RemainingAllowance<T::Balance>, DispatchError> {
+		Self::remaining_allowance_at(
+			asset_id,
+			asset_reserve,
+			frame_system::Pallet::<T>::block_number().saturating_add(One::one()),
+		)
+	}
+
+	/// Calculate the headroom of an asset for the next trade or liquidity change in the current block.
+	///
+	/// Amounts accumulated in the per-block limits in the current block are taken into account.
+	/// `asset_reserve` is used to calculate the limits when the asset has not been traded in the block yet.
+	/// The result is capped by what is left of the rolling window limits.
+	pub fn remaining_allowance_in_block(
+		asset_id: T::AssetId,
+		asset_reserve: T::Balance,
+	) -> Result<RemainingAllowance<T::Balance>, DispatchError> {
+		Self::remaining_allowance_at(asset_id, asset_reserve, frame_system::Pallet::<T>::block_number())
+	}
+
+	fn remaining_allowance_at(
+		asset_id: T::AssetId,
+		asset_reserve: T::Balance,
+		block_number: T::BlockNumber,
+	) -> Result<RemainingAllowance<T::Balance>, DispatchError> {
+		// the limits are not tracked for Omnipool's hub asset
+		if asset_id == T::OmnipoolHubAsset::get() {
+			return Ok(RemainingAllowance {
+				inflow: None,
+				outflow: None,
+				add_liquidity: None,
+				remove_liquidity: None,
+			});
+		}
+
+		// amounts stored in the per-block limits belong to the current block only
+		let in_current_block = block_number == frame_system::Pallet::<T>::block_number();
+
+		let (inflow, outflow) = match Pallet::<T>::allowed_trade_volume_limit_per_asset(asset_id)
+			.filter(|_| in_current_block)
+		{
+			Some(volume) => Self::remaining_trade_volume(&volume),
+			None => {
+				let limit = Self::calculate_limit(asset_reserve, Pallet::<T>::trade_volume_limit_per_asset(asset_id))?;
+				(limit, limit)
+			}
+		};
+
+		let add_liquidity = Pallet::<T>::add_liquidity_limit_per_asset(asset_id)
+			.map(|limit| {
+				Self::remaining_liquidity(
+					Pallet::<T>::allowed_add_liquidity_limit_per_asset(asset_id).filter(|_| in_current_block),
+					asset_reserve,
+					limit,
+				)
+			})
+			.transpose()?;
+
+		let remove_liquidity = Pallet::<T>::remove_liquidity_limit_per_asset(asset_id)
+			.map(|limit| {
+				Self::remaining_liquidity(
+					Pallet::<T>::allowed_remove_liquidity_limit_per_asset(asset_id).filter(|_| in_current_block),
+					asset_reserve,
+					limit,
+				)
+			})
+			.transpose()?;
+
+		let mut allowance = RemainingAllowance {
+			inflow: Some(inflow),
+			outflow: Some(outflow),
+			add_liquidity,
+			remove_liquidity,
+		};
+
+		let window_limits = match Pallet::<T>::window_limits_per_asset(asset_id) {
+			Some(window_limits) => window_limits,
+			None => return Ok(allowance),
+		};
+
+		if let Some(limit) = window_limits.trade_volume_limit {
+			let volume = match Pallet::<T>::allowed_trade_volume_limit_per_window(asset_id).and_then(|window| {
+				Self::remaining_window_blocks_at(window.updated_at, window_limits.blocks, block_number)
+					.map(|remaining| (window, remaining))
+			}) {
+				Some((mut window, remaining)) => {
+					window.volume.decay((remaining, window_limits.blocks))?;
+					Self::remaining_trade_volume(&window.volume)
+				}
+				None => {
+					let limit = Self::calculate_limit(asset_reserve, limit)?;
+					(limit, limit)
+				}
+			};
+			allowance.inflow = Self::min_allowance(allowance.inflow, volume.0);
+			allowance.outflow = Self::min_allowance(allowance.outflow, volume.1);
+		}
+
+		if let Some(limit) = window_limits.add_liquidity_limit {
+			let liquidity = Self::remaining_window_liquidity(
+				Pallet::<T>::allowed_add_liquidity_limit_per_window(asset_id),
+				window_limits.blocks,
+				limit,
+				asset_reserve,
+				block_number,
+			)?;
+			allowance.add_liquidity = Self::min_allowance(allowance.add_liquidity, liquidity);
+		}
+
+		if let Some(limit) = window_limits.remove_liquidity_limit {
+			let liquidity = Self::remaining_window_liquidity(
+				Pallet::<T>::allowed_remove_liquidity_limit_per_window(asset_id),
+				window_limits.blocks,
+				limit,
+				asset_reserve,
+				block_number,
+			)?;
+			allowance.remove_liquidity = Self::min_allowance(allowance.remove_liquidity, liquidity);
+		}
+
+		Ok(allowance)
+	}
+
+	/// Net amounts (inflow, outflow) that can still be traded within the limit of `volume`.
+	fn remaining_trade_volume(volume: &TradeVolumeLimit<T>) -> (T::Balance, T::Balance) {
+		(
+			volume
+				.limit
+				.saturating_add(volume.volume_out)
+				.saturating_sub(volume.volume_in),
+			volume
+				.limit
+				.saturating_add(volume.volume_in)
+				.saturating_sub(volume.volume_out),
+		)
+	}
+
+	fn remaining_liquidity(
+		allowed_liquidity: Option<LiquidityLimit<T>>,
+		asset_reserve: T::Balance,
+		limit: (u32, u32),
+	) -> Result<T::Balance, DispatchError> {
+		match allowed_liquidity {
+			Some(allowed_liquidity) => Ok(allowed_liquidity.limit.saturating_sub(allowed_liquidity.liquidity)),
+			None => Self::calculate_limit(asset_reserve, limit),
+		}
+	}
+
+	/// Liquidity that can still be added or removed within the window at `block_number`.
+	fn remaining_window_liquidity(
+		window: Option<WindowLiquidity<T>>,
+		window_blocks: u32,
+		limit: (u32, u32),
+		asset_reserve: T::Balance,
+		block_number: T::BlockNumber,
+	) -> Result<T::Balance, DispatchError> {
+		match window.and_then(|window| {
+			Self::remaining_window_blocks_at(window.updated_at, window_blocks, block_number)
+				.map(|remaining| (window, remaining))
+		}) {
+			Some((mut window, remaining)) => {
+				window.liquidity.decay((remaining, window_blocks))?;
+				Ok(window.liquidity.limit.saturating_sub(window.liquidity.liquidity))
+			}
+			None => Self::calculate_limit(asset_reserve, limit),
+		}
+	}
+
+	fn min_allowance(allowance: Option<T::Balance>, window_allowance: T::Balance) -> Option<T::Balance> {
+		Some(allowance.map_or(window_allowance, |allowance| allowance.min(window_allowance)))
+	}
+
+	/// Count a limit hit of an asset. The asset is halted when `MaxLimitHits` hits are counted within `LimitHitsPeriod`.
+	pub fn note_limit_hit(asset_id: T::AssetId) {
+		if !Self::counts_limit_hits(asset_id) {
+			return;
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut hits = match Self::limit_hits_per_asset(asset_id) {
+			Some(hits) if now < hits.period_start.saturating_add(T::LimitHitsPeriod::get()) => hits,
+			_ => LimitHits {
+				count: 0,
+				period_start: now,
+			},
+		};
+		hits.count.saturating_inc();
+
+		Self::deposit_event(Event::LimitHitRecorded {
+			asset_id,
+			hits: hits.count,
+		});
+
+		if hits.count >= T::MaxLimitHits::get() {
+			// the asset stays tradable if the handler fails to halt it, so that it can be retried with the next hit
+			if let Ok(previous_state) =
+				frame_support::storage::with_storage_layer(|| T::AssetHaltHandler::on_halt(asset_id))
+			{
+				<LimitHitsPerAsset<T>>::remove(asset_id);
+				<HaltedAssets<T>>::insert(
+					asset_id,
+					HaltedAsset {
+						halted_at: now,
+						previous_state,
+					},
+				);
+
+				Self::deposit_event(Event::AssetHalted { asset_id });
+				return;
+			}
+		}
+
+		<LimitHitsPerAsset<T>>::insert(asset_id, hits);
+	}
+
+	/// Whether limit hits of an asset are counted. They are not counted for Omnipool's hub asset,
+	/// for already halted assets and when halting is disabled.
+	fn counts_limit_hits(asset_id: T::AssetId) -> bool {
+		asset_id != T::OmnipoolHubAsset::get()
+			&& !T::MaxLimitHits::get().is_zero()
+			&& !<HaltedAssets<T>>::contains_key(asset_id)
+	}
+
+	/// Charge `LimitHitFee` from the account whose call hit a limit.
+	pub fn charge_limit_hit_fee(who: &T::AccountId) -> DispatchResult {
+		T::Currency::transfer(
+			who,
+			&T::LimitHitFeeReceiver::get(),
+			T::LimitHitFee::get(),
+			ExistenceRequirement::KeepAlive,
+		)
+	}
+
+	pub fn note_limit_hit_weight() -> Weight {
+		T::WeightInfo::note_limit_hit().saturating_add(T::AssetHaltHandler::on_halt_weight())
+	}
+
+	/// Returns the asset whose limit was reached if `error` is one of the limit errors.
+	fn limited_asset_of_error(
+		error: &DispatchError,
+		asset_in: T::AssetId,
+		asset_out: T::AssetId,
+	) -> Option<T::AssetId> {
+		let is = |e: Error<T>| *error == DispatchError::from(e);
+
+		if is(Error::<T>::TokenInfluxLimitReached)
+			|| is(Error::<T>::TokenInfluxWindowLimitReached)
+			|| is(Error::<T>::MaxLiquidityLimitPerBlockReached)
+			|| is(Error::<T>::MaxLiquidityLimitPerWindowReached)
+			|| is(Error::<T>::MaxPriceDecreasePerBlockReached)
+		{
+			Some(asset_in)
+		} else if is(Error::<T>::TokenOutflowLimitReached)
+			|| is(Error::<T>::TokenOutflowWindowLimitReached)
+			|| is(Error::<T>::MaxPriceIncreasePerBlockReached)
+		{
+			Some(asset_out)
+		} else {
+			None
+		}
+	}
+
+	/// Whether `who` has a whitelist entry which has not expired yet.
+	pub fn is_whitelisted(who: &T::AccountId) -> bool {
+		match Pallet::<T>::whitelist(who) {
+			Some(Some(expires_at)) => frame_system::Pallet::<T>::block_number() < expires_at,
+			Some(None) => true,
+			None => false,
+		}
+	}
+
 	pub(crate) fn is_origin_whitelisted_or_root(origin: OriginFor<T>) -> Result<bool, DispatchError> {
 		let who = ensure_signed_or_root(origin)?;
 		match who {
@@ -636,3 +1546,88 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 }
+
+/// Accounts whitelisted in the `Whitelist` storage.
+/// Can be used as `WhitelistedAccounts` of this pallet or as a whitelist of other pallets.
+impl<T: Config> Contains<T::AccountId> for Pallet<T> {
+	fn contains(who: &T::AccountId) -> bool {
+		Self::is_whitelisted(who)
+	}
+}
+
+/// Signed extension counting limit hits of calls rejected by the circuit breaker.
+///
+/// Storage changes of a failed call are reverted, so the hits are counted after the dispatch
+/// from the returned error and the assets provided by `LimitedAssets`.
+///
+/// To make halting assets costly, a hit is only counted if the call paid its transaction fee
+/// and the signer paid `LimitHitFee`. The fee is charged on top of the transaction fee from every signer
+/// whose call fails on a limit, whether the hit was accidental or not. It is not charged for hits which are
+/// not counted - hits of Omnipool's hub asset or of halted assets, and all hits when `MaxLimitHits` is zero.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct TrackLimitHits<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for TrackLimitHits<T> {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "TrackLimitHits")
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for TrackLimitHits<T>
+where
+	<T as frame_system::Config>::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "TrackLimitHits";
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = Option<(T::AccountId, T::AssetId, T::AssetId)>;
+
+	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Ok(T::LimitedAssets::limited_assets(call).map(|(asset_in, asset_out)| (who.clone(), asset_in, asset_out)))
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		if let (Some(Some((who, asset_in, asset_out))), Err(error)) = (pre, result) {
+			if post_info.pays_fee(info) == Pays::No {
+				return Ok(());
+			}
+
+			if let Some(asset_id) = Pallet::<T>::limited_asset_of_error(error, asset_in, asset_out) {
+				if Pallet::<T>::counts_limit_hits(asset_id) && Pallet::<T>::charge_limit_hit_fee(&who).is_ok() {
+					Pallet::<T>::note_limit_hit(asset_id);
+				}
+				frame_system::Pallet::<T>::register_extra_weight_unchecked(
+					Pallet::<T>::note_limit_hit_weight(),
+					DispatchClass::Normal,
+				);
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> TrackLimitHits<T> {
+	#[cfg_attr(feature = "cargo-clippy", allow(clippy::new_without_default))]
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
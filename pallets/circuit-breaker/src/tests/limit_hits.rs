@@ -0,0 +1,483 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::*;
+use frame_support::assert_err;
+use frame_support::dispatch::{GetDispatchInfo, Pays, PostDispatchInfo};
+use frame_support::BoundedVec;
+use pallet_omnipool::types::Tradability;
+pub use pretty_assertions::{assert_eq, assert_ne};
+use sp_runtime::traits::Dispatchable;
+
+const INITIAL_POOL_LIQUIDITY: Balance = 10_000 * ONE;
+const NATIVE_BALANCE: Balance = 1_000 * ONE;
+
+fn with_omnipool() -> sp_io::TestExternalities {
+	with_omnipool_and_max_limit_hits(3)
+}
+
+fn with_omnipool_and_max_limit_hits(max_limit_hits: u32) -> sp_io::TestExternalities {
+	let mut ext = ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1_000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, DOT, 2_000_000 * ONE),
+			(LP1, ACA, 2_000_000 * ONE),
+			(TRADER, DOT, 2_000_000 * ONE),
+		])
+		.with_registered_asset(DOT)
+		.with_registered_asset(ACA)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(DOT, FixedU128::from_float(0.65), LP1, INITIAL_POOL_LIQUIDITY)
+		.with_token(ACA, FixedU128::from_float(0.65), LP1, INITIAL_POOL_LIQUIDITY)
+		.with_max_trade_volume_limit_per_block(TEN_PERCENT)
+		.with_max_limit_hits(max_limit_hits)
+		.build();
+
+	ext.execute_with(|| {
+		for who in [LP1, TRADER] {
+			assert_ok!(Balances::set_balance(RuntimeOrigin::root(), who, NATIVE_BALANCE, 0));
+		}
+	});
+
+	ext
+}
+
+fn halted_at(block: u64, previous_state: Tradability) -> Option<HaltedAsset<u64, Tradability>> {
+	Some(HaltedAsset {
+		halted_at: block,
+		previous_state,
+	})
+}
+
+/// Dispatch a signed call the way the executive does, including the `TrackLimitHits` extension.
+fn dispatch_signed(who: AccountId, call: RuntimeCall) -> DispatchResult {
+	let info = call.get_dispatch_info();
+	let pre = TrackLimitHits::<Test>::new()
+		.pre_dispatch(&who, &call, &info, 0)
+		.unwrap();
+
+	let (post_info, result) = match call.dispatch(RuntimeOrigin::signed(who)) {
+		Ok(post_info) => (post_info, Ok(())),
+		Err(e) => (e.post_info, Err(e.error)),
+	};
+
+	assert_ok!(TrackLimitHits::<Test>::post_dispatch(
+		Some(pre),
+		&info,
+		&post_info,
+		0,
+		&result
+	));
+
+	result
+}
+
+fn sell_over_limit() -> RuntimeCall {
+	let amount = CircuitBreaker::calculate_limit(INITIAL_POOL_LIQUIDITY, TEN_PERCENT).unwrap() + ONE;
+
+	RuntimeCall::Omnipool(pallet_omnipool::Call::sell {
+		asset_in: DOT,
+		asset_out: ACA,
+		amount,
+		min_buy_amount: 0,
+	})
+}
+
+#[test]
+fn note_limit_hit_should_count_hits_of_asset() {
+	with_omnipool().execute_with(|| {
+		// Act
+		CircuitBreaker::note_limit_hit(DOT);
+		CircuitBreaker::note_limit_hit(DOT);
+
+		// Assert
+		assert_eq!(
+			CircuitBreaker::limit_hits_per_asset(DOT).unwrap(),
+			LimitHits {
+				count: 2,
+				period_start: 1,
+			}
+		);
+		assert_eq!(CircuitBreaker::halted_assets(DOT), None);
+		expect_events(vec![crate::Event::LimitHitRecorded { asset_id: DOT, hits: 2 }.into()]);
+	});
+}
+
+#[test]
+fn asset_should_be_halted_when_max_limit_hits_are_reached() {
+	with_omnipool().execute_with(|| {
+		// Act
+		for _ in 0..3 {
+			CircuitBreaker::note_limit_hit(DOT);
+		}
+
+		// Assert
+		assert_eq!(CircuitBreaker::halted_assets(DOT), halted_at(1, Tradability::default()));
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT), None);
+		assert_eq!(
+			Omnipool::load_asset_state(DOT).unwrap().tradable,
+			Tradability::REMOVE_LIQUIDITY
+		);
+		expect_events(vec![
+			crate::Event::LimitHitRecorded { asset_id: DOT, hits: 3 }.into(),
+			pallet_omnipool::Event::TradableStateUpdated {
+				asset_id: DOT,
+				state: Tradability::REMOVE_LIQUIDITY,
+			}
+			.into(),
+			crate::Event::AssetHalted { asset_id: DOT }.into(),
+		]);
+	});
+}
+
+#[test]
+fn limit_hits_should_be_reset_when_period_has_passed() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		CircuitBreaker::note_limit_hit(DOT);
+		CircuitBreaker::note_limit_hit(DOT);
+
+		// Act
+		System::set_block_number(11);
+		CircuitBreaker::note_limit_hit(DOT);
+
+		// Assert
+		assert_eq!(
+			CircuitBreaker::limit_hits_per_asset(DOT).unwrap(),
+			LimitHits {
+				count: 1,
+				period_start: 11,
+			}
+		);
+		assert_eq!(CircuitBreaker::halted_assets(DOT), None);
+	});
+}
+
+#[test]
+fn note_limit_hit_should_ignore_omnipool_hub_asset() {
+	with_omnipool().execute_with(|| {
+		// Act
+		for _ in 0..3 {
+			CircuitBreaker::note_limit_hit(LRNA);
+		}
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(LRNA), None);
+		assert_eq!(CircuitBreaker::halted_assets(LRNA), None);
+	});
+}
+
+#[test]
+fn asset_should_not_be_halted_when_halt_handler_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Act
+		// asset is not in omnipool, so its tradable state cannot be updated
+		for _ in 0..3 {
+			CircuitBreaker::note_limit_hit(DOT);
+		}
+
+		// Assert
+		assert_eq!(CircuitBreaker::halted_assets(DOT), None);
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT).unwrap().count, 3);
+	});
+}
+
+#[test]
+fn failed_trade_should_count_limit_hit_of_asset_in_when_influx_limit_is_reached() {
+	with_omnipool().execute_with(|| {
+		// Act
+		assert_err!(
+			dispatch_signed(TRADER, sell_over_limit()),
+			Error::<Test>::TokenInfluxLimitReached
+		);
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT).unwrap().count, 1);
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(ACA), None);
+		assert_eq!(Balances::free_balance(TRADER), NATIVE_BALANCE - LimitHitFee::get());
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), LimitHitFee::get());
+	});
+}
+
+#[test]
+fn failed_trade_should_not_count_limit_hit_when_limit_hit_fee_cannot_be_paid() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		assert_ok!(Balances::set_balance(
+			RuntimeOrigin::root(),
+			TRADER,
+			LimitHitFee::get(),
+			0
+		));
+
+		// Act
+		assert_err!(
+			dispatch_signed(TRADER, sell_over_limit()),
+			Error::<Test>::TokenInfluxLimitReached
+		);
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT), None);
+		assert_eq!(Balances::free_balance(TRADER), LimitHitFee::get());
+	});
+}
+
+#[test]
+fn failed_trade_should_not_be_charged_limit_hit_fee_when_halting_is_disabled() {
+	with_omnipool_and_max_limit_hits(0).execute_with(|| {
+		// Act
+		assert_err!(
+			dispatch_signed(TRADER, sell_over_limit()),
+			Error::<Test>::TokenInfluxLimitReached
+		);
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT), None);
+		assert_eq!(Balances::free_balance(TRADER), NATIVE_BALANCE);
+		assert_eq!(Balances::free_balance(TreasuryAccount::get()), 0);
+	});
+}
+
+#[test]
+fn failed_call_should_not_count_limit_hit_when_transaction_fee_is_not_paid() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		let call = sell_over_limit();
+		let info = call.get_dispatch_info();
+		let pre = TrackLimitHits::<Test>::new()
+			.pre_dispatch(&TRADER, &call, &info, 0)
+			.unwrap();
+		let post_info = PostDispatchInfo {
+			actual_weight: None,
+			pays_fee: Pays::No,
+		};
+
+		// Act
+		assert_ok!(TrackLimitHits::<Test>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			0,
+			&Err(Error::<Test>::TokenInfluxLimitReached.into())
+		));
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT), None);
+		assert_eq!(Balances::free_balance(TRADER), NATIVE_BALANCE);
+	});
+}
+
+#[test]
+fn failed_add_liquidity_batch_should_count_limit_hit_of_asset_over_limit() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		let call = RuntimeCall::Omnipool(pallet_omnipool::Call::add_liquidity_batch {
+			assets: BoundedVec::truncate_from(vec![(DOT, 100 * ONE), (ACA, INITIAL_POOL_LIQUIDITY)]),
+		});
+
+		// Act
+		assert_err!(
+			dispatch_signed(LP1, call),
+			Error::<Test>::MaxLiquidityLimitPerBlockReached
+		);
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT), None);
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(ACA).unwrap().count, 1);
+	});
+}
+
+#[test]
+fn trading_should_be_halted_when_trades_hit_limits_repeatedly() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		for _ in 0..3 {
+			assert_err!(
+				dispatch_signed(TRADER, sell_over_limit()),
+				Error::<Test>::TokenInfluxLimitReached
+			);
+		}
+
+		// Act & Assert
+		assert_eq!(CircuitBreaker::halted_assets(DOT), halted_at(1, Tradability::default()));
+		assert_noop!(
+			Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, ONE, 0),
+			pallet_omnipool::Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn failed_trade_should_count_limit_hit_of_asset_whose_price_change_limit_is_reached() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_price_change_limit(
+			RuntimeOrigin::root(),
+			DOT,
+			Some(FIVE_PERCENT)
+		));
+
+		// Act
+		// selling DOT decreases its price by more than 5%
+		assert_err!(
+			dispatch_signed(
+				TRADER,
+				RuntimeCall::Omnipool(pallet_omnipool::Call::sell {
+					asset_in: DOT,
+					asset_out: ACA,
+					amount: 400 * ONE,
+					min_buy_amount: 0,
+				})
+			),
+			Error::<Test>::MaxPriceDecreasePerBlockReached
+		);
+		// buying DOT increases its price by more than 5%
+		assert_err!(
+			dispatch_signed(
+				LP1,
+				RuntimeCall::Omnipool(pallet_omnipool::Call::sell {
+					asset_in: ACA,
+					asset_out: DOT,
+					amount: 400 * ONE,
+					min_buy_amount: 0,
+				})
+			),
+			Error::<Test>::MaxPriceIncreasePerBlockReached
+		);
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT).unwrap().count, 2);
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(ACA), None);
+	});
+}
+
+#[test]
+fn failed_call_should_not_count_limit_hit_when_error_is_not_limit_error() {
+	with_omnipool().execute_with(|| {
+		// Act
+		assert_err!(
+			dispatch_signed(
+				TRADER,
+				RuntimeCall::Omnipool(pallet_omnipool::Call::sell {
+					asset_in: DOT,
+					asset_out: ACA,
+					amount: ONE,
+					min_buy_amount: 1_000 * ONE,
+				})
+			),
+			pallet_omnipool::Error::<Test>::BuyLimitNotReached
+		);
+
+		// Assert
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(DOT), None);
+		assert_eq!(CircuitBreaker::limit_hits_per_asset(ACA), None);
+	});
+}
+
+#[test]
+fn resume_asset_should_restore_trading_when_asset_is_halted() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		for _ in 0..3 {
+			CircuitBreaker::note_limit_hit(DOT);
+		}
+
+		// Act
+		assert_ok!(CircuitBreaker::resume_asset(RuntimeOrigin::root(), DOT));
+
+		// Assert
+		assert_eq!(CircuitBreaker::halted_assets(DOT), None);
+		assert_eq!(
+			Omnipool::load_asset_state(DOT).unwrap().tradable,
+			Tradability::default()
+		);
+		expect_events(vec![crate::Event::AssetResumed { asset_id: DOT }.into()]);
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, ONE, 0));
+	});
+}
+
+#[test]
+fn resume_asset_should_restore_tradable_state_from_before_halt() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		let state = Tradability::SELL | Tradability::REMOVE_LIQUIDITY;
+		assert_ok!(Omnipool::set_asset_tradable_state(RuntimeOrigin::root(), DOT, state));
+		for _ in 0..3 {
+			CircuitBreaker::note_limit_hit(DOT);
+		}
+		assert_eq!(CircuitBreaker::halted_assets(DOT), halted_at(1, state));
+		assert_eq!(
+			Omnipool::load_asset_state(DOT).unwrap().tradable,
+			Tradability::REMOVE_LIQUIDITY
+		);
+
+		// Act
+		assert_ok!(CircuitBreaker::resume_asset(RuntimeOrigin::root(), DOT));
+
+		// Assert
+		assert_eq!(Omnipool::load_asset_state(DOT).unwrap().tradable, state);
+	});
+}
+
+#[test]
+fn asset_should_not_be_halted_when_it_is_frozen() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		assert_ok!(Omnipool::set_asset_tradable_state(
+			RuntimeOrigin::root(),
+			DOT,
+			Tradability::FROZEN
+		));
+
+		// Act
+		for _ in 0..3 {
+			CircuitBreaker::note_limit_hit(DOT);
+		}
+
+		// Assert
+		assert_eq!(CircuitBreaker::halted_assets(DOT), None);
+		assert_eq!(Omnipool::load_asset_state(DOT).unwrap().tradable, Tradability::FROZEN);
+	});
+}
+
+#[test]
+fn resume_asset_should_fail_when_asset_is_not_halted() {
+	with_omnipool().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::resume_asset(RuntimeOrigin::root(), DOT),
+			Error::<Test>::AssetNotHalted
+		);
+	});
+}
+
+#[test]
+fn resume_asset_should_fail_when_not_signed_by_technical_origin() {
+	with_omnipool().execute_with(|| {
+		// Arrange
+		for _ in 0..3 {
+			CircuitBreaker::note_limit_hit(DOT);
+		}
+
+		// Act & Assert
+		assert_noop!(
+			CircuitBreaker::resume_asset(RuntimeOrigin::signed(ALICE), DOT),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
@@ -24,7 +24,7 @@ use frame_system::EnsureRoot;
 use hydra_dx_math::omnipool::types::BalanceUpdate;
 use orml_traits::parameter_type_with_key;
 use sp_core::H256;
-use sp_runtime::traits::{ConstU128, ConstU32};
+use sp_runtime::traits::{ConstU128, ConstU32, ConstU64};
 use sp_runtime::DispatchResult;
 use sp_runtime::FixedU128;
 use sp_runtime::Permill;
@@ -78,6 +78,8 @@ thread_local! {
 	pub static MAX_NET_TRADE_VOLUME_LIMIT_PER_BLOCK: RefCell<(u32, u32)> = RefCell::new((2_000, 10_000)); // 20%
 	pub static MAX_ADD_LIQUIDITY_LIMIT_PER_BLOCK: RefCell<Option<(u32, u32)>> = RefCell::new(Some((4_000, 10_000))); // 40%
 	pub static MAX_REMOVE_LIQUIDITY_LIMIT_PER_BLOCK: RefCell<Option<(u32, u32)>> = RefCell::new(Some((2_000, 10_000))); // 20%
+	pub static MAX_PRICE_CHANGE_PER_BLOCK: RefCell<Option<(u32, u32)>> = RefCell::new(None);
+	pub static MAX_LIMIT_HITS: RefCell<u32> = RefCell::new(3);
 }
 
 frame_support::construct_runtime!(
@@ -130,7 +132,10 @@ parameter_types! {
 	pub DefaultMaxNetTradeVolumeLimitPerBlock: (u32, u32) = MAX_NET_TRADE_VOLUME_LIMIT_PER_BLOCK.with(|v| *v.borrow());
 	pub DefaultMaxAddLiquidityLimitPerBlock: Option<(u32, u32)> = MAX_ADD_LIQUIDITY_LIMIT_PER_BLOCK.with(|v| *v.borrow());
 	pub DefaultMaxRemoveLiquidityLimitPerBlock: Option<(u32, u32)> = MAX_REMOVE_LIQUIDITY_LIMIT_PER_BLOCK.with(|v| *v.borrow());
+	pub DefaultMaxPriceChangePerBlock: Option<(u32, u32)> = MAX_PRICE_CHANGE_PER_BLOCK.with(|v| *v.borrow());
 	pub const OmnipoolHubAsset: AssetId = LRNA;
+	pub MaxLimitHits: u32 = MAX_LIMIT_HITS.with(|v| *v.borrow());
+	pub const LimitHitFee: Balance = 10 * ONE;
 }
 
 impl pallet_circuit_breaker::Config for Test {
@@ -142,7 +147,15 @@ impl pallet_circuit_breaker::Config for Test {
 	type DefaultMaxNetTradeVolumeLimitPerBlock = DefaultMaxNetTradeVolumeLimitPerBlock;
 	type DefaultMaxAddLiquidityLimitPerBlock = DefaultMaxAddLiquidityLimitPerBlock;
 	type DefaultMaxRemoveLiquidityLimitPerBlock = DefaultMaxRemoveLiquidityLimitPerBlock;
+	type DefaultMaxPriceChangePerBlock = DefaultMaxPriceChangePerBlock;
 	type OmnipoolHubAsset = OmnipoolHubAsset;
+	type AssetHaltHandler = OmnipoolHaltHandler;
+	type LimitedAssets = OmnipoolCallAssets;
+	type MaxLimitHits = MaxLimitHits;
+	type LimitHitsPeriod = ConstU64<10>;
+	type Currency = Balances;
+	type LimitHitFee = LimitHitFee;
+	type LimitHitFeeReceiver = TreasuryAccount;
 	type WeightInfo = ();
 }
 
@@ -150,7 +163,7 @@ pub struct CircuitBreakerWhitelist;
 
 impl Contains<AccountId> for CircuitBreakerWhitelist {
 	fn contains(a: &AccountId) -> bool {
-		WHITELISTED_ACCCOUNT == *a
+		WHITELISTED_ACCCOUNT == *a || CircuitBreaker::contains(a)
 	}
 }
 
@@ -190,10 +203,16 @@ parameter_types! {
 	pub const HDXAssetId: AssetId = HDX;
 	pub const LRNAAssetId: AssetId = LRNA;
 	pub const DAIAssetId: AssetId = DAI;
+	pub const ListingBond: Balance = 100 * ONE;
+	pub const ListingChallengePeriod: u64 = 10;
+	pub const ListingWeightCap: Permill = Permill::from_percent(10);
 	pub const PosiitionCollectionId: u32= 1000;
 
 	pub ProtocolFee: Permill = PROTOCOL_FEE.with(|v| *v.borrow());
 	pub AssetFee: Permill = ASSET_FEE.with(|v| *v.borrow());
+	pub DynamicFeeAmplification: FixedU128 = FixedU128::from(2);
+	pub DynamicFeeDecay: Permill = Permill::from_parts(100);
+	pub const TreasuryAccount: AccountId = 1000;
 	pub AssetWeightCap: Permill =ASSET_WEIGHT_CAP.with(|v| *v.borrow());
 	pub MinAddedLiquidity: Balance = MIN_ADDED_LIQUDIITY.with(|v| *v.borrow());
 	pub MinTradeAmount: Balance = MIN_TRADE_AMOUNT.with(|v| *v.borrow());
@@ -209,9 +228,12 @@ impl pallet_omnipool::Config for Test {
 	type PositionItemId = u32;
 	type Currency = Tokens;
 	type AuthorityOrigin = EnsureRoot<Self::AccountId>;
+	type TreasuryAccount = TreasuryAccount;
 	type HubAssetId = LRNAAssetId;
 	type ProtocolFee = ProtocolFee;
 	type AssetFee = AssetFee;
+	type DynamicFeeAmplification = DynamicFeeAmplification;
+	type DynamicFeeDecay = DynamicFeeDecay;
 	type StableCoinAssetId = DAIAssetId;
 	type WeightInfo = ();
 	type HdxAssetId = HDXAssetId;
@@ -223,6 +245,12 @@ impl pallet_omnipool::Config for Test {
 	type TechnicalOrigin = EnsureRoot<Self::AccountId>;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxMergedPositions = ConstU32<10>;
+	type MaxAddLiquidityBatch = ConstU32<10>;
+	type MaxProtocolFeeBeneficiaries = ConstU32<5>;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingWeightCap = ListingWeightCap;
 	type CollectionId = u32;
 	type OmnipoolHooks = CircuitBreakerHooks<Test>;
 	type PriceBarrier = ();
@@ -230,6 +258,60 @@ impl pallet_omnipool::Config for Test {
 	type ExternalPriceOracle = WithdrawFeePriceOracle;
 }
 
+pub struct OmnipoolHaltHandler;
+
+impl AssetHaltHandler<AssetId> for OmnipoolHaltHandler {
+	type State = Tradability;
+
+	fn on_halt(asset_id: AssetId) -> Result<Tradability, DispatchError> {
+		let previous_state = Omnipool::load_asset_state(asset_id)?.tradable;
+		frame_support::ensure!(
+			previous_state != Tradability::FROZEN,
+			pallet_omnipool::Error::<Test>::NotAllowed
+		);
+		Omnipool::do_set_asset_tradable_state(asset_id, Tradability::REMOVE_LIQUIDITY)?;
+		Ok(previous_state)
+	}
+
+	fn on_resume(asset_id: AssetId, previous_state: Tradability) -> DispatchResult {
+		Omnipool::do_set_asset_tradable_state(asset_id, previous_state)
+	}
+
+	fn on_halt_weight() -> Weight {
+		Weight::zero()
+	}
+
+	fn on_resume_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+pub struct OmnipoolCallAssets;
+
+impl LimitedAssets<RuntimeCall, AssetId> for OmnipoolCallAssets {
+	fn limited_assets(call: &RuntimeCall) -> Option<(AssetId, AssetId)> {
+		match call {
+			RuntimeCall::Omnipool(pallet_omnipool::Call::sell {
+				asset_in, asset_out, ..
+			})
+			| RuntimeCall::Omnipool(pallet_omnipool::Call::buy {
+				asset_in, asset_out, ..
+			}) => Some((*asset_in, *asset_out)),
+			RuntimeCall::Omnipool(pallet_omnipool::Call::add_liquidity { asset, .. }) => Some((*asset, *asset)),
+			RuntimeCall::Omnipool(pallet_omnipool::Call::add_liquidity_batch { assets }) => assets
+				.iter()
+				.find(|(asset, amount)| {
+					Omnipool::load_asset_state(*asset)
+						.and_then(|state| CircuitBreaker::remaining_allowance_in_block(*asset, state.reserve))
+						.map(|allowance| allowance.add_liquidity.map_or(false, |remaining| *amount > remaining))
+						.unwrap_or(false)
+				})
+				.map(|(asset, _)| (*asset, *asset)),
+			_ => None,
+		}
+	}
+}
+
 pub struct CircuitBreakerHooks<T>(PhantomData<T>);
 
 impl<T> OmnipoolHooks<RuntimeOrigin, AssetId, Balance> for CircuitBreakerHooks<T>
@@ -292,10 +374,28 @@ where
 			amount_out.into(),
 		)?;
 
+		for asset in [asset_in, asset_out] {
+			pallet_circuit_breaker::Pallet::<T>::ensure_price_change_limit(
+				asset.asset_id.into(),
+				asset.before.reserve.into(),
+				asset.before.hub_reserve.into(),
+				asset.after.reserve.into(),
+				asset.after.hub_reserve.into(),
+			)?;
+		}
+
 		Ok(Weight::zero())
 	}
 
-	fn on_hub_asset_trade(_: RuntimeOrigin, _: AssetInfo<AssetId, Balance>) -> Result<Weight, Self::Error> {
+	fn on_hub_asset_trade(_: RuntimeOrigin, asset: AssetInfo<AssetId, Balance>) -> Result<Weight, Self::Error> {
+		pallet_circuit_breaker::Pallet::<T>::ensure_price_change_limit(
+			asset.asset_id.into(),
+			asset.before.reserve.into(),
+			asset.before.hub_reserve.into(),
+			asset.after.reserve.into(),
+			asset.after.hub_reserve.into(),
+		)?;
+
 		Ok(Weight::zero())
 	}
 
@@ -358,9 +458,11 @@ impl<AccountId: From<u64> + Into<u64> + Copy> Mutate<AccountId> for DummyNFT {
 	}
 }
 
+use crate::traits::{AssetHaltHandler, LimitedAssets};
 use crate::Config;
 use hydradx_traits::Registry;
 use pallet_omnipool::traits::{AssetInfo, ExternalPriceProvider, OmnipoolHooks};
+use pallet_omnipool::types::Tradability;
 
 pub struct DummyRegistry<T>(sp_std::marker::PhantomData<T>);
 
@@ -404,6 +506,8 @@ pub struct ExtBuilder {
 	max_net_trade_volume_limit_per_block: (u32, u32),
 	max_add_liquidity_limit_per_block: Option<(u32, u32)>,
 	max_remove_liquidity_limit_per_block: Option<(u32, u32)>,
+	max_price_change_per_block: Option<(u32, u32)>,
+	max_limit_hits: u32,
 }
 
 impl Default for ExtBuilder {
@@ -458,6 +562,8 @@ impl Default for ExtBuilder {
 			max_net_trade_volume_limit_per_block: (2_000, 10_000),
 			max_add_liquidity_limit_per_block: Some((4_000, 10_000)),
 			max_remove_liquidity_limit_per_block: Some((2_000, 10_000)),
+			max_price_change_per_block: None,
+			max_limit_hits: 3,
 			tvl_cap: u128::MAX,
 		}
 	}
@@ -497,6 +603,16 @@ impl ExtBuilder {
 		self
 	}
 
+	pub fn with_max_price_change_per_block(mut self, value: Option<(u32, u32)>) -> Self {
+		self.max_price_change_per_block = value;
+		self
+	}
+
+	pub fn with_max_limit_hits(mut self, value: u32) -> Self {
+		self.max_limit_hits = value;
+		self
+	}
+
 	pub fn with_token(
 		mut self,
 		asset_id: AssetId,
@@ -556,6 +672,12 @@ impl ExtBuilder {
 		MAX_REMOVE_LIQUIDITY_LIMIT_PER_BLOCK.with(|v| {
 			*v.borrow_mut() = self.max_remove_liquidity_limit_per_block;
 		});
+		MAX_PRICE_CHANGE_PER_BLOCK.with(|v| {
+			*v.borrow_mut() = self.max_price_change_per_block;
+		});
+		MAX_LIMIT_HITS.with(|v| {
+			*v.borrow_mut() = self.max_limit_hits;
+		});
 
 		orml_tokens::GenesisConfig::<Test> {
 			balances: self
@@ -1,7 +1,12 @@
 mod add_liquidity_limit;
 mod ensure_add_liquidity_limit;
 mod ensure_remove_liquidity_limit;
+mod limit_hits;
 pub(crate) mod mock;
 mod omnipool;
+mod price_change_limit;
+mod remaining_allowance;
 mod remove_liquidity_limit;
 mod trade_volume;
+mod whitelist;
+mod window_limits;
@@ -0,0 +1,285 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::*;
+use frame_support::assert_err;
+pub use pretty_assertions::{assert_eq, assert_ne};
+
+const INITIAL_POOL_LIQUIDITY: Balance = 10_000 * ONE;
+
+fn with_omnipool(price_change_limit: Option<(u32, u32)>) -> sp_io::TestExternalities {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1_000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, DOT, 2_000_000 * ONE),
+			(LP1, ACA, 2_000_000 * ONE),
+			(TRADER, DOT, 2_000_000 * ONE),
+		])
+		.with_registered_asset(DOT)
+		.with_registered_asset(ACA)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(DOT, FixedU128::from_float(0.65), LP1, INITIAL_POOL_LIQUIDITY)
+		.with_token(ACA, FixedU128::from_float(0.65), LP1, INITIAL_POOL_LIQUIDITY)
+		.with_max_price_change_per_block(price_change_limit)
+		.build()
+}
+
+#[test]
+fn set_price_change_limit_should_work_when_signed_by_technical_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange & Act
+		assert_ok!(CircuitBreaker::set_price_change_limit(
+			RuntimeOrigin::root(),
+			HDX,
+			Some(TEN_PERCENT)
+		));
+
+		// Assert
+		assert_eq!(CircuitBreaker::price_change_limit_per_asset(HDX), Some(TEN_PERCENT));
+		expect_events(vec![crate::Event::PriceChangeLimitChanged {
+			asset_id: HDX,
+			price_change_limit: Some(TEN_PERCENT),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn set_price_change_limit_should_fail_when_not_signed_by_technical_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_price_change_limit(RuntimeOrigin::signed(ALICE), HDX, Some(TEN_PERCENT)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_price_change_limit_should_fail_when_limit_is_invalid() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_price_change_limit(RuntimeOrigin::root(), HDX, Some((0, 10_000))),
+			Error::<Test>::InvalidLimitValue
+		);
+	});
+}
+
+#[test]
+fn set_price_change_limit_should_fail_when_setting_limit_for_omnipool_hub_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_price_change_limit(RuntimeOrigin::root(), LRNA, Some(TEN_PERCENT)),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn opening_price_should_not_be_stored_when_limit_is_not_set() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Act
+		assert_ok!(CircuitBreaker::ensure_price_change_limit(
+			HDX,
+			INITIAL_LIQUIDITY,
+			INITIAL_LIQUIDITY,
+			INITIAL_LIQUIDITY,
+			2 * INITIAL_LIQUIDITY
+		));
+
+		// Assert
+		assert_eq!(CircuitBreaker::opening_price_per_asset(HDX), None);
+	});
+}
+
+#[test]
+fn ensure_price_change_limit_should_store_opening_price_when_called_first_time_in_block() {
+	ExtBuilder::default()
+		.with_max_price_change_per_block(Some(TEN_PERCENT))
+		.build()
+		.execute_with(|| {
+			// Act
+			assert_ok!(CircuitBreaker::ensure_price_change_limit(
+				HDX,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				1_050_000
+			));
+
+			// Assert
+			assert_eq!(CircuitBreaker::opening_price_per_asset(HDX), Some(FixedU128::one()));
+		});
+}
+
+#[test]
+fn ensure_price_change_limit_should_fail_when_price_change_exceeds_limit() {
+	ExtBuilder::default()
+		.with_max_price_change_per_block(Some(TEN_PERCENT))
+		.build()
+		.execute_with(|| {
+			assert_err!(
+				CircuitBreaker::ensure_price_change_limit(
+					HDX,
+					INITIAL_LIQUIDITY,
+					INITIAL_LIQUIDITY,
+					INITIAL_LIQUIDITY,
+					1_100_001
+				),
+				Error::<Test>::MaxPriceIncreasePerBlockReached
+			);
+			assert_err!(
+				CircuitBreaker::ensure_price_change_limit(
+					HDX,
+					INITIAL_LIQUIDITY,
+					INITIAL_LIQUIDITY,
+					INITIAL_LIQUIDITY,
+					899_999
+				),
+				Error::<Test>::MaxPriceDecreasePerBlockReached
+			);
+		});
+}
+
+#[test]
+fn ensure_price_change_limit_should_measure_price_change_from_opening_price() {
+	ExtBuilder::default()
+		.with_max_price_change_per_block(Some(TEN_PERCENT))
+		.build()
+		.execute_with(|| {
+			// Arrange
+			assert_ok!(CircuitBreaker::ensure_price_change_limit(
+				HDX,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				1_050_000
+			));
+
+			// Act & Assert
+			// the price moves less than 10% in the second trade, but more than 10% since the start of the block
+			assert_err!(
+				CircuitBreaker::ensure_price_change_limit(
+					HDX,
+					INITIAL_LIQUIDITY,
+					1_050_000,
+					INITIAL_LIQUIDITY,
+					1_120_000
+				),
+				Error::<Test>::MaxPriceIncreasePerBlockReached
+			);
+		});
+}
+
+#[test]
+fn opening_price_should_be_cleared_when_block_is_finalized() {
+	ExtBuilder::default()
+		.with_max_price_change_per_block(Some(TEN_PERCENT))
+		.build()
+		.execute_with(|| {
+			// Arrange
+			assert_ok!(CircuitBreaker::ensure_price_change_limit(
+				HDX,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				1_050_000
+			));
+
+			// Act
+			CircuitBreaker::on_finalize(1);
+
+			// Assert
+			assert_eq!(CircuitBreaker::opening_price_per_asset(HDX), None);
+			assert_ok!(CircuitBreaker::ensure_price_change_limit(
+				HDX,
+				INITIAL_LIQUIDITY,
+				1_050_000,
+				INITIAL_LIQUIDITY,
+				1_120_000
+			));
+		});
+}
+
+#[test]
+fn ensure_price_change_limit_should_ignore_omnipool_hub_asset() {
+	ExtBuilder::default()
+		.with_max_price_change_per_block(Some(TEN_PERCENT))
+		.build()
+		.execute_with(|| {
+			assert_ok!(CircuitBreaker::ensure_price_change_limit(
+				LRNA,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				INITIAL_LIQUIDITY,
+				2 * INITIAL_LIQUIDITY
+			));
+			assert_eq!(CircuitBreaker::opening_price_per_asset(LRNA), None);
+		});
+}
+
+#[test]
+fn sell_should_work_when_price_change_is_within_limit() {
+	with_omnipool(Some(TEN_PERCENT)).execute_with(|| {
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, 300 * ONE, 0));
+	});
+}
+
+#[test]
+fn sell_should_fail_when_price_change_exceeds_limit() {
+	with_omnipool(Some(TEN_PERCENT)).execute_with(|| {
+		assert_noop!(
+			Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, 1_200 * ONE, 0),
+			Error::<Test>::MaxPriceDecreasePerBlockReached
+		);
+	});
+}
+
+#[test]
+fn sell_should_fail_when_consequent_trades_exceed_price_change_limit() {
+	with_omnipool(Some(TEN_PERCENT)).execute_with(|| {
+		// Arrange
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, 300 * ONE, 0));
+
+		// Act & Assert
+		assert_noop!(
+			Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, 300 * ONE, 0),
+			Error::<Test>::MaxPriceDecreasePerBlockReached
+		);
+	});
+}
+
+#[test]
+fn sell_should_work_in_next_block_when_price_change_limit_was_reached() {
+	with_omnipool(Some(TEN_PERCENT)).execute_with(|| {
+		// Arrange
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, 300 * ONE, 0));
+		CircuitBreaker::on_finalize(1);
+		System::set_block_number(2);
+
+		// Act & Assert
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, 300 * ONE, 0));
+	});
+}
+
+#[test]
+fn sell_should_not_be_limited_by_price_change_when_limit_is_not_set() {
+	with_omnipool(None).execute_with(|| {
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(TRADER), DOT, ACA, 1_200 * ONE, 0));
+	});
+}
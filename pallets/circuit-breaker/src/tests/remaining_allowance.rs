@@ -0,0 +1,278 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::*;
+pub use pretty_assertions::{assert_eq, assert_ne};
+
+#[test]
+fn remaining_allowance_should_return_full_limits_when_asset_was_not_traded_in_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Act & Assert
+		assert_eq!(
+			CircuitBreaker::remaining_allowance(HDX, INITIAL_LIQUIDITY).unwrap(),
+			RemainingAllowance {
+				inflow: Some(200_000),
+				outflow: Some(200_000),
+				add_liquidity: Some(400_000),
+				remove_liquidity: Some(200_000),
+			}
+		);
+	});
+}
+
+#[test]
+fn remaining_allowance_in_block_should_account_for_trades_in_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::ensure_pool_state_change_limit(
+			DOT,
+			INITIAL_LIQUIDITY,
+			50_000,
+			HDX,
+			INITIAL_LIQUIDITY,
+			50_000,
+		));
+
+		// Act & Assert
+		// stored limits are used instead of the current reserve
+		assert_eq!(
+			CircuitBreaker::remaining_allowance_in_block(HDX, 2 * INITIAL_LIQUIDITY).unwrap(),
+			RemainingAllowance {
+				inflow: Some(250_000),
+				outflow: Some(150_000),
+				add_liquidity: Some(800_000),
+				remove_liquidity: Some(400_000),
+			}
+		);
+		assert_eq!(
+			CircuitBreaker::remaining_allowance_in_block(DOT, INITIAL_LIQUIDITY).unwrap(),
+			RemainingAllowance {
+				inflow: Some(150_000),
+				outflow: Some(250_000),
+				add_liquidity: Some(400_000),
+				remove_liquidity: Some(200_000),
+			}
+		);
+	});
+}
+
+#[test]
+fn remaining_allowance_in_block_should_account_for_liquidity_changes_in_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::ensure_add_liquidity_limit(
+			RuntimeOrigin::signed(ALICE),
+			HDX,
+			INITIAL_LIQUIDITY,
+			100_000,
+		));
+		assert_ok!(CircuitBreaker::ensure_remove_liquidity_limit(
+			RuntimeOrigin::signed(ALICE),
+			HDX,
+			INITIAL_LIQUIDITY,
+			50_000,
+		));
+
+		// Act & Assert
+		assert_eq!(
+			CircuitBreaker::remaining_allowance_in_block(HDX, INITIAL_LIQUIDITY).unwrap(),
+			RemainingAllowance {
+				inflow: Some(200_000),
+				outflow: Some(200_000),
+				add_liquidity: Some(300_000),
+				remove_liquidity: Some(150_000),
+			}
+		);
+	});
+}
+
+#[test]
+fn remaining_allowance_should_return_none_when_liquidity_limits_are_not_set() {
+	ExtBuilder::default()
+		.with_max_add_liquidity_limit_per_block(None)
+		.with_max_remove_liquidity_limit_per_block(None)
+		.build()
+		.execute_with(|| {
+			// Act & Assert
+			assert_eq!(
+				CircuitBreaker::remaining_allowance(HDX, INITIAL_LIQUIDITY).unwrap(),
+				RemainingAllowance {
+					inflow: Some(200_000),
+					outflow: Some(200_000),
+					add_liquidity: None,
+					remove_liquidity: None,
+				}
+			);
+		});
+}
+
+#[test]
+fn remaining_allowance_should_return_none_for_hub_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Act & Assert
+		assert_eq!(
+			CircuitBreaker::remaining_allowance(LRNA, INITIAL_LIQUIDITY).unwrap(),
+			RemainingAllowance {
+				inflow: None,
+				outflow: None,
+				add_liquidity: None,
+				remove_liquidity: None,
+			}
+		);
+	});
+}
+
+#[test]
+fn remaining_allowance_should_return_full_per_block_limits_for_next_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::ensure_pool_state_change_limit(
+			DOT,
+			INITIAL_LIQUIDITY,
+			50_000,
+			HDX,
+			INITIAL_LIQUIDITY,
+			50_000,
+		));
+
+		// Act & Assert
+		let next_block_allowance = RemainingAllowance {
+			inflow: Some(200_000),
+			outflow: Some(200_000),
+			add_liquidity: Some(400_000),
+			remove_liquidity: Some(200_000),
+		};
+		assert_eq!(
+			CircuitBreaker::remaining_allowance(DOT, INITIAL_LIQUIDITY).unwrap(),
+			next_block_allowance
+		);
+
+		// the amounts of the block are reset at its end
+		CircuitBreaker::on_finalize(System::block_number());
+		assert_eq!(
+			CircuitBreaker::remaining_allowance(DOT, INITIAL_LIQUIDITY).unwrap(),
+			next_block_allowance
+		);
+	});
+}
+
+#[test]
+fn remaining_allowance_should_be_capped_by_window_limits() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			DOT,
+			Some(WindowLimits {
+				blocks: 10,
+				trade_volume_limit: Some((3_000, 10_000)),
+				add_liquidity_limit: Some((3_000, 10_000)),
+				remove_liquidity_limit: Some((3_000, 10_000)),
+			})
+		));
+		assert_ok!(CircuitBreaker::ensure_pool_state_change_limit(
+			DAI,
+			INITIAL_LIQUIDITY,
+			0,
+			DOT,
+			INITIAL_LIQUIDITY,
+			150_000,
+		));
+		assert_ok!(CircuitBreaker::ensure_add_liquidity_limit(
+			RuntimeOrigin::signed(ALICE),
+			DOT,
+			INITIAL_LIQUIDITY,
+			100_000,
+		));
+
+		// Act & Assert
+		// window allowance: inflow 300_000 + 150_000, outflow 300_000 - 150_000, add liquidity 300_000 - 100_000
+		assert_eq!(
+			CircuitBreaker::remaining_allowance_in_block(DOT, INITIAL_LIQUIDITY).unwrap(),
+			RemainingAllowance {
+				inflow: Some(350_000),
+				outflow: Some(50_000),
+				add_liquidity: Some(200_000),
+				remove_liquidity: Some(200_000),
+			}
+		);
+		// window amounts decay to 9/10 in the next block
+		assert_eq!(
+			CircuitBreaker::remaining_allowance(DOT, INITIAL_LIQUIDITY).unwrap(),
+			RemainingAllowance {
+				inflow: Some(200_000),
+				outflow: Some(165_000),
+				add_liquidity: Some(210_000),
+				remove_liquidity: Some(200_000),
+			}
+		);
+	});
+}
+
+#[test]
+fn remaining_allowance_should_return_full_window_limits_when_window_has_passed() {
+	ExtBuilder::default()
+		.with_max_trade_volume_limit_per_block((5_000, 10_000))
+		.build()
+		.execute_with(|| {
+			// Arrange
+			assert_ok!(CircuitBreaker::set_window_limits(
+				RuntimeOrigin::root(),
+				DOT,
+				Some(WindowLimits {
+					blocks: 10,
+					trade_volume_limit: Some((3_000, 10_000)),
+					add_liquidity_limit: None,
+					remove_liquidity_limit: None,
+				})
+			));
+			assert_ok!(CircuitBreaker::ensure_pool_state_change_limit(
+				DAI,
+				INITIAL_LIQUIDITY,
+				0,
+				DOT,
+				INITIAL_LIQUIDITY,
+				150_000,
+			));
+			CircuitBreaker::on_finalize(System::block_number());
+
+			// Act & Assert
+			// one block of the window is left in the next block
+			System::set_block_number(System::block_number() + 8);
+			assert_eq!(
+				CircuitBreaker::remaining_allowance(DOT, INITIAL_LIQUIDITY).unwrap(),
+				RemainingAllowance {
+					inflow: Some(315_000),
+					outflow: Some(285_000),
+					add_liquidity: Some(400_000),
+					remove_liquidity: Some(200_000),
+				}
+			);
+
+			System::set_block_number(System::block_number() + 1);
+			assert_eq!(
+				CircuitBreaker::remaining_allowance(DOT, INITIAL_LIQUIDITY).unwrap(),
+				RemainingAllowance {
+					inflow: Some(300_000),
+					outflow: Some(300_000),
+					add_liquidity: Some(400_000),
+					remove_liquidity: Some(200_000),
+				}
+			);
+		});
+}
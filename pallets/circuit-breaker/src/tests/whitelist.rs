@@ -0,0 +1,205 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::*;
+pub use pretty_assertions::{assert_eq, assert_ne};
+
+const MARKET_MAKER: AccountId = 5;
+
+#[test]
+fn add_to_whitelist_should_work_when_signed_by_technical_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange & Act
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			Some(10)
+		));
+
+		// Assert
+		assert_eq!(CircuitBreaker::whitelist(MARKET_MAKER), Some(Some(10)));
+		assert!(CircuitBreaker::is_whitelisted(&MARKET_MAKER));
+		expect_events(vec![crate::Event::AccountWhitelisted {
+			who: MARKET_MAKER,
+			expires_at: Some(10),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn add_to_whitelist_should_update_expiry_when_account_is_already_whitelisted() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			Some(10)
+		));
+
+		// Act
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			None
+		));
+
+		// Assert
+		assert_eq!(CircuitBreaker::whitelist(MARKET_MAKER), Some(None));
+	});
+}
+
+#[test]
+fn add_to_whitelist_should_fail_when_not_signed_by_technical_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::add_to_whitelist(RuntimeOrigin::signed(ALICE), MARKET_MAKER, None),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn add_to_whitelist_should_fail_when_expiry_is_not_in_future() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::add_to_whitelist(RuntimeOrigin::root(), MARKET_MAKER, Some(1)),
+			Error::<Test>::InvalidExpiry
+		);
+	});
+}
+
+#[test]
+fn remove_from_whitelist_should_work_when_account_is_whitelisted() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			None
+		));
+
+		// Act
+		assert_ok!(CircuitBreaker::remove_from_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER
+		));
+
+		// Assert
+		assert_eq!(CircuitBreaker::whitelist(MARKET_MAKER), None);
+		assert!(!CircuitBreaker::is_whitelisted(&MARKET_MAKER));
+		expect_events(vec![
+			crate::Event::AccountRemovedFromWhitelist { who: MARKET_MAKER }.into()
+		]);
+	});
+}
+
+#[test]
+fn remove_from_whitelist_should_fail_when_not_signed_by_technical_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			None
+		));
+
+		// Act & Assert
+		assert_noop!(
+			CircuitBreaker::remove_from_whitelist(RuntimeOrigin::signed(ALICE), MARKET_MAKER),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn remove_from_whitelist_should_fail_when_account_is_not_whitelisted() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::remove_from_whitelist(RuntimeOrigin::root(), MARKET_MAKER),
+			Error::<Test>::AccountNotWhitelisted
+		);
+	});
+}
+
+#[test]
+fn is_whitelisted_should_return_false_when_entry_expired() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			Some(10)
+		));
+
+		// Act
+		System::set_block_number(10);
+
+		// Assert
+		assert!(!CircuitBreaker::is_whitelisted(&MARKET_MAKER));
+		assert_eq!(CircuitBreaker::whitelist(MARKET_MAKER), Some(Some(10)));
+	});
+}
+
+#[test]
+fn ensure_add_liquidity_limit_should_be_ignored_for_account_in_whitelist_storage() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			None
+		));
+
+		// Act & Assert
+		assert_ok!(CircuitBreaker::ensure_add_liquidity_limit(
+			RuntimeOrigin::signed(MARKET_MAKER),
+			HDX,
+			INITIAL_LIQUIDITY,
+			400_001
+		));
+	});
+}
+
+#[test]
+fn ensure_add_liquidity_limit_should_fail_when_whitelist_entry_expired() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::add_to_whitelist(
+			RuntimeOrigin::root(),
+			MARKET_MAKER,
+			Some(10)
+		));
+		assert_ok!(CircuitBreaker::calculate_and_store_liquidity_limits(
+			HDX,
+			INITIAL_LIQUIDITY
+		));
+		System::set_block_number(10);
+
+		// Act & Assert
+		assert_noop!(
+			CircuitBreaker::ensure_add_liquidity_limit(
+				RuntimeOrigin::signed(MARKET_MAKER),
+				HDX,
+				INITIAL_LIQUIDITY,
+				400_001
+			),
+			Error::<Test>::MaxLiquidityLimitPerBlockReached
+		);
+	});
+}
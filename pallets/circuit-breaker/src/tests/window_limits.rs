@@ -0,0 +1,280 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::*;
+use frame_support::assert_err;
+pub use pretty_assertions::{assert_eq, assert_ne};
+
+fn window_limits(blocks: u32, limit: (u32, u32)) -> WindowLimits {
+	WindowLimits {
+		blocks,
+		trade_volume_limit: Some(limit),
+		add_liquidity_limit: Some(limit),
+		remove_liquidity_limit: Some(limit),
+	}
+}
+
+fn next_block() {
+	let block = System::block_number();
+	CircuitBreaker::on_finalize(block);
+	System::set_block_number(block + 1);
+}
+
+fn sell_dai_for_dot(amount_out: Balance) -> Result<Weight, DispatchError> {
+	CircuitBreaker::ensure_pool_state_change_limit(DAI, INITIAL_LIQUIDITY, 0, DOT, INITIAL_LIQUIDITY, amount_out)
+}
+
+#[test]
+fn set_window_limits_should_work_when_signed_by_technical_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange & Act
+		let limits = window_limits(10, (3_000, 10_000));
+
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			HDX,
+			Some(limits)
+		));
+
+		// Assert
+		assert_eq!(CircuitBreaker::window_limits_per_asset(HDX), Some(limits));
+		expect_events(vec![crate::Event::WindowLimitsChanged {
+			asset_id: HDX,
+			window_limits: Some(limits),
+		}
+		.into()]);
+	});
+}
+
+#[test]
+fn set_window_limits_should_fail_when_not_signed_by_technical_origin() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_window_limits(RuntimeOrigin::signed(ALICE), HDX, Some(window_limits(10, (1, 10)))),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_window_limits_should_fail_when_window_length_is_zero() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_window_limits(RuntimeOrigin::root(), HDX, Some(window_limits(0, (1, 10)))),
+			Error::<Test>::InvalidWindowLength
+		);
+	});
+}
+
+#[test]
+fn set_window_limits_should_fail_when_limit_is_invalid() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_window_limits(RuntimeOrigin::root(), HDX, Some(window_limits(10, (0, 10)))),
+			Error::<Test>::InvalidLimitValue
+		);
+	});
+}
+
+#[test]
+fn set_window_limits_should_fail_when_setting_limits_for_omnipool_hub_asset() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CircuitBreaker::set_window_limits(RuntimeOrigin::root(), LRNA, Some(window_limits(10, (1, 10)))),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn set_window_limits_should_reset_accumulated_window_volume() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			DOT,
+			Some(window_limits(5, (3_000, 10_000)))
+		));
+		assert_ok!(sell_dai_for_dot(150_000));
+		assert!(CircuitBreaker::allowed_trade_volume_limit_per_window(DOT).is_some());
+
+		// Act
+		assert_ok!(CircuitBreaker::set_window_limits(RuntimeOrigin::root(), DOT, None));
+
+		// Assert
+		assert_eq!(CircuitBreaker::window_limits_per_asset(DOT), None);
+		assert_eq!(CircuitBreaker::allowed_trade_volume_limit_per_window(DOT), None);
+	});
+}
+
+#[test]
+fn trade_should_fail_when_outflow_window_limit_is_reached_over_multiple_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			DOT,
+			Some(window_limits(5, (3_000, 10_000)))
+		));
+
+		// Act & Assert
+		// each trade is within the per-block limit
+		assert_ok!(sell_dai_for_dot(150_000));
+		next_block();
+
+		assert_ok!(sell_dai_for_dot(150_000));
+		assert_eq!(
+			CircuitBreaker::allowed_trade_volume_limit_per_window(DOT).unwrap(),
+			WindowTradeVolume {
+				volume: TradeVolumeLimit {
+					volume_in: 0,
+					volume_out: 270_000,
+					limit: 300_000,
+				},
+				updated_at: 2,
+			}
+		);
+		next_block();
+
+		assert_err!(sell_dai_for_dot(150_000), Error::<Test>::TokenOutflowWindowLimitReached);
+	});
+}
+
+#[test]
+fn trade_window_should_start_again_when_window_has_passed() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			DOT,
+			Some(window_limits(5, (3_000, 10_000)))
+		));
+		assert_ok!(sell_dai_for_dot(150_000));
+		next_block();
+		assert_ok!(sell_dai_for_dot(150_000));
+
+		// Act
+		for _ in 0..5 {
+			next_block();
+		}
+		assert_ok!(sell_dai_for_dot(150_000));
+
+		// Assert
+		assert_eq!(
+			CircuitBreaker::allowed_trade_volume_limit_per_window(DOT).unwrap(),
+			WindowTradeVolume {
+				volume: TradeVolumeLimit {
+					volume_in: 0,
+					volume_out: 150_000,
+					limit: 300_000,
+				},
+				updated_at: 7,
+			}
+		);
+	});
+}
+
+#[test]
+fn trade_should_not_be_limited_by_window_when_window_limits_are_not_set() {
+	ExtBuilder::default().build().execute_with(|| {
+		for _ in 0..5 {
+			assert_ok!(sell_dai_for_dot(150_000));
+			next_block();
+		}
+
+		assert_eq!(CircuitBreaker::allowed_trade_volume_limit_per_window(DOT), None);
+	});
+}
+
+#[test]
+fn add_liquidity_should_fail_when_window_limit_is_reached_over_multiple_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			HDX,
+			Some(window_limits(5, (5_000, 10_000)))
+		));
+
+		// Act & Assert
+		assert_ok!(CircuitBreaker::ensure_add_liquidity_limit(
+			RuntimeOrigin::signed(ALICE),
+			HDX,
+			INITIAL_LIQUIDITY,
+			300_000
+		));
+		next_block();
+
+		assert_err!(
+			CircuitBreaker::ensure_add_liquidity_limit(RuntimeOrigin::signed(ALICE), HDX, INITIAL_LIQUIDITY, 300_000),
+			Error::<Test>::MaxLiquidityLimitPerWindowReached
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_window_limit_is_reached_over_multiple_blocks() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			HDX,
+			Some(window_limits(5, (2_500, 10_000)))
+		));
+
+		// Act & Assert
+		assert_ok!(CircuitBreaker::ensure_remove_liquidity_limit(
+			RuntimeOrigin::signed(ALICE),
+			HDX,
+			INITIAL_LIQUIDITY,
+			150_000
+		));
+		next_block();
+
+		assert_err!(
+			CircuitBreaker::ensure_remove_liquidity_limit(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				INITIAL_LIQUIDITY,
+				150_000
+			),
+			Error::<Test>::MaxLiquidityLimitPerWindowReached
+		);
+	});
+}
+
+#[test]
+fn liquidity_window_limit_should_be_ignored_for_whitelisted_account() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Arrange
+		assert_ok!(CircuitBreaker::set_window_limits(
+			RuntimeOrigin::root(),
+			HDX,
+			Some(window_limits(5, (1_000, 10_000)))
+		));
+
+		// Act & Assert
+		assert_ok!(CircuitBreaker::ensure_add_liquidity_limit(
+			RuntimeOrigin::signed(WHITELISTED_ACCCOUNT),
+			HDX,
+			INITIAL_LIQUIDITY,
+			300_000
+		));
+		assert_eq!(CircuitBreaker::allowed_add_liquidity_limit_per_window(HDX), None);
+	});
+}
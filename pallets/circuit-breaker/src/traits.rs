@@ -0,0 +1,72 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2022  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use frame_support::{weights::Weight, Parameter};
+use sp_core::MaxEncodedLen;
+use sp_runtime::{DispatchError, DispatchResult};
+
+/// Handler of assets halted and resumed by the circuit breaker.
+pub trait AssetHaltHandler<AssetId> {
+	/// State of an asset saved when the asset is halted and restored when it is resumed.
+	type State: Parameter + MaxEncodedLen + Default;
+
+	/// Restrict trading of an asset after its limits were hit repeatedly.
+	/// Returns the state of the asset before it was halted.
+	fn on_halt(asset_id: AssetId) -> Result<Self::State, DispatchError>;
+
+	/// Lift the restrictions of a previously halted asset and restore its state from before the halt.
+	fn on_resume(asset_id: AssetId, previous_state: Self::State) -> DispatchResult;
+
+	/// Weight of `on_halt`.
+	fn on_halt_weight() -> Weight;
+
+	/// Weight of `on_resume`.
+	fn on_resume_weight() -> Weight;
+}
+
+impl<AssetId> AssetHaltHandler<AssetId> for () {
+	type State = ();
+
+	fn on_halt(_asset_id: AssetId) -> Result<Self::State, DispatchError> {
+		Ok(())
+	}
+
+	fn on_resume(_asset_id: AssetId, _previous_state: Self::State) -> DispatchResult {
+		Ok(())
+	}
+
+	fn on_halt_weight() -> Weight {
+		Weight::zero()
+	}
+
+	fn on_resume_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Provides assets whose limits are checked when a call is dispatched.
+pub trait LimitedAssets<Call, AssetId> {
+	/// Returns the asset coming into the pool and the asset going out of the pool when `call` is dispatched.
+	/// Both are the same asset for liquidity changes. Returns None if the call is not limited.
+	fn limited_assets(call: &Call) -> Option<(AssetId, AssetId)>;
+}
+
+impl<Call, AssetId> LimitedAssets<Call, AssetId> for () {
+	fn limited_assets(_call: &Call) -> Option<(AssetId, AssetId)> {
+		None
+	}
+}
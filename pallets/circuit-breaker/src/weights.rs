@@ -55,6 +55,13 @@ pub trait WeightInfo {
 	fn set_trade_volume_limit() -> Weight;
 	fn set_add_liquidity_limit() -> Weight;
 	fn set_remove_liquidity_limit() -> Weight;
+	fn set_window_limits() -> Weight;
+	fn resume_asset() -> Weight;
+	fn note_limit_hit() -> Weight;
+	fn set_price_change_limit() -> Weight;
+	fn ensure_price_change_limit() -> Weight;
+	fn add_to_whitelist() -> Weight;
+	fn remove_from_whitelist() -> Weight;
 	fn ensure_pool_state_change_limit() -> Weight;
 	fn ensure_add_liquidity_limit() -> Weight;
 	fn ensure_remove_liquidity_limit() -> Weight;
@@ -80,6 +87,27 @@ impl WeightInfo for () {
 	fn set_remove_liquidity_limit() -> Weight {
 		Weight::zero()
 	}
+	fn set_window_limits() -> Weight {
+		Weight::zero()
+	}
+	fn resume_asset() -> Weight {
+		Weight::zero()
+	}
+	fn note_limit_hit() -> Weight {
+		Weight::zero()
+	}
+	fn set_price_change_limit() -> Weight {
+		Weight::zero()
+	}
+	fn ensure_price_change_limit() -> Weight {
+		Weight::zero()
+	}
+	fn add_to_whitelist() -> Weight {
+		Weight::zero()
+	}
+	fn remove_from_whitelist() -> Weight {
+		Weight::zero()
+	}
 	fn ensure_pool_state_change_limit() -> Weight {
 		Weight::zero()
 	}
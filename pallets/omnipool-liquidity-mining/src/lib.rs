@@ -157,6 +157,7 @@ pub mod pallet {
 
 	#[pallet::storage]
 	/// Map of omnipool position's ids to LM's deposit ids.
+	#[pallet::getter(fn omni_position_id)]
 	pub(super) type OmniPositionId<T: Config> =
 		StorageMap<_, Blake2_128Concat, DepositId, T::PositionItemId, OptionQuery>;
 
@@ -257,10 +257,16 @@ parameter_types! {
 	pub const HDXAssetId: AssetId = HDX;
 	pub const LRNAAssetId: AssetId = LRNA;
 	pub const DAIAssetId: AssetId = DAI;
+	pub const ListingBond: Balance = 100 * ONE;
+	pub const ListingChallengePeriod: u64 = 10;
+	pub const ListingWeightCap: Permill = Permill::from_percent(10);
 	pub const PositionCollectionId: CollectionId = OMNIPOOL_COLLECTION_ID;
 
 	pub ProtocolFee: Permill = PROTOCOL_FEE.with(|v| *v.borrow());
 	pub AssetFee: Permill = ASSET_FEE.with(|v| *v.borrow());
+	pub DynamicFeeAmplification: FixedU128 = FixedU128::from(2);
+	pub DynamicFeeDecay: Permill = Permill::from_parts(100);
+	pub const TreasuryAccount: AccountId = 1000;
 	pub AssetWeightCap: Permill =ASSET_WEIGHT_CAP.with(|v| *v.borrow());
 	pub MinAddedLiquidity: Balance = MIN_ADDED_LIQUDIITY.with(|v| *v.borrow());
 	pub MinTradeAmount: Balance = MIN_TRADE_AMOUNT.with(|v| *v.borrow());
@@ -275,9 +281,12 @@ impl pallet_omnipool::Config for Test {
 	type PositionItemId = u128;
 	type Currency = Tokens;
 	type AuthorityOrigin = EnsureRoot<Self::AccountId>;
+	type TreasuryAccount = TreasuryAccount;
 	type HubAssetId = LRNAAssetId;
 	type ProtocolFee = ProtocolFee;
 	type AssetFee = AssetFee;
+	type DynamicFeeAmplification = DynamicFeeAmplification;
+	type DynamicFeeDecay = DynamicFeeDecay;
 	type StableCoinAssetId = DAIAssetId;
 	type WeightInfo = ();
 	type HdxAssetId = HDXAssetId;
@@ -289,6 +298,12 @@ impl pallet_omnipool::Config for Test {
 	type TechnicalOrigin = EnsureRoot<Self::AccountId>;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxMergedPositions = ConstU32<10>;
+	type MaxAddLiquidityBatch = ConstU32<10>;
+	type MaxProtocolFeeBeneficiaries = ConstU32<5>;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingWeightCap = ListingWeightCap;
 	type CollectionId = u128;
 	type OmnipoolHooks = ();
 	type PriceBarrier = ();
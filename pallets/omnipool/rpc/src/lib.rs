@@ -0,0 +1,255 @@
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the Omnipool pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_omnipool_runtime_api::{
+	OmnipoolApi as OmnipoolRuntimeApi, OwnedPosition, Position, PositionValue, TradeQuote,
+};
+
+/// Balances are returned as `NumberOrHex`, so that amounts above 2^53 are not rounded by JSON clients.
+#[rpc(client, server)]
+pub trait OmnipoolApi<BlockHash, AccountId, AssetId, Balance, PositionItemId> {
+	/// Quote a sell of `amount` of `asset_in` for `asset_out`.
+	#[method(name = "omnipool_quoteSell")]
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<TradeQuote<NumberOrHex>>;
+
+	/// Quote a buy of `amount` of `asset_out` for `asset_in`.
+	#[method(name = "omnipool_quoteBuy")]
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<BlockHash>,
+	) -> RpcResult<TradeQuote<NumberOrHex>>;
+
+	/// Calculate what the owner of position `position_id` would receive if all of its liquidity was removed now.
+	#[method(name = "omnipool_positionValue")]
+	fn position_value(
+		&self,
+		position_id: PositionItemId,
+		at: Option<BlockHash>,
+	) -> RpcResult<PositionValue<AssetId, NumberOrHex>>;
+
+	/// All positions of `owner`, including positions held by liquidity mining on behalf of `owner`'s deposits.
+	#[method(name = "omnipool_positions")]
+	fn positions(
+		&self,
+		owner: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<OwnedPosition<PositionItemId, AssetId, NumberOrHex>>>;
+}
+
+/// Provides RPC methods to query Omnipool.
+pub struct Omnipool<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Omnipool<C, Block> {
+	/// Create new `Omnipool` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The trade cannot be quoted.
+	QuoteError,
+	/// The position cannot be valued.
+	PositionValueError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::QuoteError => 2,
+			Error::PositionValueError => 3,
+		}
+	}
+}
+
+#[async_trait]
+impl<C, Block, AccountId, AssetId, Balance, PositionItemId>
+	OmnipoolApiServer<<Block as BlockT>::Hash, AccountId, AssetId, Balance, PositionItemId> for Omnipool<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: OmnipoolRuntimeApi<Block, AccountId, AssetId, Balance, PositionItemId>,
+	AccountId: Codec + DeserializeOwned + Send + Sync + 'static,
+	AssetId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + DeserializeOwned + Into<NumberOrHex> + Send + Sync + 'static,
+	PositionItemId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn quote_sell(
+		&self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<TradeQuote<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.quote_sell(at, asset_in, asset_out, amount)
+			.map_err(runtime_error_into_rpc_err)?
+			.map(trade_quote_into_rpc)
+			.map_err(|e| quote_error_into_rpc_err(format!("{:?}", e)))
+	}
+
+	fn quote_buy(
+		&self,
+		asset_out: AssetId,
+		asset_in: AssetId,
+		amount: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<TradeQuote<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.quote_buy(at, asset_out, asset_in, amount)
+			.map_err(runtime_error_into_rpc_err)?
+			.map(trade_quote_into_rpc)
+			.map_err(|e| quote_error_into_rpc_err(format!("{:?}", e)))
+	}
+
+	fn position_value(
+		&self,
+		position_id: PositionItemId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<PositionValue<AssetId, NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.position_value(at, position_id)
+			.map_err(runtime_error_into_rpc_err)?
+			.map(position_value_into_rpc)
+			.map_err(|e| position_value_error_into_rpc_err(format!("{:?}", e)))
+	}
+
+	fn positions(
+		&self,
+		owner: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<OwnedPosition<PositionItemId, AssetId, NumberOrHex>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		Ok(api
+			.positions(at, owner)
+			.map_err(runtime_error_into_rpc_err)?
+			.into_iter()
+			.map(owned_position_into_rpc)
+			.collect())
+	}
+}
+
+fn trade_quote_into_rpc<Balance: Into<NumberOrHex>>(quote: TradeQuote<Balance>) -> TradeQuote<NumberOrHex> {
+	TradeQuote {
+		amount_in: quote.amount_in.into(),
+		amount_out: quote.amount_out.into(),
+		asset_fee: quote.asset_fee.into(),
+		protocol_fee: quote.protocol_fee.into(),
+		spot_price: quote.spot_price,
+	}
+}
+
+fn position_value_into_rpc<AssetId, Balance: Into<NumberOrHex>>(
+	value: PositionValue<AssetId, Balance>,
+) -> PositionValue<AssetId, NumberOrHex> {
+	PositionValue {
+		asset_id: value.asset_id,
+		amount: value.amount.into(),
+		hub_amount: value.hub_amount.into(),
+		withdrawal_fee: value.withdrawal_fee,
+		early_removal_fee: value.early_removal_fee,
+		withdrawable: value.withdrawable,
+		impermanent_loss: value.impermanent_loss,
+	}
+}
+
+fn owned_position_into_rpc<PositionItemId, AssetId, Balance: Into<NumberOrHex>>(
+	owned: OwnedPosition<PositionItemId, AssetId, Balance>,
+) -> OwnedPosition<PositionItemId, AssetId, NumberOrHex> {
+	let position = owned.position;
+	OwnedPosition {
+		position_id: owned.position_id,
+		deposit_id: owned.deposit_id,
+		position: Position {
+			asset_id: position.asset_id,
+			amount: position.amount.into(),
+			shares: position.shares.into(),
+			price: (position.price.0.into(), position.price.1.into()),
+		},
+	}
+}
+
+/// Converts a runtime trap into an RPC error.
+fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::RuntimeError.into(),
+		"Unable to query Omnipool.",
+		Some(format!("{:?}", err)),
+	))
+	.into()
+}
+
+/// Converts a dispatch error returned by the runtime into an RPC error.
+fn quote_error_into_rpc_err(err: String) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::QuoteError.into(),
+		"Trade cannot be quoted.",
+		Some(err),
+	))
+	.into()
+}
+
+/// Converts a dispatch error returned by the runtime into an RPC error.
+fn position_value_error_into_rpc_err(err: String) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(
+		Error::PositionValueError.into(),
+		"Position cannot be valued.",
+		Some(err),
+	))
+	.into()
+}
@@ -0,0 +1,47 @@
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the Omnipool pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
+
+pub use pallet_omnipool::types::{OwnedPosition, Position, PositionValue, TradeQuote};
+
+sp_api::decl_runtime_apis! {
+	/// API to simulate Omnipool trades and liquidity withdrawals and to query LP positions.
+	pub trait OmnipoolApi<AccountId, AssetId, Balance, PositionItemId>
+	where
+		AccountId: Codec,
+		AssetId: Codec,
+		Balance: Codec,
+		PositionItemId: Codec,
+	{
+		/// Quote a sell of `amount` of `asset_in` for `asset_out`.
+		fn quote_sell(asset_in: AssetId, asset_out: AssetId, amount: Balance) -> Result<TradeQuote<Balance>, DispatchError>;
+
+		/// Quote a buy of `amount` of `asset_out` for `asset_in`.
+		fn quote_buy(asset_out: AssetId, asset_in: AssetId, amount: Balance) -> Result<TradeQuote<Balance>, DispatchError>;
+
+		/// Calculate what the owner of position `position_id` would receive if all of its liquidity was removed now.
+		fn position_value(position_id: PositionItemId) -> Result<PositionValue<AssetId, Balance>, DispatchError>;
+
+		/// All positions of `owner`, including positions held by liquidity mining on behalf of `owner`'s deposits.
+		fn positions(owner: AccountId) -> Vec<OwnedPosition<PositionItemId, AssetId, Balance>>;
+	}
+}
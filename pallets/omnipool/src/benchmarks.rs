@@ -23,6 +23,7 @@ use sp_runtime::FixedU128;
 
 use frame_benchmarking::account;
 use frame_benchmarking::benchmarks;
+use frame_support::BoundedVec;
 use frame_system::RawOrigin;
 use orml_traits::MultiCurrencyExtended;
 
@@ -225,6 +226,13 @@ benchmarks! {
 		T::Currency::update_balance(T::StableCoinAssetId::get(), &buyer, 500_000_000_000_000i128)?;
 		crate::Pallet::<T>::buy(RawOrigin::Signed(buyer).into(), token_id, T::StableCoinAssetId::get(), 30_000_000_000_000u128, 100_000_000_000_000u128)?;
 
+		// Protocol fee of the trade is accrued to maximum number of beneficiaries
+		let max = T::MaxProtocolFeeBeneficiaries::get();
+		let beneficiaries: Vec<(T::AccountId, Permill)> = (0..max)
+			.map(|i| (account("beneficiary", i, 1), Permill::from_rational(1, max)))
+			.collect();
+		crate::Pallet::<T>::set_protocol_fee_beneficiaries(RawOrigin::Root.into(), beneficiaries.try_into().unwrap())?;
+
 		let seller: T::AccountId = account("seller", 3, 1);
 		T::Currency::update_balance(token_id, &seller, 500_000_000_000_000i128)?;
 
@@ -279,6 +287,13 @@ benchmarks! {
 		T::Currency::update_balance(T::StableCoinAssetId::get(), &buyer, 500_000_000_000_000i128)?;
 		crate::Pallet::<T>::buy(RawOrigin::Signed(buyer).into(), token_id, T::StableCoinAssetId::get(), 30_000_000_000_000u128, 100_000_000_000_000u128)?;
 
+		// Protocol fee of the trade is accrued to maximum number of beneficiaries
+		let max = T::MaxProtocolFeeBeneficiaries::get();
+		let beneficiaries: Vec<(T::AccountId, Permill)> = (0..max)
+			.map(|i| (account("beneficiary", i, 1), Permill::from_rational(1, max)))
+			.collect();
+		crate::Pallet::<T>::set_protocol_fee_beneficiaries(RawOrigin::Root.into(), beneficiaries.try_into().unwrap())?;
+
 		let seller: T::AccountId = account("seller", 3, 1);
 		T::Currency::update_balance(token_id, &seller, 500_000_000_000_000i128)?;
 
@@ -391,6 +406,557 @@ benchmarks! {
 		assert!(asset_state.cap == 100_000_000_000_000_000u128);
 	}
 
+	set_asset_fee_bounds{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128 = FixedU128::from((1,2));
+		let native_price: FixedU128 = FixedU128::from(1);
+
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		let bounds = FeeBounds {
+			min_asset_fee: Permill::from_rational(25u32, 10_000u32),
+			max_asset_fee: Permill::from_percent(5),
+			min_protocol_fee: Permill::from_rational(5u32, 10_000u32),
+			max_protocol_fee: Permill::from_percent(1),
+		};
+	}: _(RawOrigin::Root, T::StableCoinAssetId::get(), Some(bounds))
+	verify {
+		assert_eq!(<AssetFeeBounds<T>>::get(T::StableCoinAssetId::get()), Some(bounds));
+	}
+
+	set_asset_fees{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128 = FixedU128::from((1,2));
+		let native_price: FixedU128 = FixedU128::from(1);
+
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		let fees = AssetFees {
+			asset_fee: Permill::from_percent(1),
+			protocol_fee: Permill::from_rational(5u32, 10_000u32),
+		};
+	}: _(RawOrigin::Root, T::StableCoinAssetId::get(), Some(fees))
+	verify {
+		assert_eq!(<AssetFeeOverrides<T>>::get(T::StableCoinAssetId::get()), Some(fees));
+	}
+
+	set_asset_max_price_difference{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128 = FixedU128::from((1,2));
+		let native_price: FixedU128 = FixedU128::from(1);
+
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		let max_difference = Permill::from_percent(5);
+	}: _(RawOrigin::Root, T::StableCoinAssetId::get(), Some(max_difference))
+	verify {
+		assert_eq!(<AssetMaxPriceDifference<T>>::get(T::StableCoinAssetId::get()), Some(max_difference));
+	}
+
+	withdraw_protocol_liquidity{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price, native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token provider and set balance
+		let owner: T::AccountId = account("owner", 0, 1);
+
+		let token_price = FixedU128::from((1,5));
+		let token_amount = 200_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &acc, token_amount as i128)?;
+
+		// Add the token to the pool
+		crate::Pallet::<T>::add_token(RawOrigin::Root.into(), token_id, token_price,Permill::from_percent(100), owner)?;
+
+		// Create LP provider account with correct balance
+		let lp_provider: T::AccountId = account("provider", 1, 1);
+		T::Currency::update_balance(token_id, &lp_provider, 500_000_000_000_000i128)?;
+
+		let liquidity_added = 300_000_000_000_000u128;
+
+		let current_position_id = <NextPositionId<T>>::get();
+
+		crate::Pallet::<T>::add_liquidity(RawOrigin::Signed(lp_provider.clone()).into(), token_id, liquidity_added)?;
+
+		crate::Pallet::<T>::sacrifice_position(RawOrigin::Signed(lp_provider).into(), current_position_id)?;
+
+		let protocol_shares = <Assets<T>>::get(token_id).unwrap().protocol_shares;
+		let withdrawn_shares = protocol_shares / 2;
+	}: _(RawOrigin::Root, token_id, withdrawn_shares)
+	verify {
+		assert_eq!(<Assets<T>>::get(token_id).unwrap().protocol_shares, protocol_shares - withdrawn_shares);
+	}
+
+	reduce_imbalance{
+		let amount: Balance = 1_000_000_000_000u128;
+
+		<HubAssetImbalance<T>>::put(SimpleImbalance {
+			value: 2 * amount,
+			negative: true,
+		});
+
+		T::Currency::update_balance(T::HubAssetId::get(), &T::TreasuryAccount::get(), amount as i128)?;
+	}: _(RawOrigin::Root, amount)
+	verify {
+		assert_eq!(<HubAssetImbalance<T>>::get(), SimpleImbalance { value: amount, negative: true });
+	}
+
+	set_protocol_fee_beneficiaries{
+		let max = T::MaxProtocolFeeBeneficiaries::get();
+		let beneficiaries: Vec<(T::AccountId, Permill)> = (0..max)
+			.map(|i| (account("beneficiary", i, 1), Permill::from_rational(1, max)))
+			.collect();
+
+		// Existing fees are flushed when beneficiaries are replaced
+		let fees: BoundedVec<(T::AccountId, Balance), T::MaxProtocolFeeBeneficiaries> = beneficiaries
+			.iter()
+			.map(|(who, _)| (who.clone(), 1_000_000_000_000u128))
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+		<BlockProtocolFees<T>>::put(fees);
+
+		let beneficiaries: BoundedVec<(T::AccountId, Permill), T::MaxProtocolFeeBeneficiaries> = beneficiaries.try_into().unwrap();
+	}: _(RawOrigin::Root, beneficiaries.clone())
+	verify {
+		assert_eq!(<ProtocolFeeBeneficiaries<T>>::get(), beneficiaries);
+		assert!(<BlockProtocolFees<T>>::get().is_empty());
+	}
+
+	propose_token{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price, native_price, Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token proposer and set balances
+		let proposer: T::AccountId = account("proposer", 0, 1);
+
+		// Hub reserve of the token must fit into listing weight cap
+		let token_price: FixedU128= FixedU128::from((1,5));
+		let token_amount = 50_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &proposer, token_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &proposer, T::ListingBond::get() as i128)?;
+
+		let current_position_id = <NextPositionId<T>>::get();
+
+	}: _(RawOrigin::Signed(proposer), token_id, token_amount, token_price)
+	verify {
+		assert!(<Positions<T>>::get(current_position_id).is_some());
+		assert!(<Assets<T>>::get(token_id).is_some());
+		assert!(<Listings<T>>::get(token_id).is_some());
+	}
+
+	approve_listing{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price, native_price, Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token proposer and set balances
+		let proposer: T::AccountId = account("proposer", 0, 1);
+
+		// Hub reserve of the token must fit into listing weight cap
+		let token_price: FixedU128= FixedU128::from((1,5));
+		let token_amount = 50_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &proposer, token_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &proposer, T::ListingBond::get() as i128)?;
+
+		crate::Pallet::<T>::propose_token(RawOrigin::Signed(proposer.clone()).into(), token_id, token_amount, token_price)?;
+
+		let challenge_end = <Listings<T>>::get(token_id).unwrap().challenge_end;
+		frame_system::Pallet::<T>::set_block_number(challenge_end + 1u32.into());
+
+	}: _(RawOrigin::Signed(proposer), token_id)
+	verify {
+		assert!(<Listings<T>>::get(token_id).is_none());
+		assert_eq!(<Assets<T>>::get(token_id).unwrap().tradable, Tradability::default());
+	}
+
+	reject_listing{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price, native_price, Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token proposer and set balances
+		let proposer: T::AccountId = account("proposer", 0, 1);
+
+		// Hub reserve of the token must fit into listing weight cap
+		let token_price: FixedU128= FixedU128::from((1,5));
+		let token_amount = 50_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &proposer, token_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &proposer, T::ListingBond::get() as i128)?;
+
+		crate::Pallet::<T>::propose_token(RawOrigin::Signed(proposer.clone()).into(), token_id, token_amount, token_price)?;
+
+	}: _(RawOrigin::Root, token_id)
+	verify {
+		assert!(<Listings<T>>::get(token_id).is_none());
+		assert!(<Assets<T>>::get(token_id).is_none());
+		assert_eq!(T::Currency::free_balance(token_id, &proposer), token_amount);
+	}
+
+	set_asset_min_holding_period{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128 = FixedU128::from((1,2));
+		let native_price: FixedU128 = FixedU128::from(1);
+
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		let min_holding_period = MinHoldingPeriod {
+			blocks: 10u32.into(),
+			early_removal_fee: Some(Permill::from_percent(1)),
+		};
+	}: _(RawOrigin::Root, T::StableCoinAssetId::get(), Some(min_holding_period))
+	verify {
+		assert_eq!(<AssetMinHoldingPeriod<T>>::get(T::StableCoinAssetId::get()), Some(min_holding_period));
+	}
+
+	settle_position{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token provider and set balance
+		let owner: T::AccountId = account("owner", 0, 1);
+
+		let token_price = FixedU128::from((1,5));
+		let token_amount = 200_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &acc, token_amount as i128)?;
+
+		// Add the token to the pool
+		crate::Pallet::<T>::add_token(RawOrigin::Root.into(), token_id, token_price,Permill::from_percent(100), owner)?;
+
+		// Create LP provider account with correct balance aand add some liquidity
+		let lp_provider: T::AccountId = account("provider", 1, 1);
+		T::Currency::update_balance(token_id, &lp_provider, 500_000_000_000_000i128)?;
+
+		let liquidity_added = 300_000_000_000_000u128;
+
+		let current_position_id = <NextPositionId<T>>::get();
+
+		crate::Pallet::<T>::add_liquidity(RawOrigin::Signed(lp_provider.clone()).into(), token_id, liquidity_added)?;
+
+		// to ensure worst case - Let's do a trade to make sure price changes, so LP provider receives some LRNA ( which does additional transfer)
+		let buyer: T::AccountId = account("buyer", 2, 1);
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &buyer, 500_000_000_000_000i128)?;
+		crate::Pallet::<T>::buy(RawOrigin::Signed(buyer).into(), token_id, T::StableCoinAssetId::get(), 30_000_000_000_000u128, 100_000_000_000_000u128)?;
+
+		crate::Pallet::<T>::set_asset_tradable_state(RawOrigin::Root.into(), token_id, Tradability::FROZEN)?;
+
+	}: _(RawOrigin::Root, current_position_id)
+	verify {
+		// Ensure NFT instance was burned
+		assert!(<Positions<T>>::get(current_position_id).is_none());
+
+		// Ensure lp provider received LRNA
+		assert!(T::Currency::free_balance(T::HubAssetId::get(), &lp_provider) > Balance::zero());
+	}
+
+	remove_token{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token provider and set balance
+		let owner: T::AccountId = account("owner", 0, 1);
+
+		let token_price = FixedU128::from((1,5));
+		let token_amount = 200_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &acc, token_amount as i128)?;
+
+		let owner_position_id = <NextPositionId<T>>::get();
+
+		// Add the token to the pool
+		crate::Pallet::<T>::add_token(RawOrigin::Root.into(), token_id, token_price,Permill::from_percent(100), owner.clone())?;
+
+		// All shares must be owned by protocol
+		crate::Pallet::<T>::sacrifice_position(RawOrigin::Signed(owner).into(), owner_position_id)?;
+
+		crate::Pallet::<T>::set_asset_tradable_state(RawOrigin::Root.into(), token_id, Tradability::FROZEN)?;
+
+	}: _(RawOrigin::Root, token_id)
+	verify {
+		assert!(<Assets<T>>::get(token_id).is_none());
+		assert_eq!(T::Currency::free_balance(token_id, &T::TreasuryAccount::get()), token_amount);
+	}
+
+	merge_positions{
+		let n in 1 .. T::MaxMergedPositions::get();
+
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token provider and set balance
+		let owner: T::AccountId = account("owner", 0, 1);
+
+		let token_price = FixedU128::from((1,5));
+		let token_amount = 200_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &acc, token_amount as i128)?;
+
+		// Add the token to the pool
+		crate::Pallet::<T>::add_token(RawOrigin::Root.into(), token_id, token_price,Permill::from_percent(100), owner)?;
+
+		// Create LP provider account with correct balance
+		let lp_provider: T::AccountId = account("provider", 1, 1);
+		T::Currency::update_balance(token_id, &lp_provider, 500_000_000_000_000i128)?;
+
+		let liquidity_added = 10_000_000_000_000u128;
+
+		let current_position_id = <NextPositionId<T>>::get();
+
+		crate::Pallet::<T>::add_liquidity(RawOrigin::Signed(lp_provider.clone()).into(), token_id, liquidity_added)?;
+
+		let mut positions = Vec::new();
+		for _ in 0..n {
+			positions.push(<NextPositionId<T>>::get());
+			crate::Pallet::<T>::add_liquidity(RawOrigin::Signed(lp_provider.clone()).into(), token_id, liquidity_added)?;
+		}
+		let positions: BoundedVec<T::PositionItemId, T::MaxMergedPositions> = positions.try_into().unwrap();
+
+	}: _(RawOrigin::Signed(lp_provider), current_position_id, positions.clone())
+	verify {
+		assert!(positions.iter().all(|id| <Positions<T>>::get(id).is_none()));
+		assert_eq!(<Positions<T>>::get(current_position_id).unwrap().amount, liquidity_added * (n as u128 + 1));
+	}
+
+	add_liquidity_batch{
+		let n in 1 .. T::MaxAddLiquidityBatch::get();
+
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price, native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Create account for token provider and LP provider
+		let owner: T::AccountId = account("owner", 0, 1);
+		let lp_provider: T::AccountId = account("provider", 1, 1);
+
+		let token_price = FixedU128::from((1,5));
+		let token_amount = 200_000_000_000_000u128;
+		let liquidity_added = 100_000_000_000_000u128;
+
+		let mut assets = Vec::new();
+		for i in 0..n {
+			// Register new asset in asset registry
+			let token_id = T::AssetRegistry::create_asset(&[b'F', b'C', b'K', i as u8].to_vec(), Balance::one())?;
+
+			T::Currency::update_balance(token_id, &acc, token_amount as i128)?;
+
+			// Add the token to the pool
+			crate::Pallet::<T>::add_token(RawOrigin::Root.into(), token_id, token_price,Permill::from_percent(100), owner.clone())?;
+
+			T::Currency::update_balance(token_id, &lp_provider, 500_000_000_000_000i128)?;
+
+			assets.push((token_id, liquidity_added));
+		}
+		let assets: BoundedVec<(T::AssetId, Balance), T::MaxAddLiquidityBatch> = assets.try_into().unwrap();
+
+		let current_position_id = <NextPositionId<T>>::get();
+
+	}: _(RawOrigin::Signed(lp_provider), assets)
+	verify {
+		assert!(<Positions<T>>::get(current_position_id).is_some());
+	}
+
+	split_position{
+		// Initialize pool
+		let stable_amount: Balance = 1_000_000_000_000_000u128;
+		let native_amount: Balance = 1_000_000_000_000_000u128;
+		let stable_price: FixedU128= FixedU128::from((1,2));
+		let native_price: FixedU128= FixedU128::from(1);
+		let acc = crate::Pallet::<T>::protocol_account();
+
+		crate::Pallet::<T>::set_tvl_cap(RawOrigin::Root.into(), TVL_CAP)?;
+
+		T::Currency::update_balance(T::StableCoinAssetId::get(), &acc, stable_amount as i128)?;
+		T::Currency::update_balance(T::HdxAssetId::get(), &acc, native_amount as i128)?;
+
+		crate::Pallet::<T>::initialize_pool(RawOrigin::Root.into(), stable_price,native_price,Permill::from_percent(100), Permill::from_percent(100))?;
+
+		// Register new asset in asset registry
+		let token_id = T::AssetRegistry::create_asset(&b"FCK".to_vec(), Balance::one())?;
+
+		// Create account for token provider and set balance
+		let owner: T::AccountId = account("owner", 0, 1);
+
+		let token_price = FixedU128::from((1,5));
+		let token_amount = 200_000_000_000_000u128;
+
+		T::Currency::update_balance(token_id, &acc, token_amount as i128)?;
+
+		// Add the token to the pool
+		crate::Pallet::<T>::add_token(RawOrigin::Root.into(), token_id, token_price,Permill::from_percent(100), owner)?;
+
+		// Create LP provider account with correct balance
+		let lp_provider: T::AccountId = account("provider", 1, 1);
+		T::Currency::update_balance(token_id, &lp_provider, 500_000_000_000_000i128)?;
+
+		let liquidity_added = 10_000_000_000_000u128;
+
+		let current_position_id = <NextPositionId<T>>::get();
+
+		crate::Pallet::<T>::add_liquidity(RawOrigin::Signed(lp_provider.clone()).into(), token_id, liquidity_added)?;
+
+		let next_position_id = <NextPositionId<T>>::get();
+
+	}: _(RawOrigin::Signed(lp_provider), current_position_id, liquidity_added / 2)
+	verify {
+		assert!(<Positions<T>>::get(next_position_id).is_some());
+	}
+
+	claim_protocol_fees{
+		let beneficiary: T::AccountId = account("beneficiary", 0, 1);
+		let amount = 1_000_000_000_000u128;
+		<AccruedProtocolFees<T>>::insert(&beneficiary, amount);
+	}: _(RawOrigin::Signed(beneficiary.clone()))
+	verify {
+		assert!(<AccruedProtocolFees<T>>::get(&beneficiary).is_zero());
+		assert_eq!(T::Currency::free_balance(T::HubAssetId::get(), &beneficiary), amount);
+	}
+
 }
 
 #[cfg(test)]
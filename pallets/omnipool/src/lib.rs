@@ -60,29 +60,38 @@
 //! * `set_asset_tradable_state` - Updates state of an asset in the pool to allow/disallow trading.
 //! * `add_token` - Adds token to the pool. Initial liquidity must be transffered to pool account prior to calling add_token.
 //! * `add_liquidity` - Adds liquidity of selected asset to the pool. Mints corresponding position NFT.
+//! * `add_liquidity_batch` - Adds liquidity of multiple assets to the pool in single transaction. Mints position NFT for each asset.
 //! * `remove_liquidity` - Removes liquidity of selected position from the pool. Partial withdrawals are allowed.
+//! * `remove_liquidity_with_limit` - Same as `remove_liquidity` but fails if received asset or hub asset amount is below given minimum.
 //! * `sell` - Trades an asset in for asset out by selling given amount of asset in.
 //! * `buy` - Trades an asset in for asset out by buying given amount of asset out.
 //! * `set_asset_tradable_state` - Updates asset's tradable asset with new flags. This allows/forbids asset operation such SELL,BUY,ADD or  REMOVE liquidtityy.
 //! * `refund_refused_asset` - Refunds the initial liquidity amount sent to pool account prior to add_token if the token has been refused to be added.
+//! * `set_asset_fee_bounds` - Sets bounds of asset's dynamic fees. Dynamic fees are raised after trades and decay back to minimum over blocks.
+//! * `set_asset_fees` - Overrides asset fee and protocol fee of an asset. `AssetFee` and `ProtocolFee` are used when no override is set.
+//! * `settle_position` - Removes all liquidity of a position of frozen asset on behalf of its owner.
+//! * `remove_token` - Removes frozen asset with no remaining LP positions from the pool. Remaining liquidity is transferred to treasury.
+//! * `merge_positions` - Merges positions of the same asset into one position with amount-weighted average entry price.
+//! * `split_position` - Splits given amount of shares of a position into a new position.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use frame_support::pallet_prelude::{DispatchResult, Get};
 use frame_support::require_transactional;
+use frame_support::traits::{BalanceStatus, DefensiveOption};
 use frame_support::PalletId;
 use frame_support::{ensure, transactional};
-use sp_runtime::traits::{AccountIdConversion, AtLeast32BitUnsigned, One};
-use sp_runtime::traits::{CheckedAdd, CheckedSub, Zero};
+use sp_runtime::traits::{AccountIdConversion, AtLeast32BitUnsigned, IntegerSquareRoot, One};
+use sp_runtime::traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Saturating, Zero};
 use sp_std::ops::{Add, Sub};
 use sp_std::prelude::*;
 
 use frame_support::traits::tokens::nonfungibles::{Create, Inspect, Mutate};
 use hydra_dx_math::omnipool::types::{AssetStateChange, BalanceUpdate, I129};
 use hydradx_traits::Registry;
-use orml_traits::MultiCurrency;
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
 use scale_info::TypeInfo;
-use sp_runtime::{ArithmeticError, DispatchError, FixedPointNumber, FixedU128, Permill};
+use sp_runtime::{ArithmeticError, DispatchError, FixedPointNumber, FixedU128, PerThing, Permill, SaturatedConversion};
 
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarks;
@@ -90,13 +99,17 @@ mod benchmarks;
 #[cfg(test)]
 mod tests;
 
+mod math;
 pub mod provider;
 pub mod traits;
 pub mod types;
 pub mod weights;
 
-use crate::traits::{AssetInfo, OmnipoolHooks};
-use crate::types::{AssetReserveState, AssetState, Balance, Position, SimpleImbalance, Tradability};
+use crate::traits::{AssetInfo, ExternalPriceProvider, OmnipoolHooks, ShouldAllow};
+use crate::types::{
+	AssetFees, AssetReserveState, AssetState, Balance, DynamicFee, FeeBounds, Listing, MinHoldingPeriod, Position,
+	PositionValue, Price, SimpleImbalance, Tradability, TradeQuote,
+};
 pub use pallet::*;
 pub use weights::WeightInfo;
 
@@ -108,10 +121,9 @@ pub type NFTCollectionIdOf<T> =
 pub mod pallet {
 	use super::*;
 	use crate::traits::{AssetInfo, ExternalPriceProvider, OmnipoolHooks, ShouldAllow};
-	use crate::types::{Position, Price, Tradability};
+	use crate::types::{AssetFees, DynamicFee, FeeBounds, Position, Price, Tradability};
 	use codec::HasCompact;
 	use frame_support::pallet_prelude::*;
-	use frame_support::traits::DefensiveOption;
 	use frame_system::pallet_prelude::*;
 	use hydra_dx_math::ema::EmaPrice;
 	use hydra_dx_math::omnipool::types::{BalanceUpdate, I129};
@@ -137,7 +149,7 @@ pub mod pallet {
 			+ TypeInfo;
 
 		/// Multi currency mechanism
-		type Currency: MultiCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>;
+		type Currency: MultiReservableCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>;
 
 		/// Origin that can add token, refund refused asset and  set tvl cap.
 		type AuthorityOrigin: EnsureOrigin<Self::RuntimeOrigin>;
@@ -145,6 +157,9 @@ pub mod pallet {
 		/// Origin to be able to suspend asset trades and initialize Omnipool.
 		type TechnicalOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+		/// Account which receives remaining liquidity of a delisted asset.
+		type TreasuryAccount: Get<Self::AccountId>;
+
 		/// Asset Registry mechanism - used to check if asset is correctly registered in asset registry
 		type AssetRegistry: Registry<Self::AssetId, Vec<u8>, Balance, DispatchError>;
 
@@ -168,6 +183,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type AssetFee: Get<Permill>;
 
+		/// Multiplier of traded amount to asset reserve ratio by which dynamic fees are raised after a trade.
+		#[pallet::constant]
+		type DynamicFeeAmplification: Get<FixedU128>;
+
+		/// Amount by which dynamic fees decay towards their minimum each block.
+		#[pallet::constant]
+		type DynamicFeeDecay: Get<Permill>;
+
 		/// Minimum withdrawal fee
 		#[pallet::constant]
 		type MinWithdrawalFee: Get<Permill>;
@@ -188,6 +211,30 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxOutRatio: Get<u128>;
 
+		/// Max number of positions which can be merged into a position in single transaction
+		#[pallet::constant]
+		type MaxMergedPositions: Get<u32>;
+
+		/// Max number of assets which liquidity can be added in single transaction
+		#[pallet::constant]
+		type MaxAddLiquidityBatch: Get<u32>;
+
+		/// Max number of protocol fee beneficiaries
+		#[pallet::constant]
+		type MaxProtocolFeeBeneficiaries: Get<u32>;
+
+		/// Amount of native asset reserved from proposer of a permissionless listing
+		#[pallet::constant]
+		type ListingBond: Get<Balance>;
+
+		/// Number of blocks in which governance can reject a permissionless listing
+		#[pallet::constant]
+		type ListingChallengePeriod: Get<Self::BlockNumber>;
+
+		/// Weight cap of an asset listed permissionlessly
+		#[pallet::constant]
+		type ListingWeightCap: Get<Permill>;
+
 		/// Position identifier type
 		type PositionItemId: Member + Parameter + Default + Copy + HasCompact + AtLeast32BitUnsigned + MaxEncodedLen;
 
@@ -229,6 +276,7 @@ pub mod pallet {
 
 	#[pallet::storage]
 	/// LP positions. Maps NFT instance id to corresponding position
+	#[pallet::getter(fn positions)]
 	pub(super) type Positions<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PositionItemId, Position<Balance, T::AssetId>>;
 
@@ -241,6 +289,52 @@ pub mod pallet {
 	/// TVL cap
 	pub(super) type TvlCap<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	#[pallet::storage]
+	/// Fees of an asset overriding `AssetFee`, `ProtocolFee` and dynamic fees.
+	pub(super) type AssetFeeOverrides<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, AssetFees>;
+
+	#[pallet::storage]
+	/// Dynamic fee bounds of an asset. Dynamic fees are used only for assets with bounds set.
+	pub(super) type AssetFeeBounds<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, FeeBounds>;
+
+	#[pallet::storage]
+	/// Max allowed price difference of an asset checked by price barrier, overriding the global one.
+	pub(super) type AssetMaxPriceDifference<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, Permill>;
+
+	#[pallet::storage]
+	/// Accounts receiving protocol fees collected in hub asset and their shares of the fees.
+	/// Part of protocol fees which is not distributed to beneficiaries is added to HDX subpool.
+	pub(super) type ProtocolFeeBeneficiaries<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, Permill), T::MaxProtocolFeeBeneficiaries>, ValueQuery>;
+
+	#[pallet::storage]
+	/// Block in which a position was created by adding liquidity.
+	pub(super) type PositionCreatedAt<T: Config> = StorageMap<_, Blake2_128Concat, T::PositionItemId, T::BlockNumber>;
+
+	#[pallet::storage]
+	/// Minimum holding period of liquidity of an asset.
+	pub(super) type AssetMinHoldingPeriod<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, MinHoldingPeriod<T::BlockNumber>>;
+
+	#[pallet::storage]
+	/// Permissionless listings of assets which can still be rejected by governance.
+	pub(super) type Listings<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, Listing<T::AccountId, T::PositionItemId, T::BlockNumber>>;
+
+	#[pallet::storage]
+	/// Protocol fees accrued to beneficiaries in current block.
+	pub(super) type BlockProtocolFees<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, Balance), T::MaxProtocolFeeBeneficiaries>, ValueQuery>;
+
+	#[pallet::storage]
+	/// Protocol fees in hub asset accrued to a beneficiary which have not been claimed yet.
+	pub(super) type AccruedProtocolFees<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Balance, ValueQuery>;
+
+	#[pallet::storage]
+	/// Dynamic fees of an asset at the time of last trade.
+	pub(super) type AssetDynamicFees<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AssetId, DynamicFee<T::BlockNumber>>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -319,6 +413,100 @@ pub mod pallet {
 
 		/// TVL cap has been updated.
 		TVLCapUpdated { cap: Balance },
+
+		/// Asset's dynamic fee bounds have been updated.
+		AssetFeeBoundsUpdated {
+			asset_id: T::AssetId,
+			bounds: Option<FeeBounds>,
+		},
+
+		/// Asset's fee override has been updated.
+		AssetFeesSet {
+			asset_id: T::AssetId,
+			fees: Option<AssetFees>,
+		},
+
+		/// Protocol shares of an asset were withdrawn to treasury account.
+		ProtocolLiquidityRemoved {
+			beneficiary: T::AccountId,
+			asset_id: T::AssetId,
+			amount: Balance,
+			hub_amount: Balance,
+			shares_removed: Balance,
+		},
+
+		/// Negative hub asset imbalance was reduced by burning hub asset from treasury account.
+		ImbalanceReduced {
+			amount: Balance,
+			imbalance: SimpleImbalance<Balance>,
+		},
+
+		/// Protocol fee beneficiaries have been updated.
+		ProtocolFeeBeneficiariesSet {
+			beneficiaries: Vec<(T::AccountId, Permill)>,
+		},
+
+		/// Protocol fees in hub asset were accrued to beneficiary in a block.
+		ProtocolFeesAccrued { beneficiary: T::AccountId, amount: Balance },
+
+		/// Accrued protocol fees in hub asset were claimed by beneficiary.
+		ProtocolFeesClaimed { beneficiary: T::AccountId, amount: Balance },
+
+		/// Asset has been listed permissionlessly and can be rejected until the end of challenge period.
+		ListingProposed {
+			asset_id: T::AssetId,
+			proposer: T::AccountId,
+			bond: Balance,
+			position_id: T::PositionItemId,
+			challenge_end: T::BlockNumber,
+		},
+
+		/// Challenge period of a listing has passed and the asset is fully tradable.
+		ListingApproved {
+			asset_id: T::AssetId,
+			proposer: T::AccountId,
+		},
+
+		/// Listing has been rejected and the asset removed from Omnipool.
+		ListingRejected {
+			asset_id: T::AssetId,
+			proposer: T::AccountId,
+			bond_slashed: Balance,
+		},
+
+		/// Asset's max allowed price difference has been updated.
+		AssetMaxPriceDifferenceSet {
+			asset_id: T::AssetId,
+			max_difference: Option<Permill>,
+		},
+
+		/// Asset's minimum holding period of liquidity has been updated.
+		AssetMinHoldingPeriodSet {
+			asset_id: T::AssetId,
+			min_holding_period: Option<MinHoldingPeriod<T::BlockNumber>>,
+		},
+
+		/// Asset's dynamic fees have been changed by a trade.
+		AssetFeesUpdated {
+			asset_id: T::AssetId,
+			asset_fee: Permill,
+			protocol_fee: Permill,
+		},
+
+		/// Position of a frozen asset has been settled on behalf of its owner.
+		PositionSettled {
+			position_id: T::PositionItemId,
+			owner: T::AccountId,
+			asset_id: T::AssetId,
+		},
+
+		/// Frozen asset has been removed from Omnipool.
+		/// Remaining asset reserve and hub reserve have been transferred to treasury account.
+		TokenRemoved {
+			asset_id: T::AssetId,
+			amount: Balance,
+			hub_withdrawn: Balance,
+		},
 	}
 
 	#[pallet::error]
@@ -382,6 +570,40 @@ pub mod pallet {
 		InvalidOraclePrice,
 		/// Failed to calculate withdrawal fee.
 		InvalidWithdrawalFee,
+		/// Amount received after removing liquidity is below given minimum limit.
+		SlippageLimit,
+		/// Minimum fee is greater than maximum fee or maximum fee is not lower than `MAX_FEE`.
+		InvalidFeeBounds,
+		/// Asset is not frozen.
+		AssetNotFrozen,
+		/// Asset cannot be removed while there are LP shares not owned by protocol.
+		SharesRemaining,
+		/// Positions of different assets cannot be merged.
+		PositionAssetMismatch,
+		/// Position cannot be merged into itself.
+		DuplicatePosition,
+		/// Asset is present more than once in the batch.
+		DuplicateAsset,
+		/// Trade deadline has passed.
+		DeadlinePassed,
+		/// Amount exceeds current hub asset imbalance.
+		ImbalanceExceeded,
+		/// Shares of protocol fee beneficiaries exceed 100% or a beneficiary is present more than once.
+		InvalidProtocolFeeBeneficiaries,
+		/// Asset has no pending listing.
+		ListingNotFound,
+		/// Challenge period of the listing has not passed yet.
+		ChallengePeriodNotOver,
+		/// Challenge period of the listing has passed already.
+		ChallengePeriodOver,
+		/// Asset cannot be removed while its listing is pending.
+		ListingPending,
+		/// Liquidity cannot be removed before minimum holding period of the asset has passed.
+		MinHoldingPeriodNotPassed,
+		/// Asset fee or protocol fee is not lower than `MAX_FEE`.
+		InvalidAssetFees,
+		/// No protocol fees have been accrued to the account.
+		NoProtocolFeesToClaim,
 	}
 
 	#[pallet::call]
@@ -532,92 +754,14 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::AuthorityOrigin::ensure_origin(origin.clone())?;
 
-			ensure!(!Assets::<T>::contains_key(asset), Error::<T>::AssetAlreadyAdded);
-
-			ensure!(T::AssetRegistry::exists(asset), Error::<T>::AssetNotRegistered);
-
-			ensure!(initial_price > FixedU128::zero(), Error::<T>::InvalidInitialAssetPrice);
-
-			let amount = T::Currency::free_balance(asset, &Self::protocol_account());
-
-			ensure!(
-				amount >= T::MinimumPoolLiquidity::get() && amount > 0,
-				Error::<T>::MissingBalance
-			);
-
-			let hub_reserve = initial_price.checked_mul_int(amount).ok_or(ArithmeticError::Overflow)?;
-
-			// Initial state of asset
-			let state = AssetState::<Balance> {
-				hub_reserve,
-				shares: amount,
-				protocol_shares: Balance::zero(),
-				cap: FixedU128::from(weight_cap).into_inner(),
-				tradable: Tradability::default(),
-			};
-
-			let lp_position = Position::<Balance, T::AssetId> {
-				asset_id: asset,
-				amount,
-				shares: amount,
-				price: (initial_price.into_inner(), FixedU128::DIV),
-			};
-
-			let instance_id = Self::create_and_mint_position_instance(&position_owner)?;
-
-			<Positions<T>>::insert(instance_id, lp_position);
-
-			Self::deposit_event(Event::PositionCreated {
-				position_id: instance_id,
-				owner: position_owner,
-				asset,
-				amount,
-				shares: amount,
-				price: initial_price,
-			});
-
-			let current_imbalance = <HubAssetImbalance<T>>::get();
-			let current_hub_asset_liquidity =
-				T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account());
-
-			let delta_imbalance = hydra_dx_math::omnipool::calculate_delta_imbalance(
-				hub_reserve,
-				I129 {
-					value: current_imbalance.value,
-					negative: current_imbalance.negative,
-				},
-				current_hub_asset_liquidity,
-			)
-			.ok_or(ArithmeticError::Overflow)?;
-
-			Self::update_imbalance(BalanceUpdate::Decrease(delta_imbalance))?;
-
-			let delta_hub_reserve = BalanceUpdate::Increase(hub_reserve);
-			Self::update_hub_asset_liquidity(&delta_hub_reserve)?;
-
-			let reserve = T::Currency::free_balance(asset, &Self::protocol_account());
-
-			let reserve_state: AssetReserveState<_> = (state.clone(), reserve).into();
-			let changes = AssetStateChange {
-				delta_hub_reserve,
-				delta_reserve: BalanceUpdate::Increase(reserve),
-				delta_shares: BalanceUpdate::Increase(amount),
-				delta_protocol_shares: BalanceUpdate::Increase(Balance::zero()),
-			};
-			T::OmnipoolHooks::on_liquidity_changed(
+			Self::do_add_token(
 				origin,
-				AssetInfo::new(asset, &AssetReserveState::default(), &reserve_state, &changes),
-			)?;
-
-			<Assets<T>>::insert(asset, state);
-
-			Self::ensure_tvl_cap()?;
-
-			Self::deposit_event(Event::TokenAdded {
-				asset_id: asset,
-				initial_amount: amount,
+				asset,
 				initial_price,
-			});
+				weight_cap,
+				Tradability::default(),
+				position_owner,
+			)?;
 
 			Ok(())
 		}
@@ -647,129 +791,55 @@ pub mod pallet {
 		)]
 		#[transactional]
 		pub fn add_liquidity(origin: OriginFor<T>, asset: T::AssetId, amount: Balance) -> DispatchResult {
-			//
-			// Preconditions
-			//
 			let who = ensure_signed(origin.clone())?;
 
-			ensure!(
-				amount >= T::MinimumPoolLiquidity::get(),
-				Error::<T>::InsufficientLiquidity
-			);
-
-			ensure!(
-				T::Currency::ensure_can_withdraw(asset, &who, amount).is_ok(),
-				Error::<T>::InsufficientBalance
-			);
-
-			let asset_state = Self::load_asset_state(asset)?;
-
-			ensure!(
-				asset_state.tradable.contains(Tradability::ADD_LIQUIDITY),
-				Error::<T>::NotAllowed
-			);
-
-			T::PriceBarrier::ensure_price(
-				&who,
-				T::HubAssetId::get(),
-				asset,
-				EmaPrice::new(asset_state.hub_reserve, asset_state.reserve),
-			)
-			.map_err(|_| Error::<T>::PriceDifferenceTooHigh)?;
-
-			let current_imbalance = <HubAssetImbalance<T>>::get();
-			let current_hub_asset_liquidity =
-				T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account());
-
-			//
-			// Calculate add liquidity state changes
-			//
-			let state_changes = hydra_dx_math::omnipool::calculate_add_liquidity_state_changes(
-				&(&asset_state).into(),
-				amount,
-				I129 {
-					value: current_imbalance.value,
-					negative: current_imbalance.negative,
-				},
-				current_hub_asset_liquidity,
-			)
-			.ok_or(ArithmeticError::Overflow)?;
-
-			let new_asset_state = asset_state
-				.clone()
-				.delta_update(&state_changes.asset)
-				.ok_or(ArithmeticError::Overflow)?;
-
-			let hub_reserve_ratio = FixedU128::checked_from_rational(
-				new_asset_state.hub_reserve,
-				T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account())
-					.checked_add(*state_changes.asset.delta_hub_reserve)
-					.ok_or(ArithmeticError::Overflow)?,
-			)
-			.ok_or(ArithmeticError::DivisionByZero)?;
-
-			ensure!(
-				hub_reserve_ratio <= new_asset_state.weight_cap(),
-				Error::<T>::AssetWeightCapExceeded
-			);
-
-			//
-			// Post - update states
-			//
-
-			// Create LP position with given shares
-			let lp_position = Position::<Balance, T::AssetId> {
-				asset_id: asset,
-				amount,
-				shares: *state_changes.asset.delta_shares,
-				// Note: position needs price after asset state is updated.
-				price: (new_asset_state.hub_reserve, new_asset_state.reserve),
-			};
-
-			let instance_id = Self::create_and_mint_position_instance(&who)?;
-
-			<Positions<T>>::insert(instance_id, lp_position);
-
-			Self::deposit_event(Event::PositionCreated {
-				position_id: instance_id,
-				owner: who.clone(),
-				asset,
-				amount,
-				shares: *state_changes.asset.delta_shares,
-				price: new_asset_state.price().ok_or(ArithmeticError::DivisionByZero)?,
-			});
-
-			T::Currency::transfer(
-				asset,
-				&who,
-				&Self::protocol_account(),
-				*state_changes.asset.delta_reserve,
-			)?;
-
-			debug_assert_eq!(*state_changes.asset.delta_reserve, amount);
-
-			// Callback hook info
-			let info: AssetInfo<T::AssetId, Balance> =
-				AssetInfo::new(asset, &asset_state, &new_asset_state, &state_changes.asset);
-
-			Self::update_imbalance(state_changes.delta_imbalance)?;
+			Self::do_add_liquidity(origin, who, asset, amount)?;
 
-			Self::update_hub_asset_liquidity(&state_changes.asset.delta_hub_reserve)?;
+			Self::ensure_weight_cap(asset)?;
+			Self::ensure_tvl_cap()
+		}
 
-			Self::set_asset_state(asset, new_asset_state);
+		/// Add liquidity of multiple assets to Omnipool in single transaction.
+		///
+		/// Same as `add_liquidity` called for each asset in `assets`. One position is created for each asset.
+		///
+		/// Asset weight caps and TVL cap are checked once all liquidity is added.
+		/// If adding liquidity of any asset fails, whole batch fails.
+		///
+		/// Parameters:
+		/// - `assets`: List of assets and amounts added to omnipool. Each asset can be present only once.
+		///
+		/// Emits `LiquidityAdded` event for each asset when successful.
+		///
+		#[pallet::call_index(18)]
+		#[pallet::weight(<T as Config>::WeightInfo::add_liquidity_batch(assets.len() as u32)
+			.saturating_add(T::OmnipoolHooks::on_liquidity_changed_weight()
+			.saturating_add(T::ExternalPriceOracle::get_price_weight())
+			.saturating_mul(assets.len() as u64))
+		)]
+		#[transactional]
+		pub fn add_liquidity_batch(
+			origin: OriginFor<T>,
+			assets: BoundedVec<(T::AssetId, Balance), T::MaxAddLiquidityBatch>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
 
-			Self::ensure_tvl_cap()?;
+			for (idx, (asset, _)) in assets.iter().enumerate() {
+				ensure!(
+					!assets.iter().skip(idx + 1).any(|(other, _)| other == asset),
+					Error::<T>::DuplicateAsset
+				);
+			}
 
-			Self::deposit_event(Event::LiquidityAdded {
-				who,
-				asset_id: asset,
-				amount,
-				position_id: instance_id,
-			});
+			for (asset, amount) in assets.iter() {
+				Self::do_add_liquidity(origin.clone(), who.clone(), *asset, *amount)?;
+			}
 
-			T::OmnipoolHooks::on_liquidity_changed(origin, info)?;
+			for (asset, _) in assets.iter() {
+				Self::ensure_weight_cap(*asset)?;
+			}
 
-			Ok(())
+			Self::ensure_tvl_cap()
 		}
 
 		/// Remove liquidity of asset `asset` in quantity `amount` from Omnipool
@@ -778,6 +848,9 @@ pub mod pallet {
 		///
 		/// Asset's tradable state must contain REMOVE_LIQUIDITY flag, otherwise `NotAllowed` error is returned.
 		///
+		/// If asset is frozen ( being delisted ), liquidity can be removed regardless of its tradable state.
+		/// In such case, price barrier is not checked and no withdrawal fee is charged.
+		///
 		/// if all shares from given position are removed, NFT is burned.
 		///
 		/// Parameters:
@@ -794,160 +867,39 @@ pub mod pallet {
 			position_id: T::PositionItemId,
 			amount: Balance,
 		) -> DispatchResult {
-			//
-			// Preconditions
-			//
 			let who = ensure_signed(origin.clone())?;
 
-			ensure!(amount > Balance::zero(), Error::<T>::InvalidSharesAmount);
-
-			ensure!(
-				T::NFTHandler::owner(&T::NFTCollectionId::get(), &position_id) == Some(who.clone()),
-				Error::<T>::Forbidden
-			);
-
-			let position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotFound)?;
-
-			ensure!(position.shares >= amount, Error::<T>::InsufficientShares);
+			Self::do_remove_liquidity(origin, who, position_id, amount, Balance::zero(), Balance::zero())
+		}
 
-			let asset_id = position.asset_id;
+		/// Remove liquidity of asset `asset` in quantity `amount` from Omnipool with slippage protection.
+		///
+		/// Same as `remove_liquidity` but fails with `SlippageLimit` error if amount of asset
+		/// or amount of hub asset received by LP is below given minimum.
+		///
+		/// Parameters:
+		/// - `origin`: liquidity provider
+		/// - `position_id`: an ID of liquidity position of LP
+		/// - `amount`: Amount of shares removed from omnipool
+		/// - `min_limit`: Minimum amount of asset to be received
+		/// - `min_hub_limit`: Minimum amount of hub asset to be received
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		///
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_liquidity().saturating_add(T::OmnipoolHooks::on_liquidity_changed_weight()))]
+		#[transactional]
+		pub fn remove_liquidity_with_limit(
+			origin: OriginFor<T>,
+			position_id: T::PositionItemId,
+			amount: Balance,
+			min_limit: Balance,
+			min_hub_limit: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
 
-			let asset_state = Self::load_asset_state(asset_id)?;
-
-			ensure!(
-				asset_state.tradable.contains(Tradability::REMOVE_LIQUIDITY),
-				Error::<T>::NotAllowed
-			);
-
-			T::PriceBarrier::ensure_price(
-				&who,
-				T::HubAssetId::get(),
-				asset_id,
-				EmaPrice::new(asset_state.hub_reserve, asset_state.reserve),
-			)
-			.map_err(|_| Error::<T>::PriceDifferenceTooHigh)?;
-
-			let current_imbalance = <HubAssetImbalance<T>>::get();
-			let current_hub_asset_liquidity =
-				T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account());
-
-			let ext_asset_price = T::ExternalPriceOracle::get_price(T::HubAssetId::get(), asset_id)?;
-
-			if ext_asset_price.is_zero() {
-				return Err(Error::<T>::InvalidOraclePrice.into());
-			}
-
-			let withdrawal_fee = hydra_dx_math::omnipool::calculate_withdrawal_fee(
-				asset_state.price().ok_or(ArithmeticError::DivisionByZero)?,
-				FixedU128::checked_from_rational(ext_asset_price.n, ext_asset_price.d)
-					.defensive_ok_or(Error::<T>::InvalidOraclePrice)?,
-				T::MinWithdrawalFee::get(),
-			);
-
-			//
-			// calculate state changes of remove liquidity
-			//
-			let state_changes = hydra_dx_math::omnipool::calculate_remove_liquidity_state_changes(
-				&(&asset_state).into(),
-				amount,
-				&(&position).into(),
-				I129 {
-					value: current_imbalance.value,
-					negative: current_imbalance.negative,
-				},
-				current_hub_asset_liquidity,
-				withdrawal_fee,
-			)
-			.ok_or(ArithmeticError::Overflow)?;
-
-			let new_asset_state = asset_state
-				.clone()
-				.delta_update(&state_changes.asset)
-				.ok_or(ArithmeticError::Overflow)?;
-
-			// Update position state
-			let updated_position = position
-				.delta_update(
-					&state_changes.delta_position_reserve,
-					&state_changes.delta_position_shares,
-				)
-				.ok_or(ArithmeticError::Overflow)?;
-
-			//
-			// Post - update states
-			//
-
-			T::Currency::transfer(
-				asset_id,
-				&Self::protocol_account(),
-				&who,
-				*state_changes.asset.delta_reserve,
-			)?;
-
-			Self::update_imbalance(state_changes.delta_imbalance)?;
-
-			// burn only difference between delta hub and lp hub amount.
-			Self::update_hub_asset_liquidity(
-				&state_changes
-					.asset
-					.delta_hub_reserve
-					.merge(BalanceUpdate::Increase(state_changes.lp_hub_amount))
-					.ok_or(ArithmeticError::Overflow)?,
-			)?;
-
-			// LP receives some hub asset
-			if state_changes.lp_hub_amount > Balance::zero() {
-				T::Currency::transfer(
-					T::HubAssetId::get(),
-					&Self::protocol_account(),
-					&who,
-					state_changes.lp_hub_amount,
-				)?;
-			}
-
-			if updated_position.shares == Balance::zero() {
-				// All liquidity removed, remove position and burn NFT instance
-
-				<Positions<T>>::remove(position_id);
-				T::NFTHandler::burn(&T::NFTCollectionId::get(), &position_id, Some(&who))?;
-
-				Self::deposit_event(Event::PositionDestroyed {
-					position_id,
-					owner: who.clone(),
-				});
-			} else {
-				Self::deposit_event(Event::PositionUpdated {
-					position_id,
-					owner: who.clone(),
-					asset: asset_id,
-					amount: updated_position.amount,
-					shares: updated_position.shares,
-					price: updated_position
-						.price_from_rational()
-						.ok_or(ArithmeticError::DivisionByZero)?,
-				});
-
-				<Positions<T>>::insert(position_id, updated_position);
-			}
-
-			// Callback hook info
-			let info: AssetInfo<T::AssetId, Balance> =
-				AssetInfo::new(asset_id, &asset_state, &new_asset_state, &state_changes.asset);
-
-			Self::set_asset_state(asset_id, new_asset_state);
-
-			Self::deposit_event(Event::LiquidityRemoved {
-				who,
-				position_id,
-				asset_id,
-				shares_removed: amount,
-				fee: withdrawal_fee,
-			});
-
-			T::OmnipoolHooks::on_liquidity_changed(origin, info)?;
-
-			Ok(())
-		}
+			Self::do_remove_liquidity(origin, who, position_id, amount, min_limit, min_hub_limit)
+		}
 
 		/// Sacrifice LP position in favor of pool.
 		///
@@ -982,6 +934,7 @@ pub mod pallet {
 
 			// Desotry position and burn NFT
 			<Positions<T>>::remove(position_id);
+			<PositionCreatedAt<T>>::remove(position_id);
 			T::NFTHandler::burn(&T::NFTCollectionId::get(), &position_id, Some(&who))?;
 
 			Self::deposit_event(Event::PositionDestroyed {
@@ -1042,7 +995,7 @@ pub mod pallet {
 			}
 
 			if asset_out == T::HubAssetId::get() {
-				return Self::sell_asset_for_hub_asset(&who, asset_in, amount, min_buy_amount);
+				return Self::sell_asset_for_hub_asset(origin, &who, asset_in, amount, min_buy_amount);
 			}
 
 			let asset_in_state = Self::load_asset_state(asset_in)?;
@@ -1068,8 +1021,8 @@ pub mod pallet {
 				&(&asset_in_state).into(),
 				&(&asset_out_state).into(),
 				amount,
-				T::AssetFee::get(),
-				T::ProtocolFee::get(),
+				Self::asset_fee(asset_out),
+				Self::protocol_fee(asset_in),
 				current_imbalance.value,
 			)
 			.ok_or(ArithmeticError::Overflow)?;
@@ -1157,9 +1110,9 @@ pub mod pallet {
 			Self::set_asset_state(asset_in, new_asset_in_state);
 			Self::set_asset_state(asset_out, new_asset_out_state);
 
-			T::OmnipoolHooks::on_trade(origin.clone(), info_in, info_out)?;
+			Self::on_trade(origin.clone(), info_in, info_out)?;
 
-			Self::update_hdx_subpool_hub_asset(origin, state_changes.hdx_hub_amount)?;
+			Self::process_protocol_fee(origin, state_changes.hdx_hub_amount)?;
 
 			Self::deposit_event(Event::SellExecuted {
 				who,
@@ -1212,7 +1165,7 @@ pub mod pallet {
 
 			// Special handling when one of the asset is Hub Asset
 			if asset_out == T::HubAssetId::get() {
-				return Self::buy_hub_asset(&who, asset_in, amount, max_sell_amount);
+				return Self::buy_hub_asset(origin, &who, asset_in, amount, max_sell_amount);
 			}
 
 			if asset_in == T::HubAssetId::get() {
@@ -1244,8 +1197,8 @@ pub mod pallet {
 				&(&asset_in_state).into(),
 				&(&asset_out_state).into(),
 				amount,
-				T::AssetFee::get(),
-				T::ProtocolFee::get(),
+				Self::asset_fee(asset_out),
+				Self::protocol_fee(asset_in),
 				current_imbalance.value,
 			)
 			.ok_or(ArithmeticError::Overflow)?;
@@ -1338,9 +1291,9 @@ pub mod pallet {
 			Self::set_asset_state(asset_in, new_asset_in_state);
 			Self::set_asset_state(asset_out, new_asset_out_state);
 
-			T::OmnipoolHooks::on_trade(origin.clone(), info_in, info_out)?;
+			Self::on_trade(origin.clone(), info_in, info_out)?;
 
-			Self::update_hdx_subpool_hub_asset(origin, state_changes.hdx_hub_amount)?;
+			Self::process_protocol_fee(origin, state_changes.hdx_hub_amount)?;
 
 			Self::deposit_event(Event::BuyExecuted {
 				who,
@@ -1371,29 +1324,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::TechnicalOrigin::ensure_origin(origin)?;
 
-			if asset_id == T::HubAssetId::get() {
-				// current omnipool does not allow liquidity add or remove of hub asset.
-				// Although BUY is not supported yet, we can allow the new state to be set to SELL/BUY.
-				ensure!(
-					!state.contains(Tradability::ADD_LIQUIDITY) && !state.contains(Tradability::REMOVE_LIQUIDITY),
-					Error::<T>::InvalidHubAssetTradableState
-				);
-
-				HubAssetTradability::<T>::mutate(|value| -> DispatchResult {
-					*value = state;
-					Self::deposit_event(Event::TradableStateUpdated { asset_id, state });
-					Ok(())
-				})
-			} else {
-				Assets::<T>::try_mutate(asset_id, |maybe_asset| -> DispatchResult {
-					let asset_state = maybe_asset.as_mut().ok_or(Error::<T>::AssetNotFound)?;
-
-					asset_state.tradable = state;
-					Self::deposit_event(Event::TradableStateUpdated { asset_id, state });
-
-					Ok(())
-				})
-			}
+			Self::do_set_asset_tradable_state(asset_id, state)
 		}
 
 		/// Refund given amount of asset to a recipient.
@@ -1416,26 +1347,7 @@ pub mod pallet {
 		) -> DispatchResult {
 			T::AuthorityOrigin::ensure_origin(origin)?;
 
-			// Hub asset cannot be refunded
-			ensure!(asset_id != T::HubAssetId::get(), Error::<T>::AssetRefundNotAllowed);
-
-			// Make sure that asset is not in the pool
-			ensure!(!Assets::<T>::contains_key(asset_id), Error::<T>::AssetAlreadyAdded);
-
-			ensure!(
-				T::Currency::ensure_can_withdraw(asset_id, &Self::protocol_account(), amount).is_ok(),
-				Error::<T>::InsufficientBalance
-			);
-
-			T::Currency::transfer(asset_id, &Self::protocol_account(), &recipient, amount)?;
-
-			Self::deposit_event(Event::AssetRefunded {
-				asset_id,
-				amount,
-				recipient,
-			});
-
-			Ok(())
+			Self::do_refund_asset(asset_id, amount, recipient)
 		}
 
 		/// Update asset's weight cap
@@ -1477,118 +1389,1092 @@ pub mod pallet {
 			Self::deposit_event(Event::TVLCapUpdated { cap });
 			Ok(())
 		}
-	}
 
-	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn integrity_test() {
-			assert_ne!(
-				T::MinimumPoolLiquidity::get(),
-				Balance::zero(),
-				"Minimum pool liquidity is 0."
-			);
-			assert_ne!(
-				T::MinimumTradingLimit::get(),
-				Balance::zero(),
-				"Minimum trading limit is 0."
-			);
-			assert_ne!(
-				T::HdxAssetId::get(),
-				T::StableCoinAssetId::get(),
-				"Same Hdx asset id and stable asset id."
-			);
-			assert_ne!(T::MaxInRatio::get(), Balance::zero(), "MaxInRatio is 0.");
-			assert_ne!(T::MaxOutRatio::get(), Balance::zero(), "MaxOutRatio is 0.");
-		}
-	}
-}
+		/// Update asset's dynamic fee bounds.
+		///
+		/// When bounds are set, asset fee and protocol fee of the asset are raised after trades
+		/// and decay back to the minimum each block. Setting bounds to `None` disables dynamic fees
+		/// and `AssetFee` and `ProtocolFee` are used again.
+		/// Maximum fees must be lower than `MAX_FEE`.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		/// - `bounds`: new fee bounds or `None`
+		///
+		/// Emits `AssetFeeBoundsUpdated` event when successful.
+		///
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_asset_fee_bounds())]
+		#[transactional]
+		pub fn set_asset_fee_bounds(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			bounds: Option<FeeBounds>,
+		) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
 
-impl<T: Config> Pallet<T> {
-	/// Protocol account address
-	pub fn protocol_account() -> T::AccountId {
-		PalletId(*b"omnipool").into_account_truncating()
-	}
+			ensure!(Assets::<T>::contains_key(asset_id), Error::<T>::AssetNotFound);
 
-	/// Retrieve stable asset detail from the pool.
-	/// Return NoStableCoinInPool if stable asset is not yet in the pool.
-	fn stable_asset() -> Result<(Balance, Balance), DispatchError> {
-		let stable_asset = <Assets<T>>::get(T::StableCoinAssetId::get()).ok_or(Error::<T>::NoStableAssetInPool)?;
-		let stable_reserve = T::Currency::free_balance(T::StableCoinAssetId::get(), &Self::protocol_account());
-		Ok((stable_reserve, stable_asset.hub_reserve))
-	}
+			match bounds {
+				Some(bounds) => {
+					ensure!(bounds.is_valid(), Error::<T>::InvalidFeeBounds);
+					AssetFeeBounds::<T>::insert(asset_id, bounds);
+				}
+				None => {
+					AssetFeeBounds::<T>::remove(asset_id);
+					AssetDynamicFees::<T>::remove(asset_id);
+				}
+			}
 
-	/// Retrieve state of asset from the pool and its pool balance
-	pub fn load_asset_state(asset_id: T::AssetId) -> Result<AssetReserveState<Balance>, DispatchError> {
-		let state = <Assets<T>>::get(asset_id).ok_or(Error::<T>::AssetNotFound)?;
-		let reserve = T::Currency::free_balance(asset_id, &Self::protocol_account());
-		Ok((state, reserve).into())
-	}
+			Self::deposit_event(Event::AssetFeeBoundsUpdated { asset_id, bounds });
 
-	/// Set new state of asset.
-	/// This converts the new state into correct state type ( by removing the reserve)
-	fn set_asset_state(asset_id: T::AssetId, new_state: AssetReserveState<Balance>) {
-		<Assets<T>>::insert(asset_id, Into::<AssetState<Balance>>::into(new_state));
-	}
+			Ok(())
+		}
 
-	/// Generate an nft instance id and mint NFT into the class and instance.
-	#[require_transactional]
-	fn create_and_mint_position_instance(owner: &T::AccountId) -> Result<T::PositionItemId, DispatchError> {
-		<NextPositionId<T>>::try_mutate(|current_value| -> Result<T::PositionItemId, DispatchError> {
-			let next_position_id = *current_value;
+		/// Override asset fee and protocol fee of an asset.
+		///
+		/// Overridden fees take precedence over dynamic fees and `AssetFee` and `ProtocolFee`.
+		/// Setting fees to `None` removes the override.
+		/// Both fees must be lower than `MAX_FEE`.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		/// - `fees`: new asset fees or `None`
+		///
+		/// Emits `AssetFeesSet` event when successful.
+		///
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_asset_fees())]
+		#[transactional]
+		pub fn set_asset_fees(origin: OriginFor<T>, asset_id: T::AssetId, fees: Option<AssetFees>) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
 
-			T::NFTHandler::mint_into(&T::NFTCollectionId::get(), &next_position_id, owner)?;
+			ensure!(Assets::<T>::contains_key(asset_id), Error::<T>::AssetNotFound);
 
-			*current_value = current_value
-				.checked_add(&T::PositionItemId::one())
-				.ok_or(ArithmeticError::Overflow)?;
+			if let Some(fees) = fees {
+				ensure!(fees.is_valid(), Error::<T>::InvalidAssetFees);
+			}
 
-			Ok(next_position_id)
-		})
-	}
+			AssetFeeOverrides::<T>::set(asset_id, fees);
 
-	/// Update Hub asset side of HDX subpool and add given amount to hub_asset_reserve
-	fn update_hdx_subpool_hub_asset(origin: T::RuntimeOrigin, hub_asset_amount: Balance) -> DispatchResult {
-		if hub_asset_amount > Balance::zero() {
-			let hdx_state = Self::load_asset_state(T::HdxAssetId::get())?;
+			Self::deposit_event(Event::AssetFeesSet { asset_id, fees });
 
-			let mut native_subpool = Assets::<T>::get(T::HdxAssetId::get()).ok_or(Error::<T>::AssetNotFound)?;
-			native_subpool.hub_reserve = native_subpool
-				.hub_reserve
-				.checked_add(hub_asset_amount)
-				.ok_or(ArithmeticError::Overflow)?;
-			<Assets<T>>::insert(T::HdxAssetId::get(), native_subpool);
+			Ok(())
+		}
 
-			let updated_hdx_state = Self::load_asset_state(T::HdxAssetId::get())?;
+		/// Remove all liquidity of a position of frozen asset on behalf of its owner.
+		///
+		/// Used to settle remaining LP positions when an asset is being delisted.
+		/// Liquidity is withdrawn at the frozen price without withdrawal fee and transferred to position's owner.
+		///
+		/// Positions locked in liquidity mining must be withdrawn from liquidity mining first.
+		///
+		/// Only `AuthorityOrigin` can perform this operation.
+		///
+		/// Parameters:
+		/// - `position_id`: The identifier of position which is settled.
+		///
+		/// Emits `PositionSettled` event when successful.
+		///
+		#[pallet::call_index(14)]
+		#[pallet::weight(<T as Config>::WeightInfo::settle_position().saturating_add(T::OmnipoolHooks::on_liquidity_changed_weight()))]
+		#[transactional]
+		pub fn settle_position(origin: OriginFor<T>, position_id: T::PositionItemId) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin.clone())?;
 
-			let delta_changes = AssetStateChange {
-				delta_hub_reserve: BalanceUpdate::Increase(hub_asset_amount),
-				..Default::default()
-			};
+			let position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotFound)?;
 
-			let info: AssetInfo<T::AssetId, Balance> =
-				AssetInfo::new(T::HdxAssetId::get(), &hdx_state, &updated_hdx_state, &delta_changes);
+			let asset_state = Self::load_asset_state(position.asset_id)?;
+			ensure!(asset_state.tradable == Tradability::FROZEN, Error::<T>::AssetNotFrozen);
 
-			T::OmnipoolHooks::on_liquidity_changed(origin, info)?;
-		}
-		Ok(())
-	}
+			let owner =
+				T::NFTHandler::owner(&T::NFTCollectionId::get(), &position_id).ok_or(Error::<T>::PositionNotFound)?;
 
-	/// Update total hub asset liquidity and write new value to storage.
-	/// Update total issueance if AdjustSupply is specified.
-	#[require_transactional]
-	fn update_hub_asset_liquidity(delta_amount: &BalanceUpdate<Balance>) -> DispatchResult {
-		match delta_amount {
-			BalanceUpdate::Increase(amount) => {
-				T::Currency::deposit(T::HubAssetId::get(), &Self::protocol_account(), *amount)
-			}
-			BalanceUpdate::Decrease(amount) => {
-				T::Currency::withdraw(T::HubAssetId::get(), &Self::protocol_account(), *amount)
-			}
+			Self::do_remove_liquidity(
+				origin,
+				owner.clone(),
+				position_id,
+				position.shares,
+				Balance::zero(),
+				Balance::zero(),
+			)?;
+
+			Self::deposit_event(Event::PositionSettled {
+				position_id,
+				owner,
+				asset_id: position.asset_id,
+			});
+
+			Ok(())
 		}
-	}
 
-	/// Update imbalance with given delta_imbalance - increase or decrease
-	fn update_imbalance(delta_imbalance: BalanceUpdate<Balance>) -> DispatchResult {
+		/// Remove frozen asset from Omnipool.
+		///
+		/// Asset must be frozen and all LP positions of the asset must be settled, ie. all remaining shares
+		/// must be owned by protocol.
+		///
+		/// Remaining asset reserve and hub asset reserve are transferred to treasury account.
+		///
+		/// Only `AuthorityOrigin` can perform this operation.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		///
+		/// Emits `TokenRemoved` event when successful.
+		///
+		#[pallet::call_index(15)]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_token())]
+		#[transactional]
+		pub fn remove_token(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				asset_id != T::HdxAssetId::get() && asset_id != T::StableCoinAssetId::get(),
+				Error::<T>::NotAllowed
+			);
+
+			let asset_state = Self::load_asset_state(asset_id)?;
+
+			ensure!(asset_state.tradable == Tradability::FROZEN, Error::<T>::AssetNotFrozen);
+			ensure!(
+				asset_state.shares == asset_state.protocol_shares,
+				Error::<T>::SharesRemaining
+			);
+			ensure!(!Listings::<T>::contains_key(asset_id), Error::<T>::ListingPending);
+
+			let treasury = T::TreasuryAccount::get();
+
+			if asset_state.reserve > Balance::zero() {
+				T::Currency::transfer(asset_id, &Self::protocol_account(), &treasury, asset_state.reserve)?;
+			}
+
+			if asset_state.hub_reserve > Balance::zero() {
+				T::Currency::transfer(
+					T::HubAssetId::get(),
+					&Self::protocol_account(),
+					&treasury,
+					asset_state.hub_reserve,
+				)?;
+			}
+
+			Self::remove_asset(asset_id)?;
+			AssetFeeBounds::<T>::remove(asset_id);
+			AssetDynamicFees::<T>::remove(asset_id);
+			AssetFeeOverrides::<T>::remove(asset_id);
+			AssetMaxPriceDifference::<T>::remove(asset_id);
+			AssetMinHoldingPeriod::<T>::remove(asset_id);
+
+			Self::deposit_event(Event::TokenRemoved {
+				asset_id,
+				amount: asset_state.reserve,
+				hub_withdrawn: asset_state.hub_reserve,
+			});
+
+			Ok(())
+		}
+
+		/// Merge positions into a position.
+		///
+		/// Merged positions are destroyed and their NFT instances burned. Amount and shares of merged positions
+		/// are added to the position `position_id` and its entry price is set to an amount-weighted
+		/// average of entry prices of all merged positions.
+		///
+		/// All positions must be owned by origin and must be positions of the same asset.
+		///
+		/// Parameters:
+		/// - `origin`: owner of positions
+		/// - `position_id`: The identifier of position which other positions are merged into.
+		/// - `positions`: The identifiers of positions to merge.
+		///
+		/// Emits `PositionDestroyed` event for each merged position and `PositionUpdated` event when successful.
+		///
+		#[pallet::call_index(16)]
+		#[pallet::weight(<T as Config>::WeightInfo::merge_positions(positions.len() as u32))]
+		#[transactional]
+		pub fn merge_positions(
+			origin: OriginFor<T>,
+			position_id: T::PositionItemId,
+			positions: BoundedVec<T::PositionItemId, T::MaxMergedPositions>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let position = Self::load_position(position_id, who.clone())?;
+
+			let mut amount = position.amount;
+			let mut shares = position.shares;
+			let mut prices = sp_std::vec![(position.amount, position.price)];
+			// merged position keeps the latest creation block, so holding period cannot be bypassed by merging
+			let mut created_at = <PositionCreatedAt<T>>::get(position_id);
+
+			for merged_id in positions.iter() {
+				ensure!(*merged_id != position_id, Error::<T>::DuplicatePosition);
+
+				let merged = Self::load_position(*merged_id, who.clone())?;
+
+				ensure!(merged.asset_id == position.asset_id, Error::<T>::PositionAssetMismatch);
+
+				amount = amount.checked_add(merged.amount).ok_or(ArithmeticError::Overflow)?;
+				shares = shares.checked_add(merged.shares).ok_or(ArithmeticError::Overflow)?;
+				prices.push((merged.amount, merged.price));
+
+				<Positions<T>>::remove(merged_id);
+				if let Some(merged_created_at) = <PositionCreatedAt<T>>::take(merged_id) {
+					created_at = created_at.max(Some(merged_created_at));
+				}
+				T::NFTHandler::burn(&T::NFTCollectionId::get(), merged_id, Some(&who))?;
+
+				Self::deposit_event(Event::PositionDestroyed {
+					position_id: *merged_id,
+					owner: who.clone(),
+				});
+			}
+
+			let price = crate::math::calculate_merged_position_price(&prices).unwrap_or(position.price);
+
+			let updated_position = Position::<Balance, T::AssetId> {
+				asset_id: position.asset_id,
+				amount,
+				shares,
+				price,
+			};
+
+			Self::deposit_event(Event::PositionUpdated {
+				position_id,
+				owner: who,
+				asset: updated_position.asset_id,
+				amount: updated_position.amount,
+				shares: updated_position.shares,
+				price: updated_position
+					.price_from_rational()
+					.ok_or(ArithmeticError::DivisionByZero)?,
+			});
+
+			<Positions<T>>::insert(position_id, updated_position);
+			<PositionCreatedAt<T>>::set(position_id, created_at);
+
+			Ok(())
+		}
+
+		/// Split position into two positions.
+		///
+		/// Given amount of shares is moved from the position to a new position with the same entry price.
+		/// Amount of the position is divided proportionally to the shares. New NFT instance is minted to the owner.
+		///
+		/// Parameters:
+		/// - `origin`: owner of position
+		/// - `position_id`: The identifier of position to split.
+		/// - `shares`: Amount of shares moved to the new position.
+		///
+		/// Emits `PositionUpdated` and `PositionCreated` events when successful.
+		///
+		#[pallet::call_index(17)]
+		#[pallet::weight(<T as Config>::WeightInfo::split_position())]
+		#[transactional]
+		pub fn split_position(origin: OriginFor<T>, position_id: T::PositionItemId, shares: Balance) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(shares > Balance::zero(), Error::<T>::InvalidSharesAmount);
+
+			let position = Self::load_position(position_id, who.clone())?;
+
+			ensure!(position.shares > shares, Error::<T>::InsufficientShares);
+
+			let split_amount = crate::math::calculate_split_position_amount(position.amount, shares, position.shares)
+				.ok_or(ArithmeticError::Overflow)?;
+
+			let updated_position = position
+				.delta_update(&BalanceUpdate::Decrease(split_amount), &BalanceUpdate::Decrease(shares))
+				.ok_or(ArithmeticError::Overflow)?;
+
+			let new_position = Position::<Balance, T::AssetId> {
+				asset_id: updated_position.asset_id,
+				amount: split_amount,
+				shares,
+				price: updated_position.price,
+			};
+
+			let price = new_position
+				.price_from_rational()
+				.ok_or(ArithmeticError::DivisionByZero)?;
+
+			let instance_id = Self::create_and_mint_position_instance(&who)?;
+
+			<Positions<T>>::insert(instance_id, &new_position);
+			<PositionCreatedAt<T>>::set(instance_id, <PositionCreatedAt<T>>::get(position_id));
+
+			Self::deposit_event(Event::PositionUpdated {
+				position_id,
+				owner: who.clone(),
+				asset: updated_position.asset_id,
+				amount: updated_position.amount,
+				shares: updated_position.shares,
+				price,
+			});
+
+			<Positions<T>>::insert(position_id, updated_position);
+
+			Self::deposit_event(Event::PositionCreated {
+				position_id: instance_id,
+				owner: who,
+				asset: new_position.asset_id,
+				amount: split_amount,
+				shares,
+				price,
+			});
+
+			Ok(())
+		}
+
+		/// Execute a swap of `asset_in` for `asset_out` if included in a block not later than `deadline`.
+		///
+		/// Same as `sell` but fails with `DeadlinePassed` error if current block number is greater than `deadline`.
+		///
+		/// Parameters:
+		/// - `asset_in`: ID of asset sold to the pool
+		/// - `asset_out`: ID of asset bought from the pool
+		/// - `amount`: Amount of asset sold
+		/// - `min_buy_amount`: Minimum amount required to receive
+		/// - `deadline`: Last block number in which the trade can be executed
+		///
+		/// Emits `SellExecuted` event when successful.
+		///
+		#[pallet::call_index(19)]
+		#[pallet::weight(<T as Config>::WeightInfo::sell()
+			.saturating_add(T::OmnipoolHooks::on_trade_weight())
+			.saturating_add(T::OmnipoolHooks::on_liquidity_changed_weight())
+		)]
+		#[transactional]
+		pub fn sell_with_deadline(
+			origin: OriginFor<T>,
+			asset_in: T::AssetId,
+			asset_out: T::AssetId,
+			amount: Balance,
+			min_buy_amount: Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			Self::ensure_deadline(deadline)?;
+
+			Self::sell(origin, asset_in, asset_out, amount, min_buy_amount)
+		}
+
+		/// Execute a swap of `asset_out` for `asset_in` if included in a block not later than `deadline`.
+		///
+		/// Same as `buy` but fails with `DeadlinePassed` error if current block number is greater than `deadline`.
+		///
+		/// Parameters:
+		/// - `asset_in`: ID of asset sold to the pool
+		/// - `asset_out`: ID of asset bought from the pool
+		/// - `amount`: Amount of asset bought
+		/// - `max_sell_amount`: Maximum amount to be sold.
+		/// - `deadline`: Last block number in which the trade can be executed
+		///
+		/// Emits `BuyExecuted` event when successful.
+		///
+		#[pallet::call_index(20)]
+		#[pallet::weight(<T as Config>::WeightInfo::buy()
+			.saturating_add(T::OmnipoolHooks::on_trade_weight())
+			.saturating_add(T::OmnipoolHooks::on_liquidity_changed_weight())
+		)]
+		#[transactional]
+		pub fn buy_with_deadline(
+			origin: OriginFor<T>,
+			asset_out: T::AssetId,
+			asset_in: T::AssetId,
+			amount: Balance,
+			max_sell_amount: Balance,
+			deadline: T::BlockNumber,
+		) -> DispatchResult {
+			Self::ensure_deadline(deadline)?;
+
+			Self::buy(origin, asset_out, asset_in, amount, max_sell_amount)
+		}
+
+		/// Set max allowed price difference of an asset checked by price barrier.
+		///
+		/// Overrides max allowed price difference configured for all assets.
+		/// Setting it to `None` removes the override.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		/// - `max_difference`: new max allowed price difference or `None`
+		///
+		/// Emits `AssetMaxPriceDifferenceSet` event when successful.
+		///
+		#[pallet::call_index(21)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_asset_max_price_difference())]
+		#[transactional]
+		pub fn set_asset_max_price_difference(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			max_difference: Option<Permill>,
+		) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
+
+			ensure!(Assets::<T>::contains_key(asset_id), Error::<T>::AssetNotFound);
+
+			AssetMaxPriceDifference::<T>::set(asset_id, max_difference);
+
+			Self::deposit_event(Event::AssetMaxPriceDifferenceSet {
+				asset_id,
+				max_difference,
+			});
+
+			Ok(())
+		}
+
+		/// Withdraw liquidity owned by protocol to treasury account.
+		///
+		/// Removes `amount` of protocol shares of an asset using the same state changes as `remove_liquidity`.
+		/// Protocol shares have no entry price, so they are withdrawn at current spot price without withdrawal fee.
+		///
+		/// Only `AuthorityOrigin` can perform this operation.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		/// - `amount`: Amount of protocol shares to withdraw
+		///
+		/// Emits `ProtocolLiquidityRemoved` event when successful.
+		///
+		#[pallet::call_index(22)]
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_protocol_liquidity().saturating_add(T::OmnipoolHooks::on_liquidity_changed_weight()))]
+		#[transactional]
+		pub fn withdraw_protocol_liquidity(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			amount: Balance,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin.clone())?;
+
+			ensure!(amount > Balance::zero(), Error::<T>::InvalidSharesAmount);
+
+			let asset_state = Self::load_asset_state(asset_id)?;
+
+			ensure!(asset_state.protocol_shares >= amount, Error::<T>::InsufficientShares);
+
+			let current_imbalance = <HubAssetImbalance<T>>::get();
+			let current_hub_asset_liquidity = Self::get_hub_asset_balance_of_protocol_account();
+
+			let position = hydra_dx_math::omnipool::types::Position::<Balance> {
+				amount: Balance::zero(),
+				shares: amount,
+				price: asset_state.price_as_rational(),
+			};
+
+			let state_changes = hydra_dx_math::omnipool::calculate_remove_liquidity_state_changes(
+				&(&asset_state).into(),
+				amount,
+				&position,
+				I129 {
+					value: current_imbalance.value,
+					negative: current_imbalance.negative,
+				},
+				current_hub_asset_liquidity,
+				FixedU128::zero(),
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+
+			let mut new_asset_state = asset_state
+				.clone()
+				.delta_update(&state_changes.asset)
+				.ok_or(ArithmeticError::Overflow)?;
+
+			new_asset_state.protocol_shares = new_asset_state
+				.protocol_shares
+				.checked_sub(amount)
+				.ok_or(ArithmeticError::Underflow)?;
+
+			let treasury = T::TreasuryAccount::get();
+
+			T::Currency::transfer(
+				asset_id,
+				&Self::protocol_account(),
+				&treasury,
+				*state_changes.asset.delta_reserve,
+			)?;
+
+			Self::update_imbalance(state_changes.delta_imbalance)?;
+
+			// burn only difference between delta hub and hub amount withdrawn to treasury.
+			Self::update_hub_asset_liquidity(
+				&state_changes
+					.asset
+					.delta_hub_reserve
+					.merge(BalanceUpdate::Increase(state_changes.lp_hub_amount))
+					.ok_or(ArithmeticError::Overflow)?,
+			)?;
+
+			if state_changes.lp_hub_amount > Balance::zero() {
+				T::Currency::transfer(
+					T::HubAssetId::get(),
+					&Self::protocol_account(),
+					&treasury,
+					state_changes.lp_hub_amount,
+				)?;
+			}
+
+			// Callback hook info
+			let info: AssetInfo<T::AssetId, Balance> =
+				AssetInfo::new(asset_id, &asset_state, &new_asset_state, &state_changes.asset);
+
+			Self::set_asset_state(asset_id, new_asset_state);
+
+			Self::deposit_event(Event::ProtocolLiquidityRemoved {
+				beneficiary: treasury,
+				asset_id,
+				amount: *state_changes.asset.delta_reserve,
+				hub_amount: state_changes.lp_hub_amount,
+				shares_removed: amount,
+			});
+
+			T::OmnipoolHooks::on_liquidity_changed(origin, info)?;
+
+			Ok(())
+		}
+
+		/// Reduce negative hub asset imbalance.
+		///
+		/// Burns `amount` of hub asset from treasury account and decreases the imbalance by the same amount.
+		/// Hub asset is collected in treasury account when protocol liquidity is withdrawn or asset is removed.
+		///
+		/// Only `AuthorityOrigin` can perform this operation.
+		///
+		/// Parameters:
+		/// - `amount`: Amount of hub asset to burn. Must not exceed current imbalance.
+		///
+		/// Emits `ImbalanceReduced` event when successful.
+		///
+		#[pallet::call_index(23)]
+		#[pallet::weight(<T as Config>::WeightInfo::reduce_imbalance())]
+		#[transactional]
+		pub fn reduce_imbalance(origin: OriginFor<T>, amount: Balance) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			let current_imbalance = <HubAssetImbalance<T>>::get();
+			ensure!(current_imbalance.value >= amount, Error::<T>::ImbalanceExceeded);
+
+			T::Currency::withdraw(T::HubAssetId::get(), &T::TreasuryAccount::get(), amount)?;
+
+			Self::update_imbalance(BalanceUpdate::Increase(amount))?;
+
+			Self::deposit_event(Event::ImbalanceReduced {
+				amount,
+				imbalance: <HubAssetImbalance<T>>::get(),
+			});
+
+			Ok(())
+		}
+
+		/// Set accounts receiving protocol fees and their shares of the fees.
+		///
+		/// Protocol fee of a trade which is not used to reduce hub asset imbalance is accrued in hub asset
+		/// to beneficiaries according to their shares. Remaining part is added to HDX subpool.
+		/// Setting empty list adds all protocol fees to HDX subpool.
+		///
+		/// Accrued fees are not transferred during trades. Beneficiaries claim them with `claim_protocol_fees`.
+		/// Fees accrued before the beneficiaries are replaced stay claimable.
+		///
+		/// Only `AuthorityOrigin` can perform this operation.
+		///
+		/// Parameters:
+		/// - `beneficiaries`: list of beneficiary accounts and their shares of protocol fees
+		///
+		/// Emits `ProtocolFeeBeneficiariesSet` event when successful.
+		///
+		#[pallet::call_index(24)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_protocol_fee_beneficiaries())]
+		#[transactional]
+		pub fn set_protocol_fee_beneficiaries(
+			origin: OriginFor<T>,
+			beneficiaries: BoundedVec<(T::AccountId, Permill), T::MaxProtocolFeeBeneficiaries>,
+		) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			let mut total = Permill::zero();
+			for (idx, (beneficiary, share)) in beneficiaries.iter().enumerate() {
+				ensure!(
+					!beneficiaries.iter().skip(idx + 1).any(|(b, _)| b == beneficiary),
+					Error::<T>::InvalidProtocolFeeBeneficiaries
+				);
+				total = total
+					.checked_add(share)
+					.ok_or(Error::<T>::InvalidProtocolFeeBeneficiaries)?;
+			}
+
+			// fees accrued so far in this block are accounted to previous beneficiaries
+			Self::deposit_protocol_fees_events();
+
+			ProtocolFeeBeneficiaries::<T>::put(&beneficiaries);
+
+			Self::deposit_event(Event::ProtocolFeeBeneficiariesSet {
+				beneficiaries: beneficiaries.into_inner(),
+			});
+
+			Ok(())
+		}
+
+		/// Propose new token to be added to Omnipool without governance approval.
+		///
+		/// `amount` of the asset is transferred from origin to pool's account as initial liquidity and
+		/// `ListingBond` of native asset is reserved from origin. Position NFT token is minted for origin.
+		///
+		/// Asset cannot be traded and liquidity cannot be added until the listing is approved, so hub asset
+		/// minted at the proposed `initial_price` cannot be extracted from the pool. Only removing liquidity is
+		/// allowed, which lets the proposer withdraw the initial liquidity at the same price.
+		///
+		/// Asset's weight cap is set to `ListingWeightCap` and the hub reserve minted for the asset must not exceed it.
+		///
+		/// Listing can be rejected by `AuthorityOrigin` until the end of challenge period, see `reject_listing`.
+		/// Afterwards, anyone can finalize the listing by `approve_listing`.
+		///
+		/// Parameters:
+		/// - `origin`: proposer of the asset
+		/// - `asset`: The identifier of the new asset added to the pool. Must be registered in Asset registry
+		/// - `amount`: initial liquidity of the asset
+		/// - `initial_price`: Initial price
+		///
+		/// Emits `TokenAdded` and `ListingProposed` events when successful.
+		///
+		#[pallet::call_index(25)]
+		#[pallet::weight(<T as Config>::WeightInfo::propose_token().saturating_add(T::OmnipoolHooks::on_liquidity_changed_weight()))]
+		#[transactional]
+		pub fn propose_token(
+			origin: OriginFor<T>,
+			asset: T::AssetId,
+			amount: Balance,
+			initial_price: Price,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+
+			ensure!(asset != T::HubAssetId::get(), Error::<T>::NotAllowed);
+			ensure!(!Assets::<T>::contains_key(asset), Error::<T>::AssetAlreadyAdded);
+
+			let bond = T::ListingBond::get();
+			T::Currency::reserve(T::HdxAssetId::get(), &who, bond)?;
+
+			T::Currency::transfer(asset, &who, &Self::protocol_account(), amount)?;
+
+			let position_id = Self::do_add_token(
+				origin,
+				asset,
+				initial_price,
+				T::ListingWeightCap::get(),
+				Tradability::REMOVE_LIQUIDITY,
+				who.clone(),
+			)?;
+
+			Self::ensure_weight_cap(asset)?;
+
+			let challenge_end = <frame_system::Pallet<T>>::block_number()
+				.checked_add(&T::ListingChallengePeriod::get())
+				.ok_or(ArithmeticError::Overflow)?;
+
+			Listings::<T>::insert(
+				asset,
+				Listing {
+					proposer: who.clone(),
+					bond,
+					position_id,
+					challenge_end,
+				},
+			);
+
+			Self::deposit_event(Event::ListingProposed {
+				asset_id: asset,
+				proposer: who,
+				bond,
+				position_id,
+				challenge_end,
+			});
+
+			Ok(())
+		}
+
+		/// Finalize listing of an asset after its challenge period has passed.
+		///
+		/// Asset becomes fully tradable and the bond is unreserved.
+		///
+		/// Weight cap of the asset deliberately stays at `ListingWeightCap`. Raising it is left to governance
+		/// by `set_asset_weight_cap`, so that a permissionless listing cannot grow to a large share of the pool.
+		///
+		/// Can be called by anyone.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		///
+		/// Emits `TradableStateUpdated` and `ListingApproved` events when successful.
+		///
+		#[pallet::call_index(26)]
+		#[pallet::weight(<T as Config>::WeightInfo::approve_listing())]
+		#[transactional]
+		pub fn approve_listing(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let listing = Listings::<T>::take(asset_id).ok_or(Error::<T>::ListingNotFound)?;
+
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() > listing.challenge_end,
+				Error::<T>::ChallengePeriodNotOver
+			);
+
+			Assets::<T>::try_mutate(asset_id, |maybe_asset| -> DispatchResult {
+				let asset_state = maybe_asset.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+
+				asset_state.tradable = Tradability::default();
+				Self::deposit_event(Event::TradableStateUpdated {
+					asset_id,
+					state: asset_state.tradable,
+				});
+
+				Ok(())
+			})?;
+
+			T::Currency::unreserve(T::HdxAssetId::get(), &listing.proposer, listing.bond);
+
+			Self::deposit_event(Event::ListingApproved {
+				asset_id,
+				proposer: listing.proposer,
+			});
+
+			Ok(())
+		}
+
+		/// Reject listing of an asset during its challenge period.
+		///
+		/// Asset is removed from Omnipool and its whole reserve is refunded to the owner of the listing position.
+		/// If the position has been sacrificed, reserve is transferred to treasury account.
+		/// Hub asset reserve of the asset is transferred to treasury account.
+		///
+		/// Bond of the proposer is slashed and transferred to treasury account.
+		///
+		/// Only `AuthorityOrigin` can perform this operation.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		///
+		/// Emits `AssetRefunded` and `ListingRejected` events when successful.
+		///
+		#[pallet::call_index(27)]
+		#[pallet::weight(<T as Config>::WeightInfo::reject_listing())]
+		#[transactional]
+		pub fn reject_listing(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			T::AuthorityOrigin::ensure_origin(origin)?;
+
+			let listing = Listings::<T>::take(asset_id).ok_or(Error::<T>::ListingNotFound)?;
+
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() <= listing.challenge_end,
+				Error::<T>::ChallengePeriodOver
+			);
+
+			let asset_state = Self::load_asset_state(asset_id)?;
+
+			let treasury = T::TreasuryAccount::get();
+
+			let recipient = if let Some(owner) = T::NFTHandler::owner(&T::NFTCollectionId::get(), &listing.position_id)
+			{
+				<Positions<T>>::remove(listing.position_id);
+				<PositionCreatedAt<T>>::remove(listing.position_id);
+				T::NFTHandler::burn(&T::NFTCollectionId::get(), &listing.position_id, Some(&owner))?;
+
+				Self::deposit_event(Event::PositionDestroyed {
+					position_id: listing.position_id,
+					owner: owner.clone(),
+				});
+
+				owner
+			} else {
+				treasury.clone()
+			};
+
+			if asset_state.hub_reserve > Balance::zero() {
+				T::Currency::transfer(
+					T::HubAssetId::get(),
+					&Self::protocol_account(),
+					&treasury,
+					asset_state.hub_reserve,
+				)?;
+			}
+
+			Self::remove_asset(asset_id)?;
+			AssetFeeBounds::<T>::remove(asset_id);
+			AssetDynamicFees::<T>::remove(asset_id);
+			AssetFeeOverrides::<T>::remove(asset_id);
+			AssetMaxPriceDifference::<T>::remove(asset_id);
+			AssetMinHoldingPeriod::<T>::remove(asset_id);
+
+			Self::do_refund_asset(asset_id, asset_state.reserve, recipient)?;
+
+			let bond_slashed = listing.bond.saturating_sub(T::Currency::repatriate_reserved(
+				T::HdxAssetId::get(),
+				&listing.proposer,
+				&treasury,
+				listing.bond,
+				BalanceStatus::Free,
+			)?);
+
+			Self::deposit_event(Event::ListingRejected {
+				asset_id,
+				proposer: listing.proposer,
+				bond_slashed,
+			});
+
+			Ok(())
+		}
+
+		/// Set minimum holding period of liquidity of an asset.
+		///
+		/// Liquidity of positions created by `add_liquidity` cannot be removed until `blocks` have passed
+		/// since the position was created. If `early_removal_fee` is set, liquidity can be removed earlier
+		/// but the fee is added to the withdrawal fee and stays in the pool for remaining LPs.
+		/// Setting it to `None` removes the restriction.
+		///
+		/// Parameters:
+		/// - `asset_id`: asset id
+		/// - `min_holding_period`: new minimum holding period or `None`
+		///
+		/// Emits `AssetMinHoldingPeriodSet` event when successful.
+		///
+		#[pallet::call_index(28)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_asset_min_holding_period())]
+		#[transactional]
+		pub fn set_asset_min_holding_period(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			min_holding_period: Option<MinHoldingPeriod<T::BlockNumber>>,
+		) -> DispatchResult {
+			T::TechnicalOrigin::ensure_origin(origin)?;
+
+			ensure!(Assets::<T>::contains_key(asset_id), Error::<T>::AssetNotFound);
+
+			AssetMinHoldingPeriod::<T>::set(asset_id, min_holding_period);
+
+			Self::deposit_event(Event::AssetMinHoldingPeriodSet {
+				asset_id,
+				min_holding_period,
+			});
+
+			Ok(())
+		}
+
+		/// Claim protocol fees accrued to origin as protocol fee beneficiary.
+		///
+		/// Accrued amount of hub asset is minted to origin.
+		///
+		/// Parameters:
+		/// - `origin`: beneficiary of protocol fees
+		///
+		/// Emits `ProtocolFeesClaimed` event when successful.
+		///
+		#[pallet::call_index(29)]
+		#[pallet::weight(<T as Config>::WeightInfo::claim_protocol_fees())]
+		#[transactional]
+		pub fn claim_protocol_fees(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let amount = AccruedProtocolFees::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::NoProtocolFeesToClaim);
+
+			T::Currency::deposit(T::HubAssetId::get(), &who, amount)?;
+
+			Self::deposit_event(Event::ProtocolFeesClaimed {
+				beneficiary: who,
+				amount,
+			});
+
+			Ok(())
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			// on_finalize reads and kills protocol fees accrued in the block
+			T::DbWeight::get().reads_writes(1, 1)
+		}
+
+		fn on_finalize(_n: BlockNumberFor<T>) {
+			Self::deposit_protocol_fees_events();
+		}
+
+		fn integrity_test() {
+			assert_ne!(
+				T::MinimumPoolLiquidity::get(),
+				Balance::zero(),
+				"Minimum pool liquidity is 0."
+			);
+			assert_ne!(
+				T::MinimumTradingLimit::get(),
+				Balance::zero(),
+				"Minimum trading limit is 0."
+			);
+			assert_ne!(
+				T::HdxAssetId::get(),
+				T::StableCoinAssetId::get(),
+				"Same Hdx asset id and stable asset id."
+			);
+			assert_ne!(T::MaxInRatio::get(), Balance::zero(), "MaxInRatio is 0.");
+			assert_ne!(T::MaxOutRatio::get(), Balance::zero(), "MaxOutRatio is 0.");
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Protocol account address
+	pub fn protocol_account() -> T::AccountId {
+		PalletId(*b"omnipool").into_account_truncating()
+	}
+
+	/// Retrieve stable asset detail from the pool.
+	/// Return NoStableCoinInPool if stable asset is not yet in the pool.
+	fn stable_asset() -> Result<(Balance, Balance), DispatchError> {
+		let stable_asset = <Assets<T>>::get(T::StableCoinAssetId::get()).ok_or(Error::<T>::NoStableAssetInPool)?;
+		let stable_reserve = T::Currency::free_balance(T::StableCoinAssetId::get(), &Self::protocol_account());
+		Ok((stable_reserve, stable_asset.hub_reserve))
+	}
+
+	/// Ensure that current block number is not greater than trade `deadline`.
+	fn ensure_deadline(deadline: T::BlockNumber) -> DispatchResult {
+		ensure!(
+			<frame_system::Pallet<T>>::block_number() <= deadline,
+			Error::<T>::DeadlinePassed
+		);
+		Ok(())
+	}
+
+	/// Retrieve state of asset from the pool and its pool balance
+	pub fn load_asset_state(asset_id: T::AssetId) -> Result<AssetReserveState<Balance>, DispatchError> {
+		let state = <Assets<T>>::get(asset_id).ok_or(Error::<T>::AssetNotFound)?;
+		let reserve = T::Currency::free_balance(asset_id, &Self::protocol_account());
+		Ok((state, reserve).into())
+	}
+
+	/// Set new state of asset.
+	/// This converts the new state into correct state type ( by removing the reserve)
+	fn set_asset_state(asset_id: T::AssetId, new_state: AssetReserveState<Balance>) {
+		<Assets<T>>::insert(asset_id, Into::<AssetState<Balance>>::into(new_state));
+	}
+
+	/// Generate an nft instance id and mint NFT into the class and instance.
+	#[require_transactional]
+	fn create_and_mint_position_instance(owner: &T::AccountId) -> Result<T::PositionItemId, DispatchError> {
+		<NextPositionId<T>>::try_mutate(|current_value| -> Result<T::PositionItemId, DispatchError> {
+			let next_position_id = *current_value;
+
+			T::NFTHandler::mint_into(&T::NFTCollectionId::get(), &next_position_id, owner)?;
+
+			*current_value = current_value
+				.checked_add(&T::PositionItemId::one())
+				.ok_or(ArithmeticError::Overflow)?;
+
+			Ok(next_position_id)
+		})
+	}
+
+	/// Accrue protocol fee in hub asset to protocol fee beneficiaries.
+	/// Part which is not accrued is added to HDX subpool.
+	///
+	/// Accrued hub asset is burned from protocol account, so that its balance keeps matching hub reserves,
+	/// and is minted to beneficiaries when claimed. Nothing is transferred to beneficiaries here,
+	/// so a beneficiary account cannot make trades fail.
+	fn process_protocol_fee(origin: T::RuntimeOrigin, hub_asset_amount: Balance) -> DispatchResult {
+		let beneficiaries = ProtocolFeeBeneficiaries::<T>::get();
+
+		if hub_asset_amount.is_zero() || beneficiaries.is_empty() {
+			return Self::update_hdx_subpool_hub_asset(origin, hub_asset_amount);
+		}
+
+		let mut remaining = hub_asset_amount;
+
+		BlockProtocolFees::<T>::try_mutate(|block_fees| -> DispatchResult {
+			for (beneficiary, share) in beneficiaries {
+				let amount = share.mul_floor(hub_asset_amount);
+				if amount.is_zero() {
+					continue;
+				}
+
+				AccruedProtocolFees::<T>::try_mutate(&beneficiary, |accrued| -> DispatchResult {
+					*accrued = accrued.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+					Ok(())
+				})?;
+
+				remaining = remaining.checked_sub(amount).ok_or(ArithmeticError::Underflow)?;
+
+				if let Some((_, block_amount)) = block_fees.iter_mut().find(|(b, _)| *b == beneficiary) {
+					*block_amount = block_amount.checked_add(amount).ok_or(ArithmeticError::Overflow)?;
+				} else {
+					block_fees
+						.try_push((beneficiary, amount))
+						.map_err(|_| Error::<T>::InvalidProtocolFeeBeneficiaries)?;
+				}
+			}
+			Ok(())
+		})?;
+
+		let accrued = hub_asset_amount
+			.checked_sub(remaining)
+			.ok_or(ArithmeticError::Underflow)?;
+		if !accrued.is_zero() {
+			T::Currency::withdraw(T::HubAssetId::get(), &Self::protocol_account(), accrued)?;
+		}
+
+		Self::update_hdx_subpool_hub_asset(origin, remaining)
+	}
+
+	/// Emit events for protocol fees accrued to beneficiaries since last call and reset the accounting.
+	fn deposit_protocol_fees_events() {
+		for (beneficiary, amount) in BlockProtocolFees::<T>::take() {
+			Self::deposit_event(Event::ProtocolFeesAccrued { beneficiary, amount });
+		}
+	}
+
+	/// Update Hub asset side of HDX subpool and add given amount to hub_asset_reserve
+	fn update_hdx_subpool_hub_asset(origin: T::RuntimeOrigin, hub_asset_amount: Balance) -> DispatchResult {
+		if hub_asset_amount > Balance::zero() {
+			let hdx_state = Self::load_asset_state(T::HdxAssetId::get())?;
+
+			let mut native_subpool = Assets::<T>::get(T::HdxAssetId::get()).ok_or(Error::<T>::AssetNotFound)?;
+			native_subpool.hub_reserve = native_subpool
+				.hub_reserve
+				.checked_add(hub_asset_amount)
+				.ok_or(ArithmeticError::Overflow)?;
+			<Assets<T>>::insert(T::HdxAssetId::get(), native_subpool);
+
+			let updated_hdx_state = Self::load_asset_state(T::HdxAssetId::get())?;
+
+			let delta_changes = AssetStateChange {
+				delta_hub_reserve: BalanceUpdate::Increase(hub_asset_amount),
+				..Default::default()
+			};
+
+			let info: AssetInfo<T::AssetId, Balance> =
+				AssetInfo::new(T::HdxAssetId::get(), &hdx_state, &updated_hdx_state, &delta_changes);
+
+			T::OmnipoolHooks::on_liquidity_changed(origin, info)?;
+		}
+		Ok(())
+	}
+
+	/// Update total hub asset liquidity and write new value to storage.
+	/// Update total issueance if AdjustSupply is specified.
+	#[require_transactional]
+	fn update_hub_asset_liquidity(delta_amount: &BalanceUpdate<Balance>) -> DispatchResult {
+		match delta_amount {
+			BalanceUpdate::Increase(amount) => {
+				T::Currency::deposit(T::HubAssetId::get(), &Self::protocol_account(), *amount)
+			}
+			BalanceUpdate::Decrease(amount) => {
+				T::Currency::withdraw(T::HubAssetId::get(), &Self::protocol_account(), *amount)
+			}
+		}
+	}
+
+	/// Update imbalance with given delta_imbalance - increase or decrease
+	fn update_imbalance(delta_imbalance: BalanceUpdate<Balance>) -> DispatchResult {
 		<HubAssetImbalance<T>>::try_mutate(|current_imbalance| -> DispatchResult {
 			*current_imbalance = match delta_imbalance {
 				BalanceUpdate::Decrease(amount) => (*current_imbalance).sub(amount).ok_or(ArithmeticError::Overflow)?,
@@ -1618,232 +2504,876 @@ impl<T: Config> Pallet<T> {
 		asset_in.tradable.contains(Tradability::SELL) && asset_out.tradable.contains(Tradability::BUY)
 	}
 
-	/// Swap hub asset for asset_out.
-	/// Special handling of sell trade where asset in is Hub Asset.
-	fn sell_hub_asset(
+	/// Swap hub asset for asset_out.
+	/// Special handling of sell trade where asset in is Hub Asset.
+	fn sell_hub_asset(
+		origin: T::RuntimeOrigin,
+		who: &T::AccountId,
+		asset_out: T::AssetId,
+		amount: Balance,
+		limit: Balance,
+	) -> DispatchResult {
+		ensure!(
+			HubAssetTradability::<T>::get().contains(Tradability::SELL),
+			Error::<T>::NotAllowed
+		);
+
+		let asset_state = Self::load_asset_state(asset_out)?;
+
+		ensure!(asset_state.tradable.contains(Tradability::BUY), Error::<T>::NotAllowed);
+		ensure!(
+			amount
+				<= asset_state
+					.hub_reserve
+					.checked_div(T::MaxInRatio::get())
+					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+
+		let current_hub_asset_liquidity = Self::get_hub_asset_balance_of_protocol_account();
+
+		let state_changes = hydra_dx_math::omnipool::calculate_sell_hub_state_changes(
+			&(&asset_state).into(),
+			amount,
+			Self::asset_fee(asset_out),
+			I129 {
+				value: current_imbalance.value,
+				negative: current_imbalance.negative,
+			},
+			current_hub_asset_liquidity,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		ensure!(
+			*state_changes.asset.delta_reserve >= limit,
+			Error::<T>::BuyLimitNotReached
+		);
+
+		ensure!(
+			*state_changes.asset.delta_reserve
+				<= asset_state
+					.reserve
+					.checked_div(T::MaxOutRatio::get())
+					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		let new_asset_out_state = asset_state
+			.clone()
+			.delta_update(&state_changes.asset)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		// Token updates
+		T::Currency::transfer(
+			T::HubAssetId::get(),
+			who,
+			&Self::protocol_account(),
+			*state_changes.asset.delta_hub_reserve,
+		)?;
+		T::Currency::transfer(
+			asset_out,
+			&Self::protocol_account(),
+			who,
+			*state_changes.asset.delta_reserve,
+		)?;
+
+		let info: AssetInfo<T::AssetId, Balance> =
+			AssetInfo::new(asset_out, &asset_state, &new_asset_out_state, &state_changes.asset);
+
+		Self::update_imbalance(state_changes.delta_imbalance)?;
+
+		Self::set_asset_state(asset_out, new_asset_out_state);
+
+		Self::deposit_event(Event::SellExecuted {
+			who: who.clone(),
+			asset_in: T::HubAssetId::get(),
+			asset_out,
+			amount_in: *state_changes.asset.delta_hub_reserve,
+			amount_out: *state_changes.asset.delta_reserve,
+		});
+
+		Self::on_hub_asset_trade(origin, info)?;
+
+		Ok(())
+	}
+
+	/// Swap asset for Hub Asset
+	/// Special handling of buy trade where asset in is Hub Asset.
+	fn buy_asset_for_hub_asset(
+		origin: T::RuntimeOrigin,
+		who: &T::AccountId,
+		asset_out: T::AssetId,
+		amount: Balance,
+		limit: Balance,
+	) -> DispatchResult {
+		ensure!(
+			HubAssetTradability::<T>::get().contains(Tradability::SELL),
+			Error::<T>::NotAllowed
+		);
+
+		let asset_state = Self::load_asset_state(asset_out)?;
+
+		ensure!(asset_state.tradable.contains(Tradability::BUY), Error::<T>::NotAllowed);
+
+		ensure!(
+			amount
+				<= asset_state
+					.reserve
+					.checked_div(T::MaxOutRatio::get())
+					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+
+		let current_hub_asset_liquidity = Self::get_hub_asset_balance_of_protocol_account();
+
+		let state_changes = hydra_dx_math::omnipool::calculate_buy_for_hub_asset_state_changes(
+			&(&asset_state).into(),
+			amount,
+			Self::asset_fee(asset_out),
+			I129 {
+				value: current_imbalance.value,
+				negative: current_imbalance.negative,
+			},
+			current_hub_asset_liquidity,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		ensure!(
+			*state_changes.asset.delta_hub_reserve <= limit,
+			Error::<T>::SellLimitExceeded
+		);
+
+		ensure!(
+			*state_changes.asset.delta_hub_reserve
+				<= asset_state
+					.hub_reserve
+					.checked_div(T::MaxInRatio::get())
+					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		let new_asset_out_state = asset_state
+			.clone()
+			.delta_update(&state_changes.asset)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		T::Currency::transfer(
+			T::HubAssetId::get(),
+			who,
+			&Self::protocol_account(),
+			*state_changes.asset.delta_hub_reserve,
+		)?;
+		T::Currency::transfer(
+			asset_out,
+			&Self::protocol_account(),
+			who,
+			*state_changes.asset.delta_reserve,
+		)?;
+
+		let info: AssetInfo<T::AssetId, Balance> =
+			AssetInfo::new(asset_out, &asset_state, &new_asset_out_state, &state_changes.asset);
+
+		Self::update_imbalance(state_changes.delta_imbalance)?;
+
+		Self::set_asset_state(asset_out, new_asset_out_state);
+
+		Self::deposit_event(Event::BuyExecuted {
+			who: who.clone(),
+			asset_in: T::HubAssetId::get(),
+			asset_out,
+			amount_in: *state_changes.asset.delta_hub_reserve,
+			amount_out: *state_changes.asset.delta_reserve,
+		});
+
+		Self::on_hub_asset_trade(origin, info)?;
+
+		Ok(())
+	}
+
+	/// Add `amount` of `asset` to the pool and mint new position to `who`.
+	///
+	/// Asset weight cap and TVL cap are not checked - caller must ensure them after all liquidity is added.
+	#[require_transactional]
+	fn do_add_liquidity(
+		origin: T::RuntimeOrigin,
+		who: T::AccountId,
+		asset: T::AssetId,
+		amount: Balance,
+	) -> DispatchResult {
+		//
+		// Preconditions
+		//
+		ensure!(
+			amount >= T::MinimumPoolLiquidity::get(),
+			Error::<T>::InsufficientLiquidity
+		);
+
+		ensure!(
+			T::Currency::ensure_can_withdraw(asset, &who, amount).is_ok(),
+			Error::<T>::InsufficientBalance
+		);
+
+		let asset_state = Self::load_asset_state(asset)?;
+
+		ensure!(
+			asset_state.tradable.contains(Tradability::ADD_LIQUIDITY),
+			Error::<T>::NotAllowed
+		);
+
+		T::PriceBarrier::ensure_price(
+			&who,
+			T::HubAssetId::get(),
+			asset,
+			EmaPrice::new(asset_state.hub_reserve, asset_state.reserve),
+		)
+		.map_err(|_| Error::<T>::PriceDifferenceTooHigh)?;
+
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+		let current_hub_asset_liquidity = T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account());
+
+		//
+		// Calculate add liquidity state changes
+		//
+		let state_changes = hydra_dx_math::omnipool::calculate_add_liquidity_state_changes(
+			&(&asset_state).into(),
+			amount,
+			I129 {
+				value: current_imbalance.value,
+				negative: current_imbalance.negative,
+			},
+			current_hub_asset_liquidity,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		let new_asset_state = asset_state
+			.clone()
+			.delta_update(&state_changes.asset)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		//
+		// Post - update states
+		//
+
+		// Create LP position with given shares
+		let lp_position = Position::<Balance, T::AssetId> {
+			asset_id: asset,
+			amount,
+			shares: *state_changes.asset.delta_shares,
+			// Note: position needs price after asset state is updated.
+			price: (new_asset_state.hub_reserve, new_asset_state.reserve),
+		};
+
+		let instance_id = Self::create_and_mint_position_instance(&who)?;
+
+		<Positions<T>>::insert(instance_id, lp_position);
+		<PositionCreatedAt<T>>::insert(instance_id, <frame_system::Pallet<T>>::block_number());
+
+		Self::deposit_event(Event::PositionCreated {
+			position_id: instance_id,
+			owner: who.clone(),
+			asset,
+			amount,
+			shares: *state_changes.asset.delta_shares,
+			price: new_asset_state.price().ok_or(ArithmeticError::DivisionByZero)?,
+		});
+
+		T::Currency::transfer(
+			asset,
+			&who,
+			&Self::protocol_account(),
+			*state_changes.asset.delta_reserve,
+		)?;
+
+		debug_assert_eq!(*state_changes.asset.delta_reserve, amount);
+
+		// Callback hook info
+		let info: AssetInfo<T::AssetId, Balance> =
+			AssetInfo::new(asset, &asset_state, &new_asset_state, &state_changes.asset);
+
+		Self::update_imbalance(state_changes.delta_imbalance)?;
+
+		Self::update_hub_asset_liquidity(&state_changes.asset.delta_hub_reserve)?;
+
+		Self::set_asset_state(asset, new_asset_state);
+
+		Self::deposit_event(Event::LiquidityAdded {
+			who,
+			asset_id: asset,
+			amount,
+			position_id: instance_id,
+		});
+
+		T::OmnipoolHooks::on_liquidity_changed(origin, info)?;
+
+		Ok(())
+	}
+
+	/// Ensure that asset's hub reserve ratio to total hub asset liquidity does not exceed asset's weight cap.
+	fn ensure_weight_cap(asset_id: T::AssetId) -> DispatchResult {
+		let asset_state = Self::load_asset_state(asset_id)?;
+
+		let hub_reserve_ratio = FixedU128::checked_from_rational(
+			asset_state.hub_reserve,
+			T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account()),
+		)
+		.ok_or(ArithmeticError::DivisionByZero)?;
+
+		ensure!(
+			hub_reserve_ratio <= asset_state.weight_cap(),
+			Error::<T>::AssetWeightCapExceeded
+		);
+
+		Ok(())
+	}
+
+	/// Remove `amount` of shares of given position from the pool.
+	///
+	/// Fails with `SlippageLimit` if LP receives less than `min_limit` of asset or less than `min_hub_limit` of hub asset.
+	fn do_remove_liquidity(
 		origin: T::RuntimeOrigin,
-		who: &T::AccountId,
-		asset_out: T::AssetId,
+		who: T::AccountId,
+		position_id: T::PositionItemId,
 		amount: Balance,
-		limit: Balance,
+		min_limit: Balance,
+		min_hub_limit: Balance,
 	) -> DispatchResult {
+		//
+		// Preconditions
+		//
+		ensure!(amount > Balance::zero(), Error::<T>::InvalidSharesAmount);
+
 		ensure!(
-			HubAssetTradability::<T>::get().contains(Tradability::SELL),
-			Error::<T>::NotAllowed
+			T::NFTHandler::owner(&T::NFTCollectionId::get(), &position_id) == Some(who.clone()),
+			Error::<T>::Forbidden
 		);
 
-		let asset_state = Self::load_asset_state(asset_out)?;
+		let position = Positions::<T>::get(position_id).ok_or(Error::<T>::PositionNotFound)?;
+
+		ensure!(position.shares >= amount, Error::<T>::InsufficientShares);
+
+		let asset_id = position.asset_id;
+
+		let asset_state = Self::load_asset_state(asset_id)?;
+
+		// Frozen asset is being delisted - price cannot move anymore,
+		// so LPs withdraw at the frozen price without price checks and withdrawal fee.
+		let is_frozen = asset_state.tradable == Tradability::FROZEN;
 
-		ensure!(asset_state.tradable.contains(Tradability::BUY), Error::<T>::NotAllowed);
 		ensure!(
-			amount
-				<= asset_state
-					.hub_reserve
-					.checked_div(T::MaxInRatio::get())
-					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
-			Error::<T>::MaxInRatioExceeded
+			is_frozen || asset_state.tradable.contains(Tradability::REMOVE_LIQUIDITY),
+			Error::<T>::NotAllowed
 		);
 
 		let current_imbalance = <HubAssetImbalance<T>>::get();
+		let current_hub_asset_liquidity = T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account());
 
-		let current_hub_asset_liquidity = Self::get_hub_asset_balance_of_protocol_account();
+		let withdrawal_fee = if is_frozen {
+			FixedU128::zero()
+		} else {
+			T::PriceBarrier::ensure_price(
+				&who,
+				T::HubAssetId::get(),
+				asset_id,
+				hydra_dx_math::ema::EmaPrice::new(asset_state.hub_reserve, asset_state.reserve),
+			)
+			.map_err(|_| Error::<T>::PriceDifferenceTooHigh)?;
 
-		let state_changes = hydra_dx_math::omnipool::calculate_sell_hub_state_changes(
+			Self::withdrawal_fee(asset_id, &asset_state)?
+				.saturating_add(
+					Self::early_removal_fee(position_id, asset_id).ok_or(Error::<T>::MinHoldingPeriodNotPassed)?,
+				)
+				.min(FixedU128::one())
+		};
+
+		//
+		// calculate state changes of remove liquidity
+		//
+		let state_changes = hydra_dx_math::omnipool::calculate_remove_liquidity_state_changes(
 			&(&asset_state).into(),
 			amount,
-			T::AssetFee::get(),
+			&(&position).into(),
 			I129 {
 				value: current_imbalance.value,
 				negative: current_imbalance.negative,
 			},
 			current_hub_asset_liquidity,
+			withdrawal_fee,
 		)
 		.ok_or(ArithmeticError::Overflow)?;
 
 		ensure!(
-			*state_changes.asset.delta_reserve >= limit,
-			Error::<T>::BuyLimitNotReached
-		);
-
-		ensure!(
-			*state_changes.asset.delta_reserve
-				<= asset_state
-					.reserve
-					.checked_div(T::MaxOutRatio::get())
-					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
-			Error::<T>::MaxOutRatioExceeded
+			*state_changes.asset.delta_reserve >= min_limit,
+			Error::<T>::SlippageLimit
 		);
+		ensure!(state_changes.lp_hub_amount >= min_hub_limit, Error::<T>::SlippageLimit);
 
-		let new_asset_out_state = asset_state
+		let new_asset_state = asset_state
 			.clone()
 			.delta_update(&state_changes.asset)
 			.ok_or(ArithmeticError::Overflow)?;
 
-		// Token updates
-		T::Currency::transfer(
-			T::HubAssetId::get(),
-			who,
-			&Self::protocol_account(),
-			*state_changes.asset.delta_hub_reserve,
-		)?;
+		// Update position state
+		let updated_position = position
+			.delta_update(
+				&state_changes.delta_position_reserve,
+				&state_changes.delta_position_shares,
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		//
+		// Post - update states
+		//
+
 		T::Currency::transfer(
-			asset_out,
+			asset_id,
 			&Self::protocol_account(),
-			who,
+			&who,
 			*state_changes.asset.delta_reserve,
 		)?;
 
-		let info: AssetInfo<T::AssetId, Balance> =
-			AssetInfo::new(asset_out, &asset_state, &new_asset_out_state, &state_changes.asset);
-
 		Self::update_imbalance(state_changes.delta_imbalance)?;
 
-		Self::set_asset_state(asset_out, new_asset_out_state);
+		// burn only difference between delta hub and lp hub amount.
+		Self::update_hub_asset_liquidity(
+			&state_changes
+				.asset
+				.delta_hub_reserve
+				.merge(BalanceUpdate::Increase(state_changes.lp_hub_amount))
+				.ok_or(ArithmeticError::Overflow)?,
+		)?;
 
-		Self::deposit_event(Event::SellExecuted {
-			who: who.clone(),
-			asset_in: T::HubAssetId::get(),
-			asset_out,
-			amount_in: *state_changes.asset.delta_hub_reserve,
-			amount_out: *state_changes.asset.delta_reserve,
+		// LP receives some hub asset
+		if state_changes.lp_hub_amount > Balance::zero() {
+			T::Currency::transfer(
+				T::HubAssetId::get(),
+				&Self::protocol_account(),
+				&who,
+				state_changes.lp_hub_amount,
+			)?;
+		}
+
+		if updated_position.shares == Balance::zero() {
+			// All liquidity removed, remove position and burn NFT instance
+
+			<Positions<T>>::remove(position_id);
+			<PositionCreatedAt<T>>::remove(position_id);
+			T::NFTHandler::burn(&T::NFTCollectionId::get(), &position_id, Some(&who))?;
+
+			Self::deposit_event(Event::PositionDestroyed {
+				position_id,
+				owner: who.clone(),
+			});
+		} else {
+			Self::deposit_event(Event::PositionUpdated {
+				position_id,
+				owner: who.clone(),
+				asset: asset_id,
+				amount: updated_position.amount,
+				shares: updated_position.shares,
+				price: updated_position
+					.price_from_rational()
+					.ok_or(ArithmeticError::DivisionByZero)?,
+			});
+
+			<Positions<T>>::insert(position_id, updated_position);
+		}
+
+		// Callback hook info
+		let info: AssetInfo<T::AssetId, Balance> =
+			AssetInfo::new(asset_id, &asset_state, &new_asset_state, &state_changes.asset);
+
+		Self::set_asset_state(asset_id, new_asset_state);
+
+		Self::deposit_event(Event::LiquidityRemoved {
+			who,
+			position_id,
+			asset_id,
+			shares_removed: amount,
+			fee: withdrawal_fee,
 		});
 
-		T::OmnipoolHooks::on_hub_asset_trade(origin, info)?;
+		T::OmnipoolHooks::on_liquidity_changed(origin, info)?;
 
 		Ok(())
 	}
 
-	/// Swap asset for Hub Asset
-	/// Special handling of buy trade where asset in is Hub Asset.
-	fn buy_asset_for_hub_asset(
+	/// Buy hub asset from the pool
+	/// Special handling of buy trade where asset out is Hub Asset.
+	fn buy_hub_asset(
 		origin: T::RuntimeOrigin,
 		who: &T::AccountId,
-		asset_out: T::AssetId,
+		asset_in: T::AssetId,
 		amount: Balance,
 		limit: Balance,
 	) -> DispatchResult {
 		ensure!(
-			HubAssetTradability::<T>::get().contains(Tradability::SELL),
+			HubAssetTradability::<T>::get().contains(Tradability::BUY),
 			Error::<T>::NotAllowed
 		);
 
-		let asset_state = Self::load_asset_state(asset_out)?;
+		let asset_state = Self::load_asset_state(asset_in)?;
 
-		ensure!(asset_state.tradable.contains(Tradability::BUY), Error::<T>::NotAllowed);
+		ensure!(asset_state.tradable.contains(Tradability::SELL), Error::<T>::NotAllowed);
 
 		ensure!(
 			amount
 				<= asset_state
-					.reserve
+					.hub_reserve
 					.checked_div(T::MaxOutRatio::get())
-					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
+					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxOutRatio is zero.
 			Error::<T>::MaxOutRatioExceeded
 		);
 
+		T::PriceBarrier::ensure_price(
+			who,
+			T::HubAssetId::get(),
+			asset_in,
+			hydra_dx_math::ema::EmaPrice::new(asset_state.hub_reserve, asset_state.reserve),
+		)
+		.map_err(|_| Error::<T>::PriceDifferenceTooHigh)?;
+
 		let current_imbalance = <HubAssetImbalance<T>>::get();
 
 		let current_hub_asset_liquidity = Self::get_hub_asset_balance_of_protocol_account();
 
-		let state_changes = hydra_dx_math::omnipool::calculate_buy_for_hub_asset_state_changes(
-			&(&asset_state).into(),
+		let state_changes = math::calculate_buy_hub_asset_state_changes(
+			&asset_state,
 			amount,
-			T::AssetFee::get(),
-			I129 {
-				value: current_imbalance.value,
-				negative: current_imbalance.negative,
-			},
+			Self::protocol_fee(asset_in),
+			&current_imbalance,
 			current_hub_asset_liquidity,
 		)
 		.ok_or(ArithmeticError::Overflow)?;
 
 		ensure!(
-			*state_changes.asset.delta_hub_reserve <= limit,
+			*state_changes.asset.delta_reserve <= limit,
 			Error::<T>::SellLimitExceeded
 		);
 
 		ensure!(
-			*state_changes.asset.delta_hub_reserve
+			*state_changes.asset.delta_reserve
 				<= asset_state
-					.hub_reserve
+					.reserve
 					.checked_div(T::MaxInRatio::get())
 					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
 			Error::<T>::MaxInRatioExceeded
 		);
 
-		let new_asset_out_state = asset_state
+		ensure!(
+			T::Currency::ensure_can_withdraw(asset_in, who, *state_changes.asset.delta_reserve).is_ok(),
+			Error::<T>::InsufficientBalance
+		);
+
+		Self::execute_hub_asset_out_trade(origin, who, asset_in, asset_state, state_changes, false)
+	}
+
+	/// Swap asset for Hub Asset
+	/// Special handling of sell trade where asset out is Hub Asset.
+	fn sell_asset_for_hub_asset(
+		origin: T::RuntimeOrigin,
+		who: &T::AccountId,
+		asset_in: T::AssetId,
+		amount: Balance,
+		limit: Balance,
+	) -> DispatchResult {
+		ensure!(
+			HubAssetTradability::<T>::get().contains(Tradability::BUY),
+			Error::<T>::NotAllowed
+		);
+
+		let asset_state = Self::load_asset_state(asset_in)?;
+
+		ensure!(asset_state.tradable.contains(Tradability::SELL), Error::<T>::NotAllowed);
+
+		ensure!(
+			amount
+				<= asset_state
+					.reserve
+					.checked_div(T::MaxInRatio::get())
+					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxInRatio is zero.
+			Error::<T>::MaxInRatioExceeded
+		);
+
+		T::PriceBarrier::ensure_price(
+			who,
+			T::HubAssetId::get(),
+			asset_in,
+			hydra_dx_math::ema::EmaPrice::new(asset_state.hub_reserve, asset_state.reserve),
+		)
+		.map_err(|_| Error::<T>::PriceDifferenceTooHigh)?;
+
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+
+		let current_hub_asset_liquidity = Self::get_hub_asset_balance_of_protocol_account();
+
+		let state_changes = math::calculate_sell_for_hub_asset_state_changes(
+			&asset_state,
+			amount,
+			Self::protocol_fee(asset_in),
+			&current_imbalance,
+			current_hub_asset_liquidity,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		ensure!(state_changes.hub_amount >= limit, Error::<T>::BuyLimitNotReached);
+
+		ensure!(
+			*state_changes.asset.delta_hub_reserve
+				<= asset_state
+					.hub_reserve
+					.checked_div(T::MaxOutRatio::get())
+					.ok_or(ArithmeticError::DivisionByZero)?, // Note: this can only fail if MaxOutRatio is zero.
+			Error::<T>::MaxOutRatioExceeded
+		);
+
+		Self::execute_hub_asset_out_trade(origin, who, asset_in, asset_state, state_changes, true)
+	}
+
+	/// Apply state changes of a trade where hub asset is bought from the pool with `asset_in`.
+	/// Emits `SellExecuted` if `is_sell` is true, `BuyExecuted` otherwise.
+	fn execute_hub_asset_out_trade(
+		origin: T::RuntimeOrigin,
+		who: &T::AccountId,
+		asset_in: T::AssetId,
+		asset_state: AssetReserveState<Balance>,
+		state_changes: math::HubAssetOutStateChange,
+		is_sell: bool,
+	) -> DispatchResult {
+		let new_asset_in_state = asset_state
 			.clone()
 			.delta_update(&state_changes.asset)
 			.ok_or(ArithmeticError::Overflow)?;
 
 		T::Currency::transfer(
-			T::HubAssetId::get(),
+			asset_in,
 			who,
 			&Self::protocol_account(),
-			*state_changes.asset.delta_hub_reserve,
+			*state_changes.asset.delta_reserve,
 		)?;
 		T::Currency::transfer(
-			asset_out,
+			T::HubAssetId::get(),
 			&Self::protocol_account(),
 			who,
-			*state_changes.asset.delta_reserve,
+			state_changes.hub_amount,
 		)?;
 
-		let info: AssetInfo<T::AssetId, Balance> =
-			AssetInfo::new(asset_out, &asset_state, &new_asset_out_state, &state_changes.asset);
+		let burned_fee = state_changes.burned_fee();
+		if burned_fee > Balance::zero() {
+			T::Currency::withdraw(T::HubAssetId::get(), &Self::protocol_account(), burned_fee)?;
+		}
+
+		let info: AssetInfo<T::AssetId, Balance> =
+			AssetInfo::new(asset_in, &asset_state, &new_asset_in_state, &state_changes.asset);
+
+		Self::update_imbalance(state_changes.delta_imbalance)?;
+
+		Self::set_asset_state(asset_in, new_asset_in_state);
+
+		if is_sell {
+			Self::deposit_event(Event::SellExecuted {
+				who: who.clone(),
+				asset_in,
+				asset_out: T::HubAssetId::get(),
+				amount_in: *state_changes.asset.delta_reserve,
+				amount_out: state_changes.hub_amount,
+			});
+		} else {
+			Self::deposit_event(Event::BuyExecuted {
+				who: who.clone(),
+				asset_in,
+				asset_out: T::HubAssetId::get(),
+				amount_in: *state_changes.asset.delta_reserve,
+				amount_out: state_changes.hub_amount,
+			});
+		}
+
+		Self::on_hub_asset_trade(origin.clone(), info)?;
+
+		Self::process_protocol_fee(origin, state_changes.hdx_hub_amount)?;
+
+		Ok(())
+	}
+
+	/// Get hub asset balance of protocol account
+	fn get_hub_asset_balance_of_protocol_account() -> Balance {
+		T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account())
+	}
+
+	/// Check invariants of Omnipool state.
+	///
+	/// Asset reserves are not stored. They are read from the balances of protocol account,
+	/// so they cannot drift from the balances and are only checked to be non-zero for assets with shares.
+	///
+	/// - every asset with shares has non-zero balance in protocol account
+	/// - sum of assets' hub reserves equals hub asset balance of protocol account
+	/// - protocol shares of an asset do not exceed its shares
+	/// - every position belongs to an asset in the pool and has an NFT instance
+	#[cfg(any(feature = "try-runtime", test))]
+	pub fn do_try_state() -> Result<(), &'static str> {
+		let mut total_hub_reserve = Balance::zero();
+
+		for (asset_id, asset_state) in <Assets<T>>::iter() {
+			let reserve = T::Currency::free_balance(asset_id, &Self::protocol_account());
+
+			ensure!(
+				asset_state.shares.is_zero() || !reserve.is_zero(),
+				"Asset with shares has no reserve in protocol account"
+			);
+			ensure!(
+				asset_state.shares >= asset_state.protocol_shares,
+				"Asset protocol shares exceed asset shares"
+			);
+
+			total_hub_reserve = total_hub_reserve
+				.checked_add(asset_state.hub_reserve)
+				.ok_or("Sum of hub reserves overflows")?;
+		}
+
+		ensure!(
+			total_hub_reserve == Self::get_hub_asset_balance_of_protocol_account(),
+			"Sum of hub reserves does not match hub asset balance of protocol account"
+		);
+
+		for (position_id, position) in <Positions<T>>::iter() {
+			ensure!(
+				<Assets<T>>::contains_key(position.asset_id),
+				"Position of asset which is not in pool"
+			);
+			ensure!(
+				T::NFTHandler::owner(&T::NFTCollectionId::get(), &position_id).is_some(),
+				"Position without NFT instance"
+			);
+		}
+
+		Ok(())
+	}
+
+	/// Add new token to Omnipool with initial liquidity equal to pool's account balance of the token.
+	///
+	/// Position NFT token is minted for `position_owner`. Returns id of the created position.
+	fn do_add_token(
+		origin: T::RuntimeOrigin,
+		asset: T::AssetId,
+		initial_price: Price,
+		weight_cap: Permill,
+		tradable: Tradability,
+		position_owner: T::AccountId,
+	) -> Result<T::PositionItemId, DispatchError> {
+		ensure!(!Assets::<T>::contains_key(asset), Error::<T>::AssetAlreadyAdded);
+
+		ensure!(T::AssetRegistry::exists(asset), Error::<T>::AssetNotRegistered);
+
+		ensure!(initial_price > FixedU128::zero(), Error::<T>::InvalidInitialAssetPrice);
+
+		let amount = T::Currency::free_balance(asset, &Self::protocol_account());
+
+		ensure!(
+			amount >= T::MinimumPoolLiquidity::get() && amount > 0,
+			Error::<T>::MissingBalance
+		);
+
+		let hub_reserve = initial_price.checked_mul_int(amount).ok_or(ArithmeticError::Overflow)?;
+
+		// Initial state of asset
+		let state = AssetState::<Balance> {
+			hub_reserve,
+			shares: amount,
+			protocol_shares: Balance::zero(),
+			cap: FixedU128::from(weight_cap).into_inner(),
+			tradable,
+		};
+
+		let lp_position = Position::<Balance, T::AssetId> {
+			asset_id: asset,
+			amount,
+			shares: amount,
+			price: (initial_price.into_inner(), FixedU128::DIV),
+		};
+
+		let instance_id = Self::create_and_mint_position_instance(&position_owner)?;
+
+		<Positions<T>>::insert(instance_id, lp_position);
+
+		Self::deposit_event(Event::PositionCreated {
+			position_id: instance_id,
+			owner: position_owner,
+			asset,
+			amount,
+			shares: amount,
+			price: initial_price,
+		});
+
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+		let current_hub_asset_liquidity = T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account());
+
+		let delta_imbalance = hydra_dx_math::omnipool::calculate_delta_imbalance(
+			hub_reserve,
+			I129 {
+				value: current_imbalance.value,
+				negative: current_imbalance.negative,
+			},
+			current_hub_asset_liquidity,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
 
-		Self::update_imbalance(state_changes.delta_imbalance)?;
+		Self::update_imbalance(BalanceUpdate::Decrease(delta_imbalance))?;
 
-		Self::set_asset_state(asset_out, new_asset_out_state);
+		let delta_hub_reserve = BalanceUpdate::Increase(hub_reserve);
+		Self::update_hub_asset_liquidity(&delta_hub_reserve)?;
 
-		Self::deposit_event(Event::BuyExecuted {
-			who: who.clone(),
-			asset_in: T::HubAssetId::get(),
-			asset_out,
-			amount_in: *state_changes.asset.delta_hub_reserve,
-			amount_out: *state_changes.asset.delta_reserve,
-		});
+		let reserve = T::Currency::free_balance(asset, &Self::protocol_account());
 
-		T::OmnipoolHooks::on_hub_asset_trade(origin, info)?;
+		let reserve_state: AssetReserveState<_> = (state.clone(), reserve).into();
+		let changes = AssetStateChange {
+			delta_hub_reserve,
+			delta_reserve: BalanceUpdate::Increase(reserve),
+			delta_shares: BalanceUpdate::Increase(amount),
+			delta_protocol_shares: BalanceUpdate::Increase(Balance::zero()),
+		};
+		T::OmnipoolHooks::on_liquidity_changed(
+			origin,
+			AssetInfo::new(asset, &AssetReserveState::default(), &reserve_state, &changes),
+		)?;
 
-		Ok(())
-	}
+		<Assets<T>>::insert(asset, state);
 
-	/// Buy hub asset from the pool
-	/// Special handling of buy trade where asset out is Hub Asset.
-	fn buy_hub_asset(_who: &T::AccountId, _asset_in: T::AssetId, _amount: Balance, _limit: Balance) -> DispatchResult {
-		ensure!(
-			HubAssetTradability::<T>::get().contains(Tradability::BUY),
-			Error::<T>::NotAllowed
-		);
+		Self::ensure_tvl_cap()?;
 
-		// Note: Currently not allowed at all, neither math is done for this case
-		// this is already ready when hub asset will be allowed to be bought from the pool
+		Self::deposit_event(Event::TokenAdded {
+			asset_id: asset,
+			initial_amount: amount,
+			initial_price,
+		});
 
-		Err(Error::<T>::NotAllowed.into())
+		Ok(instance_id)
 	}
 
-	/// Swap asset for Hub Asset
-	/// Special handling of sell trade where asset out is Hub Asset.
-	fn sell_asset_for_hub_asset(
-		_who: &T::AccountId,
-		_asset_in: T::AssetId,
-		_amount: Balance,
-		_limit: Balance,
-	) -> DispatchResult {
+	/// Refund given amount of asset which is not in Omnipool from pool's account to a recipient.
+	fn do_refund_asset(asset_id: T::AssetId, amount: Balance, recipient: T::AccountId) -> DispatchResult {
+		// Hub asset cannot be refunded
+		ensure!(asset_id != T::HubAssetId::get(), Error::<T>::AssetRefundNotAllowed);
+
+		// Make sure that asset is not in the pool
+		ensure!(!Assets::<T>::contains_key(asset_id), Error::<T>::AssetAlreadyAdded);
+
 		ensure!(
-			HubAssetTradability::<T>::get().contains(Tradability::BUY),
-			Error::<T>::NotAllowed
+			T::Currency::ensure_can_withdraw(asset_id, &Self::protocol_account(), amount).is_ok(),
+			Error::<T>::InsufficientBalance
 		);
 
-		// Note: Currently not allowed at all, neither math is done for this case
-		// this is already ready when hub asset will be allowed to be bought from the pool
+		T::Currency::transfer(asset_id, &Self::protocol_account(), &recipient, amount)?;
 
-		Err(Error::<T>::NotAllowed.into())
-	}
+		Self::deposit_event(Event::AssetRefunded {
+			asset_id,
+			amount,
+			recipient,
+		});
 
-	/// Get hub asset balance of protocol account
-	fn get_hub_asset_balance_of_protocol_account() -> Balance {
-		T::Currency::free_balance(T::HubAssetId::get(), &Self::protocol_account())
+		Ok(())
 	}
 
 	/// Remove asset from list of Omnipool assets.
@@ -1901,4 +3431,554 @@ impl<T: Config> Pallet<T> {
 	pub fn exists(asset: T::AssetId) -> bool {
 		Assets::<T>::contains_key(asset)
 	}
+
+	/// Update asset's tradable state without checking the origin.
+	///
+	/// Used by the extrinsic and by other pallets restricting trading of an asset, e.g. the circuit breaker.
+	pub fn do_set_asset_tradable_state(asset_id: T::AssetId, state: Tradability) -> DispatchResult {
+		if asset_id == T::HubAssetId::get() {
+			// current omnipool does not allow liquidity add or remove of hub asset.
+			ensure!(
+				!state.contains(Tradability::ADD_LIQUIDITY) && !state.contains(Tradability::REMOVE_LIQUIDITY),
+				Error::<T>::InvalidHubAssetTradableState
+			);
+
+			HubAssetTradability::<T>::mutate(|value| -> DispatchResult {
+				*value = state;
+				Self::deposit_event(Event::TradableStateUpdated { asset_id, state });
+				Ok(())
+			})
+		} else {
+			Assets::<T>::try_mutate(asset_id, |maybe_asset| -> DispatchResult {
+				let asset_state = maybe_asset.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+
+				asset_state.tradable = state;
+				Self::deposit_event(Event::TradableStateUpdated { asset_id, state });
+
+				Ok(())
+			})
+		}
+	}
+
+	/// Asset fee charged when `asset_id` is bought from the pool.
+	///
+	/// Returns fee set by governance if there is one, current dynamic fee if asset has fee bounds set,
+	/// `AssetFee` otherwise.
+	pub fn asset_fee(asset_id: T::AssetId) -> Permill {
+		if let Some(fees) = AssetFeeOverrides::<T>::get(asset_id) {
+			return fees.asset_fee;
+		}
+
+		AssetFeeBounds::<T>::get(asset_id)
+			.map(|bounds| Self::current_dynamic_fee(asset_id, &bounds).asset_fee)
+			.unwrap_or_else(T::AssetFee::get)
+	}
+
+	/// Protocol fee charged when `asset_id` is sold to the pool.
+	///
+	/// Returns fee set by governance if there is one, current dynamic fee if asset has fee bounds set,
+	/// `ProtocolFee` otherwise.
+	pub fn protocol_fee(asset_id: T::AssetId) -> Permill {
+		if let Some(fees) = AssetFeeOverrides::<T>::get(asset_id) {
+			return fees.protocol_fee;
+		}
+
+		AssetFeeBounds::<T>::get(asset_id)
+			.map(|bounds| Self::current_dynamic_fee(asset_id, &bounds).protocol_fee)
+			.unwrap_or_else(T::ProtocolFee::get)
+	}
+
+	/// Dynamic fees of an asset decayed to current block.
+	/// Fees start at `AssetFee` and `ProtocolFee` (within the bounds) if the asset has not been traded yet.
+	fn current_dynamic_fee(asset_id: T::AssetId, bounds: &FeeBounds) -> DynamicFee<T::BlockNumber> {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let last = AssetDynamicFees::<T>::get(asset_id).unwrap_or(DynamicFee {
+			asset_fee: T::AssetFee::get(),
+			protocol_fee: T::ProtocolFee::get(),
+			updated_at: now,
+		});
+
+		let blocks: u32 = now.saturating_sub(last.updated_at).saturated_into();
+		let decay = T::DynamicFeeDecay::get();
+
+		DynamicFee {
+			asset_fee: math::calculate_decayed_fee(
+				last.asset_fee,
+				decay,
+				blocks,
+				bounds.min_asset_fee,
+				bounds.max_asset_fee,
+			),
+			protocol_fee: math::calculate_decayed_fee(
+				last.protocol_fee,
+				decay,
+				blocks,
+				bounds.min_protocol_fee,
+				bounds.max_protocol_fee,
+			),
+			updated_at: now,
+		}
+	}
+
+	/// Update dynamic fees of both traded assets and notify `OmnipoolHooks` about the trade.
+	///
+	/// Every trade between two assets of the pool must go through here so that fees follow all trading activity.
+	fn on_trade(
+		origin: T::RuntimeOrigin,
+		asset_in: AssetInfo<T::AssetId, Balance>,
+		asset_out: AssetInfo<T::AssetId, Balance>,
+	) -> DispatchResult {
+		Self::update_dynamic_fees(&asset_in);
+		Self::update_dynamic_fees(&asset_out);
+
+		T::OmnipoolHooks::on_trade(origin, asset_in, asset_out)?;
+
+		Ok(())
+	}
+
+	/// Update dynamic fees of the asset traded for hub asset and notify `OmnipoolHooks` about the trade.
+	fn on_hub_asset_trade(origin: T::RuntimeOrigin, asset: AssetInfo<T::AssetId, Balance>) -> DispatchResult {
+		Self::update_dynamic_fees(&asset);
+
+		T::OmnipoolHooks::on_hub_asset_trade(origin, asset)?;
+
+		Ok(())
+	}
+
+	/// Raise dynamic fees of traded asset. Does nothing if the asset has no fee bounds set.
+	///
+	/// Asset fee is raised when asset is bought from the pool, protocol fee when asset is sold to the pool.
+	fn update_dynamic_fees(asset: &AssetInfo<T::AssetId, Balance>) {
+		let bounds = match AssetFeeBounds::<T>::get(asset.asset_id) {
+			Some(bounds) => bounds,
+			None => return,
+		};
+
+		let mut fee = Self::current_dynamic_fee(asset.asset_id, &bounds);
+		let amplification = T::DynamicFeeAmplification::get();
+
+		match asset.delta_changes.delta_reserve {
+			BalanceUpdate::Decrease(amount) => {
+				fee.asset_fee = math::calculate_raised_fee(
+					fee.asset_fee,
+					amplification,
+					amount,
+					asset.before.reserve,
+					bounds.max_asset_fee,
+				);
+			}
+			BalanceUpdate::Increase(amount) => {
+				fee.protocol_fee = math::calculate_raised_fee(
+					fee.protocol_fee,
+					amplification,
+					amount,
+					asset.before.reserve,
+					bounds.max_protocol_fee,
+				);
+			}
+		}
+
+		let previous = AssetDynamicFees::<T>::mutate(asset.asset_id, |maybe_fee| maybe_fee.replace(fee));
+
+		if previous.map(|p| (p.asset_fee, p.protocol_fee)) != Some((fee.asset_fee, fee.protocol_fee)) {
+			Self::deposit_event(Event::AssetFeesUpdated {
+				asset_id: asset.asset_id,
+				asset_fee: fee.asset_fee,
+				protocol_fee: fee.protocol_fee,
+			});
+		}
+	}
+
+	/// Simulate a sell of `amount` of `asset_in` for `asset_out`.
+	///
+	/// State changes are calculated with the same math as `sell` but nothing is written to storage.
+	/// Trade limits, price barrier and hooks are not taken into account.
+	pub fn quote_sell(
+		asset_in: T::AssetId,
+		asset_out: T::AssetId,
+		amount: Balance,
+	) -> Result<TradeQuote<Balance>, DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::SameAssetTradeNotAllowed);
+
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+
+		if asset_in == T::HubAssetId::get() {
+			ensure!(
+				HubAssetTradability::<T>::get().contains(Tradability::SELL),
+				Error::<T>::NotAllowed
+			);
+
+			let asset_state = Self::load_asset_state(asset_out)?;
+
+			ensure!(asset_state.tradable.contains(Tradability::BUY), Error::<T>::NotAllowed);
+
+			let asset_fee = Self::asset_fee(asset_out);
+
+			let state_changes = hydra_dx_math::omnipool::calculate_sell_hub_state_changes(
+				&(&asset_state).into(),
+				amount,
+				asset_fee,
+				I129 {
+					value: current_imbalance.value,
+					negative: current_imbalance.negative,
+				},
+				Self::get_hub_asset_balance_of_protocol_account(),
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+
+			let new_asset_out_state = asset_state
+				.delta_update(&state_changes.asset)
+				.ok_or(ArithmeticError::Overflow)?;
+
+			return Ok(TradeQuote {
+				amount_in: *state_changes.asset.delta_hub_reserve,
+				amount_out: *state_changes.asset.delta_reserve,
+				asset_fee: Self::calculate_asset_fee_amount(*state_changes.asset.delta_reserve, asset_fee)?,
+				protocol_fee: Balance::zero(),
+				spot_price: new_asset_out_state.price().ok_or(ArithmeticError::DivisionByZero)?,
+			});
+		}
+
+		if asset_out == T::HubAssetId::get() {
+			ensure!(
+				HubAssetTradability::<T>::get().contains(Tradability::BUY),
+				Error::<T>::NotAllowed
+			);
+
+			let asset_state = Self::load_asset_state(asset_in)?;
+
+			ensure!(asset_state.tradable.contains(Tradability::SELL), Error::<T>::NotAllowed);
+
+			let state_changes = math::calculate_sell_for_hub_asset_state_changes(
+				&asset_state,
+				amount,
+				Self::protocol_fee(asset_in),
+				&current_imbalance,
+				Self::get_hub_asset_balance_of_protocol_account(),
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+
+			return Self::hub_asset_out_trade_quote(asset_state, state_changes);
+		}
+
+		let asset_in_state = Self::load_asset_state(asset_in)?;
+		let asset_out_state = Self::load_asset_state(asset_out)?;
+
+		ensure!(
+			Self::allow_assets(&asset_in_state, &asset_out_state),
+			Error::<T>::NotAllowed
+		);
+
+		let asset_fee = Self::asset_fee(asset_out);
+
+		let state_changes = hydra_dx_math::omnipool::calculate_sell_state_changes(
+			&(&asset_in_state).into(),
+			&(&asset_out_state).into(),
+			amount,
+			asset_fee,
+			Self::protocol_fee(asset_in),
+			current_imbalance.value,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		Self::trade_quote(
+			asset_in_state,
+			asset_out_state,
+			&state_changes.asset_in,
+			&state_changes.asset_out,
+			asset_fee,
+		)
+	}
+
+	/// Simulate a buy of `amount` of `asset_out` for `asset_in`.
+	///
+	/// State changes are calculated with the same math as `buy` but nothing is written to storage.
+	/// Trade limits, price barrier and hooks are not taken into account.
+	pub fn quote_buy(
+		asset_out: T::AssetId,
+		asset_in: T::AssetId,
+		amount: Balance,
+	) -> Result<TradeQuote<Balance>, DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::SameAssetTradeNotAllowed);
+
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+
+		if asset_in == T::HubAssetId::get() {
+			ensure!(
+				HubAssetTradability::<T>::get().contains(Tradability::SELL),
+				Error::<T>::NotAllowed
+			);
+
+			let asset_state = Self::load_asset_state(asset_out)?;
+
+			ensure!(asset_state.tradable.contains(Tradability::BUY), Error::<T>::NotAllowed);
+
+			let asset_fee = Self::asset_fee(asset_out);
+
+			let state_changes = hydra_dx_math::omnipool::calculate_buy_for_hub_asset_state_changes(
+				&(&asset_state).into(),
+				amount,
+				asset_fee,
+				I129 {
+					value: current_imbalance.value,
+					negative: current_imbalance.negative,
+				},
+				Self::get_hub_asset_balance_of_protocol_account(),
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+
+			let new_asset_out_state = asset_state
+				.delta_update(&state_changes.asset)
+				.ok_or(ArithmeticError::Overflow)?;
+
+			return Ok(TradeQuote {
+				amount_in: *state_changes.asset.delta_hub_reserve,
+				amount_out: *state_changes.asset.delta_reserve,
+				asset_fee: Self::calculate_asset_fee_amount(*state_changes.asset.delta_reserve, asset_fee)?,
+				protocol_fee: Balance::zero(),
+				spot_price: new_asset_out_state.price().ok_or(ArithmeticError::DivisionByZero)?,
+			});
+		}
+
+		if asset_out == T::HubAssetId::get() {
+			ensure!(
+				HubAssetTradability::<T>::get().contains(Tradability::BUY),
+				Error::<T>::NotAllowed
+			);
+
+			let asset_state = Self::load_asset_state(asset_in)?;
+
+			ensure!(asset_state.tradable.contains(Tradability::SELL), Error::<T>::NotAllowed);
+
+			let state_changes = math::calculate_buy_hub_asset_state_changes(
+				&asset_state,
+				amount,
+				Self::protocol_fee(asset_in),
+				&current_imbalance,
+				Self::get_hub_asset_balance_of_protocol_account(),
+			)
+			.ok_or(ArithmeticError::Overflow)?;
+
+			return Self::hub_asset_out_trade_quote(asset_state, state_changes);
+		}
+
+		let asset_in_state = Self::load_asset_state(asset_in)?;
+		let asset_out_state = Self::load_asset_state(asset_out)?;
+
+		ensure!(
+			Self::allow_assets(&asset_in_state, &asset_out_state),
+			Error::<T>::NotAllowed
+		);
+
+		ensure!(asset_out_state.reserve >= amount, Error::<T>::InsufficientLiquidity);
+
+		let asset_fee = Self::asset_fee(asset_out);
+
+		let state_changes = hydra_dx_math::omnipool::calculate_buy_state_changes(
+			&(&asset_in_state).into(),
+			&(&asset_out_state).into(),
+			amount,
+			asset_fee,
+			Self::protocol_fee(asset_in),
+			current_imbalance.value,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		Self::trade_quote(
+			asset_in_state,
+			asset_out_state,
+			&state_changes.asset_in,
+			&state_changes.asset_out,
+			asset_fee,
+		)
+	}
+
+	/// Build trade quote from calculated state changes of a trade between two non-hub assets.
+	fn trade_quote(
+		asset_in_state: AssetReserveState<Balance>,
+		asset_out_state: AssetReserveState<Balance>,
+		delta_in: &AssetStateChange<Balance>,
+		delta_out: &AssetStateChange<Balance>,
+		asset_fee: Permill,
+	) -> Result<TradeQuote<Balance>, DispatchError> {
+		let new_asset_in_state = asset_in_state.delta_update(delta_in).ok_or(ArithmeticError::Overflow)?;
+		let new_asset_out_state = asset_out_state
+			.delta_update(delta_out)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		// (IN / LRNA) * (LRNA / OUT) = IN / OUT
+		let price_in = FixedU128::checked_from_rational(new_asset_in_state.reserve, new_asset_in_state.hub_reserve)
+			.ok_or(ArithmeticError::DivisionByZero)?;
+		let price_out = new_asset_out_state.price().ok_or(ArithmeticError::DivisionByZero)?;
+
+		Ok(TradeQuote {
+			amount_in: *delta_in.delta_reserve,
+			amount_out: *delta_out.delta_reserve,
+			asset_fee: Self::calculate_asset_fee_amount(*delta_out.delta_reserve, asset_fee)?,
+			// Protocol fee is the part of hub asset which is not added to asset out subpool.
+			protocol_fee: (*delta_in.delta_hub_reserve).saturating_sub(*delta_out.delta_hub_reserve),
+			spot_price: price_in.checked_mul(&price_out).ok_or(ArithmeticError::Overflow)?,
+		})
+	}
+
+	/// Build trade quote from calculated state changes of a trade where hub asset is bought from the pool.
+	fn hub_asset_out_trade_quote(
+		asset_state: AssetReserveState<Balance>,
+		state_changes: math::HubAssetOutStateChange,
+	) -> Result<TradeQuote<Balance>, DispatchError> {
+		let new_asset_in_state = asset_state
+			.delta_update(&state_changes.asset)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		Ok(TradeQuote {
+			amount_in: *state_changes.asset.delta_reserve,
+			amount_out: state_changes.hub_amount,
+			asset_fee: Balance::zero(),
+			protocol_fee: state_changes.protocol_fee,
+			spot_price: FixedU128::checked_from_rational(new_asset_in_state.reserve, new_asset_in_state.hub_reserve)
+				.ok_or(ArithmeticError::DivisionByZero)?,
+		})
+	}
+
+	/// Calculate asset fee amount paid in asset out.
+	/// Given `amount_out` is the amount received by trader, so the fee has been already deducted.
+	fn calculate_asset_fee_amount(amount_out: Balance, fee: Permill) -> Result<Balance, DispatchError> {
+		let fee = FixedU128::from(fee);
+		let fee_ratio = fee
+			.checked_div(&FixedU128::one().checked_sub(&fee).ok_or(ArithmeticError::Underflow)?)
+			.ok_or(ArithmeticError::DivisionByZero)?;
+
+		fee_ratio
+			.checked_mul_int(amount_out)
+			.ok_or_else(|| ArithmeticError::Overflow.into())
+	}
+
+	/// Calculate withdrawal fee of an asset from the difference between the current spot price and the oracle price.
+	fn withdrawal_fee(
+		asset_id: T::AssetId,
+		asset_state: &AssetReserveState<Balance>,
+	) -> Result<FixedU128, DispatchError> {
+		let ext_asset_price = T::ExternalPriceOracle::get_price(T::HubAssetId::get(), asset_id)?;
+
+		if ext_asset_price.is_zero() {
+			return Err(Error::<T>::InvalidOraclePrice.into());
+		}
+
+		Ok(hydra_dx_math::omnipool::calculate_withdrawal_fee(
+			asset_state.price().ok_or(ArithmeticError::DivisionByZero)?,
+			FixedU128::checked_from_rational(ext_asset_price.n, ext_asset_price.d)
+				.defensive_ok_or(Error::<T>::InvalidOraclePrice)?,
+			T::MinWithdrawalFee::get(),
+		))
+	}
+
+	/// Additional withdrawal fee of a position whose liquidity is removed before minimum holding period of the asset.
+	///
+	/// Returns `None` if the period has not passed and early removal is not allowed.
+	fn early_removal_fee(position_id: T::PositionItemId, asset_id: T::AssetId) -> Option<FixedU128> {
+		let (min_holding_period, created_at) = match (
+			<AssetMinHoldingPeriod<T>>::get(asset_id),
+			<PositionCreatedAt<T>>::get(position_id),
+		) {
+			(Some(min_holding_period), Some(created_at)) => (min_holding_period, created_at),
+			_ => return Some(FixedU128::zero()),
+		};
+
+		if <frame_system::Pallet<T>>::block_number() >= created_at.saturating_add(min_holding_period.blocks) {
+			return Some(FixedU128::zero());
+		}
+
+		min_holding_period.early_removal_fee.map(FixedU128::from)
+	}
+
+	/// Calculate what the owner of a position would receive if all of its liquidity was removed now.
+	///
+	/// Amounts are calculated with the same math as `remove_liquidity`, including the withdrawal fee,
+	/// but nothing is written to storage. Price barrier is not taken into account.
+	///
+	/// Position within minimum holding period which cannot be removed early is valued as if the period has passed
+	/// and is reported as not withdrawable.
+	pub fn position_value(position_id: T::PositionItemId) -> Result<PositionValue<T::AssetId, Balance>, DispatchError> {
+		let owner =
+			T::NFTHandler::owner(&T::NFTCollectionId::get(), &position_id).ok_or(Error::<T>::PositionNotFound)?;
+		let position = Self::load_position(position_id, owner)?;
+		let asset_state = Self::load_asset_state(position.asset_id)?;
+
+		let (withdrawal_fee, early_removal_fee, withdrawable) = if asset_state.tradable == Tradability::FROZEN {
+			(FixedU128::zero(), FixedU128::zero(), true)
+		} else {
+			let early_removal_fee = Self::early_removal_fee(position_id, position.asset_id);
+			let withdrawal_fee = Self::withdrawal_fee(position.asset_id, &asset_state)?
+				.saturating_add(early_removal_fee.unwrap_or_default())
+				.min(FixedU128::one());
+			(
+				withdrawal_fee,
+				early_removal_fee.unwrap_or_default(),
+				early_removal_fee.is_some(),
+			)
+		};
+
+		let (amount, hub_amount) = Self::calculate_position_withdrawal(&asset_state, &position, withdrawal_fee)?;
+
+		let current_price = asset_state.price().ok_or(ArithmeticError::DivisionByZero)?;
+		let entry_price = FixedU128::checked_from_rational(position.price.0, position.price.1)
+			.ok_or(ArithmeticError::DivisionByZero)?;
+
+		Ok(PositionValue {
+			asset_id: position.asset_id,
+			amount,
+			hub_amount,
+			withdrawal_fee,
+			early_removal_fee,
+			withdrawable,
+			impermanent_loss: Self::impermanent_loss(entry_price, current_price),
+		})
+	}
+
+	/// Impermanent loss of liquidity provided at `entry_price` when the spot price moves to `current_price`.
+	///
+	/// With price ratio `r = current_price / entry_price`, the loss is `1 - 2 * sqrt(r) / (1 + r)`,
+	/// calculated as `(sqrt(current_price) - sqrt(entry_price))^2 / (current_price + entry_price)`.
+	fn impermanent_loss(entry_price: FixedU128, current_price: FixedU128) -> FixedU128 {
+		let (entry, current) = (entry_price.into_inner(), current_price.into_inner());
+		let sqrt_diff = entry.integer_sqrt().abs_diff(current.integer_sqrt());
+
+		FixedU128::checked_from_rational(sqrt_diff.saturating_mul(sqrt_diff), entry.saturating_add(current))
+			.unwrap_or_default()
+			.min(FixedU128::one())
+	}
+
+	/// Calculate amounts of asset and hub asset received when all shares of given position are removed.
+	fn calculate_position_withdrawal(
+		asset_state: &AssetReserveState<Balance>,
+		position: &Position<Balance, T::AssetId>,
+		withdrawal_fee: FixedU128,
+	) -> Result<(Balance, Balance), DispatchError> {
+		let current_imbalance = <HubAssetImbalance<T>>::get();
+
+		let state_changes = hydra_dx_math::omnipool::calculate_remove_liquidity_state_changes(
+			&asset_state.into(),
+			position.shares,
+			&position.into(),
+			I129 {
+				value: current_imbalance.value,
+				negative: current_imbalance.negative,
+			},
+			Self::get_hub_asset_balance_of_protocol_account(),
+			withdrawal_fee,
+		)
+		.ok_or(ArithmeticError::Overflow)?;
+
+		Ok((*state_changes.asset.delta_reserve, state_changes.lp_hub_amount))
+	}
+}
+
+/// Provides max allowed price difference of an asset set by `set_asset_max_price_difference`.
+pub struct MaxPriceDifferenceOf<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> orml_traits::GetByKey<T::AssetId, Option<Permill>> for MaxPriceDifferenceOf<T> {
+	fn get(asset_id: &T::AssetId) -> Option<Permill> {
+		AssetMaxPriceDifference::<T>::get(asset_id)
+	}
 }
@@ -0,0 +1,245 @@
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Omnipool math which is not provided by hydra-dx-math.
+//!
+//! ## Hub asset out trades
+//! Hub asset leaves the pool from the subpool of traded asset. Protocol fee is charged on hub asset amount
+//! and it is either burned to reduce the imbalance or added to the HDX subpool if there is no imbalance to cover.
+//!
+//! ## Dynamic fees
+//! Fee of an asset is raised by `amplification * volume / liquidity` after a trade and linearly decays
+//! back to its minimum by `decay` each block.
+//!
+//! ## Position merge and split
+//! Merged position's entry price is average of entry prices of merged positions weighted by their amounts.
+//! Split position keeps its entry price and its amount is divided proportionally to split shares.
+
+use crate::types::{AssetReserveState, Balance, SimpleImbalance};
+use hydra_dx_math::omnipool::types::{AssetStateChange, BalanceUpdate};
+use primitive_types::U256;
+use sp_runtime::traits::{One, Saturating, Zero};
+use sp_runtime::{FixedPointNumber, FixedU128, PerThing, Permill};
+use sp_std::cmp::min;
+
+/// State changes of a trade where hub asset is bought from the pool.
+pub(crate) struct HubAssetOutStateChange {
+	/// Delta changes of traded asset subpool.
+	pub asset: AssetStateChange<Balance>,
+	/// Amount of hub asset received by trader.
+	pub hub_amount: Balance,
+	/// Protocol fee amount in hub asset.
+	pub protocol_fee: Balance,
+	/// Part of protocol fee which goes to HDX subpool.
+	pub hdx_hub_amount: Balance,
+	/// Change of hub asset imbalance.
+	pub delta_imbalance: BalanceUpdate<Balance>,
+}
+
+impl HubAssetOutStateChange {
+	/// Part of protocol fee which is burned.
+	pub fn burned_fee(&self) -> Balance {
+		self.protocol_fee.saturating_sub(self.hdx_hub_amount)
+	}
+}
+
+/// Calculate state changes of sell trade where `amount` of asset is sold for hub asset.
+pub(crate) fn calculate_sell_for_hub_asset_state_changes(
+	asset_state: &AssetReserveState<Balance>,
+	amount: Balance,
+	protocol_fee: Permill,
+	imbalance: &SimpleImbalance<Balance>,
+	total_hub_reserve: Balance,
+) -> Option<HubAssetOutStateChange> {
+	let reserve = U256::from(asset_state.reserve);
+	let hub_reserve = U256::from(asset_state.hub_reserve);
+
+	let delta_hub_reserve = hub_reserve
+		.checked_mul(U256::from(amount))?
+		.checked_div(reserve.checked_add(U256::from(amount))?)?;
+	let delta_hub_reserve = Balance::try_from(delta_hub_reserve).ok()?;
+
+	let protocol_fee_amount = protocol_fee.mul_floor(delta_hub_reserve);
+	let hub_amount = delta_hub_reserve.checked_sub(protocol_fee_amount)?;
+
+	let (delta_imbalance, hdx_hub_amount) =
+		calculate_hub_asset_out_imbalance(hub_amount, protocol_fee_amount, imbalance, total_hub_reserve)?;
+
+	Some(HubAssetOutStateChange {
+		asset: AssetStateChange {
+			delta_reserve: BalanceUpdate::Increase(amount),
+			delta_hub_reserve: BalanceUpdate::Decrease(delta_hub_reserve),
+			..Default::default()
+		},
+		hub_amount,
+		protocol_fee: protocol_fee_amount,
+		hdx_hub_amount,
+		delta_imbalance,
+	})
+}
+
+/// Calculate state changes of buy trade where `hub_amount` of hub asset is bought with an asset.
+pub(crate) fn calculate_buy_hub_asset_state_changes(
+	asset_state: &AssetReserveState<Balance>,
+	hub_amount: Balance,
+	protocol_fee: Permill,
+	imbalance: &SimpleImbalance<Balance>,
+	total_hub_reserve: Balance,
+) -> Option<HubAssetOutStateChange> {
+	let accuracy = U256::from(Permill::ACCURACY);
+	let fee_parts = U256::from(protocol_fee.deconstruct());
+
+	// Hub asset taken from the subpool including protocol fee.
+	let delta_hub_reserve = div_ceil(
+		U256::from(hub_amount).checked_mul(accuracy)?,
+		accuracy.checked_sub(fee_parts)?,
+	)?;
+
+	let hub_reserve = U256::from(asset_state.hub_reserve);
+
+	if delta_hub_reserve >= hub_reserve {
+		return None;
+	}
+
+	let delta_reserve = div_ceil(
+		U256::from(asset_state.reserve).checked_mul(delta_hub_reserve)?,
+		hub_reserve.checked_sub(delta_hub_reserve)?,
+	)?;
+
+	let delta_hub_reserve = Balance::try_from(delta_hub_reserve).ok()?;
+	let delta_reserve = Balance::try_from(delta_reserve).ok()?;
+
+	let protocol_fee_amount = delta_hub_reserve.checked_sub(hub_amount)?;
+
+	let (delta_imbalance, hdx_hub_amount) =
+		calculate_hub_asset_out_imbalance(hub_amount, protocol_fee_amount, imbalance, total_hub_reserve)?;
+
+	Some(HubAssetOutStateChange {
+		asset: AssetStateChange {
+			delta_reserve: BalanceUpdate::Increase(delta_reserve),
+			delta_hub_reserve: BalanceUpdate::Decrease(delta_hub_reserve),
+			..Default::default()
+		},
+		hub_amount,
+		protocol_fee: protocol_fee_amount,
+		hdx_hub_amount,
+		delta_imbalance,
+	})
+}
+
+/// Calculate imbalance change when `hub_amount` leaves the pool and `protocol_fee` is collected.
+///
+/// Hub asset paid out reduces the imbalance by `hub_amount * (Q + L) / Q` - reverse of selling hub asset to the pool.
+/// Protocol fee is burned up to the remaining imbalance, rest of the fee goes to HDX subpool.
+///
+/// Returns imbalance change and hub asset amount for HDX subpool.
+fn calculate_hub_asset_out_imbalance(
+	hub_amount: Balance,
+	protocol_fee: Balance,
+	imbalance: &SimpleImbalance<Balance>,
+	total_hub_reserve: Balance,
+) -> Option<(BalanceUpdate<Balance>, Balance)> {
+	let current_imbalance = if imbalance.negative { imbalance.value } else { 0 };
+
+	let q = U256::from(total_hub_reserve);
+	let delta_imbalance = div_ceil(
+		U256::from(hub_amount).checked_mul(q.checked_sub(U256::from(current_imbalance))?)?,
+		q,
+	)?;
+	let delta_imbalance = Balance::try_from(delta_imbalance).ok()?;
+
+	let burned_fee = min(protocol_fee, current_imbalance.saturating_sub(delta_imbalance));
+	let hdx_hub_amount = protocol_fee.checked_sub(burned_fee)?;
+
+	Some((
+		BalanceUpdate::Increase(delta_imbalance.checked_add(burned_fee)?),
+		hdx_hub_amount,
+	))
+}
+
+/// Decay `fee` by `decay` for each of `blocks` elapsed. Result is kept within `min` and `max`.
+pub(crate) fn calculate_decayed_fee(fee: Permill, decay: Permill, blocks: u32, min: Permill, max: Permill) -> Permill {
+	let decay = Permill::from_parts(decay.deconstruct().saturating_mul(blocks));
+	fee.saturating_sub(decay).max(min).min(max)
+}
+
+/// Raise `fee` by `amplification * volume / liquidity`. Result is capped by `max`.
+pub(crate) fn calculate_raised_fee(
+	fee: Permill,
+	amplification: FixedU128,
+	volume: Balance,
+	liquidity: Balance,
+	max: Permill,
+) -> Permill {
+	if volume.is_zero() {
+		return fee;
+	}
+
+	let ratio = match FixedU128::checked_from_rational(volume, liquidity) {
+		Some(ratio) => ratio,
+		None => return max,
+	};
+
+	let increase = ratio.saturating_mul(amplification).min(FixedU128::one());
+	let increase = Permill::from_rational(increase.into_inner(), FixedU128::DIV);
+
+	fee.saturating_add(increase).min(max)
+}
+
+/// Calculate entry price of merged position as amount-weighted average of given `(amount, price)` pairs.
+///
+/// Price is returned as `(hub amount, asset amount)` - same as `Position::price`.
+pub(crate) fn calculate_merged_position_price(
+	positions: &[(Balance, (Balance, Balance))],
+) -> Option<(Balance, Balance)> {
+	let mut total_hub_amount = U256::zero();
+	let mut total_amount = U256::zero();
+
+	for (amount, (hub_reserve, reserve)) in positions {
+		let hub_amount = U256::from(*amount)
+			.checked_mul(U256::from(*hub_reserve))?
+			.checked_div(U256::from(*reserve))?;
+		total_hub_amount = total_hub_amount.checked_add(hub_amount)?;
+		total_amount = total_amount.checked_add(U256::from(*amount))?;
+	}
+
+	if total_amount.is_zero() {
+		return None;
+	}
+
+	Some((
+		Balance::try_from(total_hub_amount).ok()?,
+		Balance::try_from(total_amount).ok()?,
+	))
+}
+
+/// Calculate amount of asset which belongs to `shares` of a position with given `amount` and `position_shares`.
+pub(crate) fn calculate_split_position_amount(
+	amount: Balance,
+	shares: Balance,
+	position_shares: Balance,
+) -> Option<Balance> {
+	let split_amount = U256::from(amount)
+		.checked_mul(U256::from(shares))?
+		.checked_div(U256::from(position_shares))?;
+	Balance::try_from(split_amount).ok()
+}
+
+fn div_ceil(a: U256, b: U256) -> Option<U256> {
+	if b.is_zero() {
+		return None;
+	}
+	a.checked_add(b.checked_sub(U256::one())?)?.checked_div(b)
+}
@@ -216,3 +216,132 @@ fn add_liquidity_should_fail_when_prices_differ_and_is_lower() {
 			);
 		});
 }
+
+#[test]
+fn add_liquidity_batch_should_create_position_for_each_asset() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 5000 * ONE))
+		.add_endowed_accounts((LP1, 200, 5000 * ONE))
+		.add_endowed_accounts((LP2, 100, 5000 * ONE))
+		.add_endowed_accounts((LP2, 200, 5000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let position_id = <NextPositionId<Test>>::get();
+
+			assert_ok!(Omnipool::add_liquidity_batch(
+				RuntimeOrigin::signed(LP1),
+				vec![(100, 400 * ONE), (200, 200 * ONE)].try_into().unwrap()
+			));
+
+			assert_eq!(
+				Positions::<Test>::get(position_id).unwrap(),
+				Position::<Balance, AssetId> {
+					asset_id: 100,
+					amount: 400 * ONE,
+					shares: 400 * ONE,
+					price: (1560 * ONE, 2400 * ONE),
+				}
+			);
+			assert_eq!(
+				Positions::<Test>::get(position_id + 1).unwrap(),
+				Position::<Balance, AssetId> {
+					asset_id: 200,
+					amount: 200 * ONE,
+					shares: 200 * ONE,
+					price: (1430 * ONE, 2200 * ONE),
+				}
+			);
+
+			assert_balance!(LP1, 100, 4600 * ONE);
+			assert_balance!(LP1, 200, 4800 * ONE);
+
+			assert_eq!(POSITIONS.with(|v| v.borrow().get(&position_id).copied()), Some(LP1));
+			assert_eq!(
+				POSITIONS.with(|v| v.borrow().get(&(position_id + 1)).copied()),
+				Some(LP1)
+			);
+
+			assert_hub_asset!();
+		});
+}
+
+#[test]
+fn add_liquidity_batch_should_check_weight_cap_when_all_liquidity_is_added() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 5000 * ONE))
+		.add_endowed_accounts((LP1, DAI, 5000 * ONE))
+		.add_endowed_accounts((LP2, 100, 5000 * ONE))
+		.with_registered_asset(100)
+		.with_asset_weight_cap(Permill::from_percent(10))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 100 * ONE)
+		.build()
+		.execute_with(|| {
+			// Weight cap of asset is exceeded when its liquidity is added alone
+			assert_noop!(
+				Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 100, 2000 * ONE),
+				Error::<Test>::AssetWeightCapExceeded
+			);
+			assert_noop!(
+				Omnipool::add_liquidity_batch(RuntimeOrigin::signed(LP1), vec![(100, 2000 * ONE)].try_into().unwrap()),
+				Error::<Test>::AssetWeightCapExceeded
+			);
+
+			// but not when liquidity of another asset is added in the same batch
+			assert_ok!(Omnipool::add_liquidity_batch(
+				RuntimeOrigin::signed(LP1),
+				vec![(100, 2000 * ONE), (DAI, 4000 * ONE)].try_into().unwrap()
+			));
+
+			assert_balance!(LP1, 100, 3000 * ONE);
+			assert_balance!(LP1, DAI, 1000 * ONE);
+		});
+}
+
+#[test]
+fn add_liquidity_batch_should_fail_when_asset_is_present_more_than_once() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 1_000, 5000 * ONE))
+		.add_endowed_accounts((LP2, 1_000, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::add_liquidity_batch(
+					RuntimeOrigin::signed(LP1),
+					vec![(1_000, 400 * ONE), (1_000, 200 * ONE)].try_into().unwrap()
+				),
+				Error::<Test>::DuplicateAsset
+			);
+		});
+}
+
+#[test]
+fn add_liquidity_batch_should_not_add_any_liquidity_when_one_asset_fails() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 5000 * ONE))
+		.add_endowed_accounts((LP1, 200, 100 * ONE))
+		.add_endowed_accounts((LP2, 100, 5000 * ONE))
+		.add_endowed_accounts((LP2, 200, 5000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::add_liquidity_batch(
+					RuntimeOrigin::signed(LP1),
+					vec![(100, 400 * ONE), (200, 200 * ONE)].try_into().unwrap()
+				),
+				Error::<Test>::InsufficientBalance
+			);
+		});
+}
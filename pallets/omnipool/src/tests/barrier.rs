@@ -2,11 +2,24 @@ use crate::traits::{EnsurePriceWithin, ExternalPriceProvider, ShouldAllow};
 use frame_support::dispatch::Weight;
 use frame_support::{assert_err, assert_ok, parameter_types};
 use hydra_dx_math::ema::EmaPrice;
+use orml_traits::GetByKey;
 use sp_runtime::Permill;
 use std::cell::RefCell;
 
 thread_local! {
 	pub static EXTERNAL_PRICE: RefCell<EmaPrice> = RefCell::new(EmaPrice::default());
+	pub static ASSET_MAX_ALLOWED: RefCell<Option<(u32, Permill)>> = RefCell::new(None);
+}
+
+struct AssetMaxAllowed;
+
+impl GetByKey<u32, Option<Permill>> for AssetMaxAllowed {
+	fn get(asset_id: &u32) -> Option<Permill> {
+		ASSET_MAX_ALLOWED.with(|v| {
+			v.borrow()
+				.and_then(|(asset, max_allowed)| (asset == *asset_id).then_some(max_allowed))
+		})
+	}
 }
 
 struct SinglePriceProvider;
@@ -31,10 +44,24 @@ parameter_types! {
 fn ensure_price_should_be_ok_when_price_is_within_allowed_difference() {
 	EXTERNAL_PRICE.with(|v| *v.borrow_mut() = EmaPrice::new(1, 10));
 	let spot_price = EmaPrice::new(999, 10000);
-	assert_ok!(EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, ()>::ensure_price(&0, 1, 2, spot_price));
+	assert_ok!(EnsurePriceWithin::<
+		u64,
+		u32,
+		SinglePriceProvider,
+		MaxAllowed,
+		AssetMaxAllowed,
+		(),
+	>::ensure_price(&0, 1, 2, spot_price));
 
 	let spot_price = EmaPrice::new(101, 1000);
-	assert_ok!(EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, ()>::ensure_price(&0, 1, 2, spot_price));
+	assert_ok!(EnsurePriceWithin::<
+		u64,
+		u32,
+		SinglePriceProvider,
+		MaxAllowed,
+		AssetMaxAllowed,
+		(),
+	>::ensure_price(&0, 1, 2, spot_price));
 }
 
 #[test]
@@ -42,13 +69,54 @@ fn ensure_price_should_fail_when_price_is_not_within_allowed_difference() {
 	EXTERNAL_PRICE.with(|v| *v.borrow_mut() = EmaPrice::new(1, 10));
 	let spot_price = EmaPrice::new(8, 1000);
 	assert_err!(
-		EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, ()>::ensure_price(&0, 1, 2, spot_price),
+		EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, AssetMaxAllowed, ()>::ensure_price(
+			&0, 1, 2, spot_price
+		),
 		()
 	);
 
 	let spot_price = EmaPrice::new(2, 10);
 	assert_err!(
-		EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, ()>::ensure_price(&0, 1, 2, spot_price),
+		EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, AssetMaxAllowed, ()>::ensure_price(
+			&0, 1, 2, spot_price
+		),
+		()
+	);
+}
+
+#[test]
+fn ensure_price_should_use_asset_max_allowed_difference_when_set() {
+	EXTERNAL_PRICE.with(|v| *v.borrow_mut() = EmaPrice::new(1, 10));
+	ASSET_MAX_ALLOWED.with(|v| *v.borrow_mut() = Some((2, Permill::from_percent(5))));
+	let spot_price = EmaPrice::new(104, 1000);
+	assert_ok!(EnsurePriceWithin::<
+		u64,
+		u32,
+		SinglePriceProvider,
+		MaxAllowed,
+		AssetMaxAllowed,
+		(),
+	>::ensure_price(&0, 1, 2, spot_price));
+
+	ASSET_MAX_ALLOWED.with(|v| *v.borrow_mut() = Some((2, Permill::from_rational(1u32, 1000u32))));
+	let spot_price = EmaPrice::new(1005, 10000);
+	assert_err!(
+		EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, AssetMaxAllowed, ()>::ensure_price(
+			&0, 1, 2, spot_price
+		),
+		()
+	);
+}
+
+#[test]
+fn ensure_price_should_use_max_allowed_difference_when_asset_one_is_not_set() {
+	EXTERNAL_PRICE.with(|v| *v.borrow_mut() = EmaPrice::new(1, 10));
+	ASSET_MAX_ALLOWED.with(|v| *v.borrow_mut() = Some((3, Permill::from_percent(5))));
+	let spot_price = EmaPrice::new(104, 1000);
+	assert_err!(
+		EnsurePriceWithin::<u64, u32, SinglePriceProvider, MaxAllowed, AssetMaxAllowed, ()>::ensure_price(
+			&0, 1, 2, spot_price
+		),
 		()
 	);
 }
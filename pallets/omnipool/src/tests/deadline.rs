@@ -0,0 +1,93 @@
+use super::*;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+
+fn builder() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 100, 2000 * ONE),
+			(LP3, 200, 2000 * ONE),
+			(LP1, 100, 1000 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+}
+
+#[test]
+fn sell_with_deadline_should_work_when_deadline_is_not_reached() {
+	builder().build().execute_with(|| {
+		// Arrange
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 100, 400 * ONE));
+		System::set_block_number(10);
+
+		// Act
+		assert_ok!(Omnipool::sell_with_deadline(
+			RuntimeOrigin::signed(LP1),
+			100,
+			200,
+			50 * ONE,
+			10 * ONE,
+			10
+		));
+
+		// Assert
+		assert_eq!(Tokens::free_balance(100, &LP1), 550 * ONE);
+		assert_eq!(Tokens::free_balance(200, &LP1), 47808764940238);
+	});
+}
+
+#[test]
+fn sell_with_deadline_should_fail_when_deadline_has_passed() {
+	builder().build().execute_with(|| {
+		// Arrange
+		System::set_block_number(11);
+
+		// Act & Assert
+		assert_noop!(
+			Omnipool::sell_with_deadline(RuntimeOrigin::signed(LP1), 100, 200, 50 * ONE, 10 * ONE, 10),
+			Error::<Test>::DeadlinePassed
+		);
+	});
+}
+
+#[test]
+fn buy_with_deadline_should_work_when_deadline_is_not_reached() {
+	builder().build().execute_with(|| {
+		// Arrange
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 100, 400 * ONE));
+		System::set_block_number(10);
+
+		// Act
+		assert_ok!(Omnipool::buy_with_deadline(
+			RuntimeOrigin::signed(LP1),
+			200,
+			100,
+			50 * ONE,
+			100 * ONE,
+			10
+		));
+
+		// Assert
+		assert_eq!(Tokens::free_balance(100, &LP1), 547598253275108);
+		assert_eq!(Tokens::free_balance(200, &LP1), 50 * ONE);
+	});
+}
+
+#[test]
+fn buy_with_deadline_should_fail_when_deadline_has_passed() {
+	builder().build().execute_with(|| {
+		// Arrange
+		System::set_block_number(11);
+
+		// Act & Assert
+		assert_noop!(
+			Omnipool::buy_with_deadline(RuntimeOrigin::signed(LP1), 200, 100, 50 * ONE, 100 * ONE, 10),
+			Error::<Test>::DeadlinePassed
+		);
+	});
+}
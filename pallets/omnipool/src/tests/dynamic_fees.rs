@@ -0,0 +1,328 @@
+use super::*;
+use crate::types::{DynamicFee, FeeBounds};
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+fn fee_bounds() -> FeeBounds {
+	FeeBounds {
+		min_asset_fee: Permill::from_percent(1),
+		max_asset_fee: Permill::from_percent(5),
+		min_protocol_fee: Permill::from_rational(5u32, 1000u32),
+		max_protocol_fee: Permill::from_percent(2),
+	}
+}
+
+#[test]
+fn set_asset_fee_bounds_should_work_when_asset_exists() {
+	ExtBuilder::default()
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.add_endowed_accounts((LP2, 100, 2000 * ONE))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				100,
+				Some(fee_bounds())
+			));
+
+			assert_eq!(AssetFeeBounds::<Test>::get(100), Some(fee_bounds()));
+			assert_eq!(Omnipool::asset_fee(100), Permill::from_percent(1));
+			assert_eq!(Omnipool::protocol_fee(100), Permill::from_rational(5u32, 1000u32));
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::AssetFeeBoundsUpdated {
+					asset_id: 100,
+					bounds: Some(fee_bounds()),
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn set_asset_fee_bounds_should_fail_when_asset_is_not_in_pool() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_fee_bounds(RuntimeOrigin::root(), 100, Some(fee_bounds())),
+				Error::<Test>::AssetNotFound
+			);
+		});
+}
+
+#[test]
+fn set_asset_fee_bounds_should_fail_when_min_is_greater_than_max() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			let bounds = FeeBounds {
+				min_asset_fee: Permill::from_percent(5),
+				max_asset_fee: Permill::from_percent(1),
+				..fee_bounds()
+			};
+			assert_noop!(
+				Omnipool::set_asset_fee_bounds(RuntimeOrigin::root(), HDX, Some(bounds)),
+				Error::<Test>::InvalidFeeBounds
+			);
+		});
+}
+
+#[test]
+fn set_asset_fee_bounds_should_fail_when_max_fee_is_too_high() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			let bounds = FeeBounds {
+				max_asset_fee: Permill::one(),
+				..fee_bounds()
+			};
+			assert_noop!(
+				Omnipool::set_asset_fee_bounds(RuntimeOrigin::root(), HDX, Some(bounds)),
+				Error::<Test>::InvalidFeeBounds
+			);
+		});
+}
+
+#[test]
+fn set_asset_fee_bounds_should_fail_when_origin_is_not_technical_origin() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_fee_bounds(RuntimeOrigin::signed(LP1), HDX, Some(fee_bounds())),
+				sp_runtime::traits::BadOrigin
+			);
+		});
+}
+
+#[test]
+fn set_asset_fee_bounds_should_disable_dynamic_fees_when_bounds_are_removed() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 1000 * ONE))
+		.add_endowed_accounts((LP2, 100, 2000 * ONE))
+		.add_endowed_accounts((LP3, 200, 2000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_asset_fee(Permill::from_percent(3))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				200,
+				Some(fee_bounds())
+			));
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, 100 * ONE, 0));
+			assert!(AssetDynamicFees::<Test>::get(200).is_some());
+
+			assert_ok!(Omnipool::set_asset_fee_bounds(RuntimeOrigin::root(), 200, None));
+
+			assert_eq!(AssetFeeBounds::<Test>::get(200), None);
+			assert_eq!(AssetDynamicFees::<Test>::get(200), None);
+			assert_eq!(Omnipool::asset_fee(200), Permill::from_percent(3));
+		});
+}
+
+#[test]
+fn sell_should_raise_asset_fee_of_asset_out_and_protocol_fee_of_asset_in() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 1000 * ONE))
+		.add_endowed_accounts((LP2, 100, 2000 * ONE))
+		.add_endowed_accounts((LP3, 200, 2000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				100,
+				Some(fee_bounds())
+			));
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				200,
+				Some(fee_bounds())
+			));
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, 10 * ONE, 0));
+
+			// asset in - only protocol fee is raised
+			let fee_in = AssetDynamicFees::<Test>::get(100).unwrap();
+			assert_eq!(fee_in.asset_fee, Permill::from_percent(1));
+			assert!(fee_in.protocol_fee > Permill::from_rational(5u32, 1000u32));
+			assert!(fee_in.protocol_fee < Permill::from_percent(2));
+
+			// asset out - only asset fee is raised
+			let fee_out = AssetDynamicFees::<Test>::get(200).unwrap();
+			assert!(fee_out.asset_fee > Permill::from_percent(1));
+			assert!(fee_out.asset_fee < Permill::from_percent(5));
+			assert_eq!(fee_out.protocol_fee, Permill::from_rational(5u32, 1000u32));
+
+			let events = frame_system::Pallet::<Test>::events();
+			assert!(events.iter().any(|record| record.event
+				== crate::Event::AssetFeesUpdated {
+					asset_id: 100,
+					asset_fee: fee_in.asset_fee,
+					protocol_fee: fee_in.protocol_fee,
+				}
+				.into()));
+			assert!(events.iter().any(|record| record.event
+				== crate::Event::AssetFeesUpdated {
+					asset_id: 200,
+					asset_fee: fee_out.asset_fee,
+					protocol_fee: fee_out.protocol_fee,
+				}
+				.into()));
+		});
+}
+
+#[test]
+fn sell_of_hub_asset_should_raise_asset_fee_of_asset_out() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, LRNA, 100 * ONE))
+		.add_endowed_accounts((LP3, 200, 2000 * ONE))
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				200,
+				Some(fee_bounds())
+			));
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), LRNA, 200, 10 * ONE, 0));
+
+			let fee_out = AssetDynamicFees::<Test>::get(200).unwrap();
+			assert!(fee_out.asset_fee > Permill::from_percent(1));
+			assert_eq!(fee_out.protocol_fee, Permill::from_rational(5u32, 1000u32));
+		});
+}
+
+#[test]
+fn fees_should_be_capped_by_max_when_trade_is_large() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 1000 * ONE))
+		.add_endowed_accounts((LP2, 100, 2000 * ONE))
+		.add_endowed_accounts((LP3, 200, 2000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				100,
+				Some(fee_bounds())
+			));
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				200,
+				Some(fee_bounds())
+			));
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, 500 * ONE, 0));
+
+			assert_eq!(Omnipool::protocol_fee(100), Permill::from_percent(2));
+			assert_eq!(Omnipool::asset_fee(200), Permill::from_percent(5));
+		});
+}
+
+#[test]
+fn fees_should_decay_to_min_when_blocks_pass() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 1000 * ONE))
+		.add_endowed_accounts((LP2, 100, 2000 * ONE))
+		.add_endowed_accounts((LP3, 200, 2000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				200,
+				Some(fee_bounds())
+			));
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, 500 * ONE, 0));
+			assert_eq!(
+				AssetDynamicFees::<Test>::get(200),
+				Some(DynamicFee {
+					asset_fee: Permill::from_percent(5),
+					protocol_fee: Permill::from_rational(5u32, 1000u32),
+					updated_at: 1,
+				})
+			);
+
+			// Decay is 100 parts per block
+			System::set_block_number(11);
+			assert_eq!(
+				Omnipool::asset_fee(200),
+				Permill::from_percent(5) - Permill::from_parts(1_000)
+			);
+
+			System::set_block_number(1_000);
+			assert_eq!(Omnipool::asset_fee(200), Permill::from_percent(1));
+		});
+}
+
+#[test]
+fn sell_should_charge_dynamic_asset_fee_when_bounds_are_set() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 1000 * ONE))
+		.add_endowed_accounts((LP2, 100, 2000 * ONE))
+		.add_endowed_accounts((LP3, 200, 2000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let sell_amount = 50 * ONE;
+
+			let quote_without_fee = Omnipool::quote_sell(100, 200, sell_amount).unwrap();
+			assert_eq!(quote_without_fee.asset_fee, Balance::zero());
+
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				200,
+				Some(fee_bounds())
+			));
+
+			let quote = Omnipool::quote_sell(100, 200, sell_amount).unwrap();
+			assert!(quote.asset_fee > Balance::zero());
+			assert!(quote.amount_out < quote_without_fee.amount_out);
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, sell_amount, 0));
+
+			assert_balance!(LP1, 200, quote.amount_out);
+		});
+}
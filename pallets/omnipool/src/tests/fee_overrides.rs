@@ -0,0 +1,215 @@
+use super::*;
+use crate::types::{AssetFees, FeeBounds};
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+#[test]
+fn set_asset_fees_should_work_when_asset_exists() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			let fees = AssetFees {
+				asset_fee: Permill::from_percent(2),
+				protocol_fee: Permill::from_percent(1),
+			};
+
+			assert_ok!(Omnipool::set_asset_fees(RuntimeOrigin::root(), DAI, Some(fees)));
+
+			assert_eq!(AssetFeeOverrides::<Test>::get(DAI), Some(fees));
+			assert_eq!(Omnipool::asset_fee(DAI), Permill::from_percent(2));
+			assert_eq!(Omnipool::protocol_fee(DAI), Permill::from_percent(1));
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::AssetFeesSet {
+					asset_id: DAI,
+					fees: Some(fees),
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn asset_fees_should_fall_back_to_constants_when_override_is_removed() {
+	ExtBuilder::default()
+		.with_asset_fee(Permill::from_percent(3))
+		.with_protocol_fee(Permill::from_percent(4))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_eq!(Omnipool::asset_fee(DAI), Permill::from_percent(3));
+			assert_eq!(Omnipool::protocol_fee(DAI), Permill::from_percent(4));
+
+			assert_ok!(Omnipool::set_asset_fees(
+				RuntimeOrigin::root(),
+				DAI,
+				Some(AssetFees {
+					asset_fee: Permill::from_percent(1),
+					protocol_fee: Permill::zero(),
+				})
+			));
+			assert_eq!(Omnipool::asset_fee(DAI), Permill::from_percent(1));
+			assert_eq!(Omnipool::protocol_fee(DAI), Permill::zero());
+
+			// other assets are not affected
+			assert_eq!(Omnipool::asset_fee(HDX), Permill::from_percent(3));
+			assert_eq!(Omnipool::protocol_fee(HDX), Permill::from_percent(4));
+
+			assert_ok!(Omnipool::set_asset_fees(RuntimeOrigin::root(), DAI, None));
+
+			assert_eq!(AssetFeeOverrides::<Test>::get(DAI), None);
+			assert_eq!(Omnipool::asset_fee(DAI), Permill::from_percent(3));
+			assert_eq!(Omnipool::protocol_fee(DAI), Permill::from_percent(4));
+		});
+}
+
+#[test]
+fn asset_fees_override_should_take_precedence_over_dynamic_fees() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_fee_bounds(
+				RuntimeOrigin::root(),
+				DAI,
+				Some(FeeBounds {
+					min_asset_fee: Permill::from_percent(1),
+					max_asset_fee: Permill::from_percent(5),
+					min_protocol_fee: Permill::from_percent(1),
+					max_protocol_fee: Permill::from_percent(5),
+				})
+			));
+			assert_ok!(Omnipool::set_asset_fees(
+				RuntimeOrigin::root(),
+				DAI,
+				Some(AssetFees {
+					asset_fee: Permill::from_percent(10),
+					protocol_fee: Permill::from_percent(10),
+				})
+			));
+
+			assert_eq!(Omnipool::asset_fee(DAI), Permill::from_percent(10));
+			assert_eq!(Omnipool::protocol_fee(DAI), Permill::from_percent(10));
+		});
+}
+
+#[test]
+fn set_asset_fees_should_fail_when_asset_is_not_in_pool() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_fees(RuntimeOrigin::root(), 100, Some(AssetFees::default())),
+				Error::<Test>::AssetNotFound
+			);
+		});
+}
+
+#[test]
+fn set_asset_fees_should_fail_when_asset_fee_is_too_high() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_fees(
+					RuntimeOrigin::root(),
+					DAI,
+					Some(AssetFees {
+						asset_fee: Permill::one(),
+						protocol_fee: Permill::zero(),
+					})
+				),
+				Error::<Test>::InvalidAssetFees
+			);
+		});
+}
+
+#[test]
+fn set_asset_fees_should_fail_when_protocol_fee_is_not_lower_than_max_fee() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_fees(
+					RuntimeOrigin::root(),
+					DAI,
+					Some(AssetFees {
+						asset_fee: Permill::zero(),
+						protocol_fee: crate::types::MAX_FEE,
+					})
+				),
+				Error::<Test>::InvalidAssetFees
+			);
+		});
+}
+
+#[test]
+fn set_asset_fees_should_fail_when_origin_is_not_technical_origin() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_fees(RuntimeOrigin::signed(LP1), DAI, Some(AssetFees::default())),
+				sp_runtime::traits::BadOrigin
+			);
+		});
+}
+
+#[test]
+fn sell_should_charge_overridden_fees_when_set() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 1000 * ONE))
+		.add_endowed_accounts((LP2, 100, 2000 * ONE))
+		.add_endowed_accounts((LP3, 200, 2000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let sell_amount = 50 * ONE;
+
+			assert_ok!(Omnipool::set_asset_fees(
+				RuntimeOrigin::root(),
+				100,
+				Some(AssetFees {
+					asset_fee: Permill::zero(),
+					protocol_fee: Permill::from_percent(20),
+				})
+			));
+			assert_ok!(Omnipool::set_asset_fees(
+				RuntimeOrigin::root(),
+				200,
+				Some(AssetFees {
+					asset_fee: Permill::from_percent(10),
+					protocol_fee: Permill::zero(),
+				})
+			));
+
+			let asset_in_state = Omnipool::load_asset_state(100).unwrap();
+			let asset_out_state = Omnipool::load_asset_state(200).unwrap();
+
+			let expected = hydra_dx_math::omnipool::calculate_sell_state_changes(
+				&(&asset_in_state).into(),
+				&(&asset_out_state).into(),
+				sell_amount,
+				Permill::from_percent(10),
+				Permill::from_percent(20),
+				HubAssetImbalance::<Test>::get().value,
+			)
+			.unwrap();
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, sell_amount, 0));
+
+			assert_balance!(LP1, 200, *expected.asset_out.delta_reserve);
+		});
+}
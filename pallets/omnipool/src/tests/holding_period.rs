@@ -0,0 +1,236 @@
+use super::*;
+use crate::types::MinHoldingPeriod;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+fn builder() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+			(LP1, 1_000, 5000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+}
+
+fn holding_period(blocks: u64, early_removal_fee: Option<Permill>) -> Option<MinHoldingPeriod<u64>> {
+	Some(MinHoldingPeriod {
+		blocks,
+		early_removal_fee,
+	})
+}
+
+#[test]
+fn set_asset_min_holding_period_should_work_when_asset_exists() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::set_asset_min_holding_period(
+			RuntimeOrigin::root(),
+			1_000,
+			holding_period(10, None)
+		));
+
+		assert_eq!(AssetMinHoldingPeriod::<Test>::get(1_000), holding_period(10, None));
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::AssetMinHoldingPeriodSet {
+				asset_id: 1_000,
+				min_holding_period: holding_period(10, None),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_asset_min_holding_period_should_fail_when_asset_is_not_in_pool() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::set_asset_min_holding_period(RuntimeOrigin::root(), 2_000, holding_period(10, None)),
+			Error::<Test>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn set_asset_min_holding_period_should_fail_when_origin_is_not_technical_origin() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::set_asset_min_holding_period(RuntimeOrigin::signed(LP1), 1_000, holding_period(10, None)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn add_liquidity_should_record_creation_block_of_position() {
+	builder().build().execute_with(|| {
+		System::set_block_number(5);
+
+		let position_id = <NextPositionId<Test>>::get();
+
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+		assert_eq!(PositionCreatedAt::<Test>::get(position_id), Some(5));
+	});
+}
+
+#[test]
+fn remove_liquidity_should_fail_when_holding_period_has_not_passed_and_early_removal_is_not_allowed() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::set_asset_min_holding_period(
+			RuntimeOrigin::root(),
+			1_000,
+			holding_period(10, None)
+		));
+
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+		System::set_block_number(10);
+
+		assert_noop!(
+			Omnipool::remove_liquidity(RuntimeOrigin::signed(LP1), position_id, 400 * ONE),
+			Error::<Test>::MinHoldingPeriodNotPassed
+		);
+
+		System::set_block_number(11);
+
+		assert_ok!(Omnipool::remove_liquidity(
+			RuntimeOrigin::signed(LP1),
+			position_id,
+			400 * ONE
+		));
+
+		assert_balance!(LP1, 1_000, 5000 * ONE);
+		assert!(PositionCreatedAt::<Test>::get(position_id).is_none());
+	});
+}
+
+#[test]
+fn position_value_should_report_position_as_not_withdrawable_when_early_removal_is_not_allowed() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::set_asset_min_holding_period(
+			RuntimeOrigin::root(),
+			1_000,
+			holding_period(10, None)
+		));
+
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+		let value = Omnipool::position_value(position_id).unwrap();
+		assert!(!value.withdrawable);
+		assert_eq!(value.early_removal_fee, FixedU128::zero());
+		assert_eq!(value.amount, 400 * ONE);
+
+		System::set_block_number(11);
+
+		let value = Omnipool::position_value(position_id).unwrap();
+		assert!(value.withdrawable);
+	});
+}
+
+#[test]
+fn remove_liquidity_should_charge_early_removal_fee_when_holding_period_has_not_passed() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::set_asset_min_holding_period(
+			RuntimeOrigin::root(),
+			1_000,
+			holding_period(10, Some(Permill::from_percent(10)))
+		));
+
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+		let value = Omnipool::position_value(position_id).unwrap();
+		assert_eq!(value.withdrawal_fee, FixedU128::from_rational(1, 10));
+		assert_eq!(value.early_removal_fee, FixedU128::from_rational(1, 10));
+		assert!(value.withdrawable);
+
+		assert_ok!(Omnipool::remove_liquidity(
+			RuntimeOrigin::signed(LP1),
+			position_id,
+			400 * ONE
+		));
+
+		// fee stays in the pool
+		assert_balance!(LP1, 1_000, 4960 * ONE);
+		assert_balance!(Omnipool::protocol_account(), 1_000, 2040 * ONE);
+		assert_eq!(value.amount, 360 * ONE);
+		assert_hub_asset!();
+	});
+}
+
+#[test]
+fn remove_liquidity_should_not_be_restricted_when_position_was_not_created_by_add_liquidity() {
+	builder().build().execute_with(|| {
+		assert_ok!(Omnipool::set_asset_min_holding_period(
+			RuntimeOrigin::root(),
+			1_000,
+			holding_period(10, None)
+		));
+
+		// position of initial liquidity added by add_token
+		let position_id = <NextPositionId<Test>>::get() - 1;
+
+		assert_ok!(Omnipool::remove_liquidity(
+			RuntimeOrigin::signed(LP2),
+			position_id,
+			100 * ONE
+		));
+	});
+}
+
+#[test]
+fn merge_positions_should_keep_latest_creation_block() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+		System::set_block_number(7);
+
+		let merged_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+		assert_ok!(Omnipool::merge_positions(
+			RuntimeOrigin::signed(LP1),
+			position_id,
+			vec![merged_id].try_into().unwrap()
+		));
+
+		assert_eq!(PositionCreatedAt::<Test>::get(position_id), Some(7));
+		assert!(PositionCreatedAt::<Test>::get(merged_id).is_none());
+	});
+}
+
+#[test]
+fn split_position_should_copy_creation_block_to_new_position() {
+	builder().build().execute_with(|| {
+		System::set_block_number(3);
+
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+		let new_position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::split_position(
+			RuntimeOrigin::signed(LP1),
+			position_id,
+			100 * ONE
+		));
+
+		assert_eq!(PositionCreatedAt::<Test>::get(new_position_id), Some(3));
+	});
+}
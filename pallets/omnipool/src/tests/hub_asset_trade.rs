@@ -0,0 +1,264 @@
+use super::*;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+#[test]
+fn sell_asset_for_hub_asset_should_work_when_hub_asset_can_be_bought() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 2000 * ONE),
+			(LP2, 100, 100 * ONE),
+			(LP3, LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				LRNA,
+				Tradability::SELL | Tradability::BUY
+			));
+
+			let sell_amount = 10 * ONE;
+			let asset_state = Omnipool::load_asset_state(100).unwrap();
+			let old_imbalance = HubAssetImbalance::<Test>::get();
+
+			let expected_hub_amount = asset_state.hub_reserve * sell_amount / (asset_state.reserve + sell_amount);
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP2), 100, LRNA, sell_amount, 0));
+
+			assert_balance!(LP2, 100, 90 * ONE);
+			assert_balance!(LP2, LRNA, expected_hub_amount);
+
+			let updated_state = Omnipool::load_asset_state(100).unwrap();
+			assert_eq!(updated_state.reserve, asset_state.reserve + sell_amount);
+			assert_eq!(updated_state.hub_reserve, asset_state.hub_reserve - expected_hub_amount);
+
+			let updated_imbalance = HubAssetImbalance::<Test>::get();
+			assert!(updated_imbalance.negative);
+			assert!(updated_imbalance.value < old_imbalance.value);
+
+			assert_hub_asset!();
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::SellExecuted {
+					who: LP2,
+					asset_in: 100,
+					asset_out: LRNA,
+					amount_in: sell_amount,
+					amount_out: expected_hub_amount,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn buy_hub_asset_should_work_when_hub_asset_can_be_bought() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 2000 * ONE),
+			(LP2, 100, 100 * ONE),
+			(LP3, LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				LRNA,
+				Tradability::SELL | Tradability::BUY
+			));
+
+			let buy_amount = 5 * ONE;
+			let asset_state = Omnipool::load_asset_state(100).unwrap();
+			let old_imbalance = HubAssetImbalance::<Test>::get();
+
+			assert_ok!(Omnipool::buy(
+				RuntimeOrigin::signed(LP2),
+				LRNA,
+				100,
+				buy_amount,
+				100 * ONE
+			));
+
+			assert_balance!(LP2, LRNA, buy_amount);
+
+			let updated_state = Omnipool::load_asset_state(100).unwrap();
+			let amount_in = 100 * ONE - Tokens::free_balance(100, &LP2);
+			assert_eq!(updated_state.reserve, asset_state.reserve + amount_in);
+			assert_eq!(updated_state.hub_reserve, asset_state.hub_reserve - buy_amount);
+
+			let updated_imbalance = HubAssetImbalance::<Test>::get();
+			assert!(updated_imbalance.negative);
+			assert!(updated_imbalance.value < old_imbalance.value);
+
+			assert_hub_asset!();
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::BuyExecuted {
+					who: LP2,
+					asset_in: 100,
+					asset_out: LRNA,
+					amount_in,
+					amount_out: buy_amount,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn sell_asset_for_hub_asset_should_charge_protocol_fee() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 2000 * ONE),
+			(LP2, 100, 100 * ONE),
+			(LP3, LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_protocol_fee(Permill::from_percent(10))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				LRNA,
+				Tradability::SELL | Tradability::BUY
+			));
+
+			let sell_amount = 10 * ONE;
+			let asset_state = Omnipool::load_asset_state(100).unwrap();
+
+			let delta_hub_reserve = asset_state.hub_reserve * sell_amount / (asset_state.reserve + sell_amount);
+			let protocol_fee = Permill::from_percent(10).mul_floor(delta_hub_reserve);
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP2), 100, LRNA, sell_amount, 0));
+
+			assert_balance!(LP2, LRNA, delta_hub_reserve - protocol_fee);
+
+			let updated_state = Omnipool::load_asset_state(100).unwrap();
+			assert_eq!(updated_state.hub_reserve, asset_state.hub_reserve - delta_hub_reserve);
+
+			assert_hub_asset!();
+		});
+}
+
+#[test]
+fn buy_hub_asset_should_fail_when_imbalance_becomes_positive() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 2000 * ONE),
+			(LP2, 100, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				LRNA,
+				Tradability::SELL | Tradability::BUY
+			));
+
+			assert_noop!(
+				Omnipool::buy(RuntimeOrigin::signed(LP2), LRNA, 100, 5 * ONE, 100 * ONE),
+				Error::<Test>::PositiveImbalance
+			);
+			assert_noop!(
+				Omnipool::sell(RuntimeOrigin::signed(LP2), 100, LRNA, 10 * ONE, 0),
+				Error::<Test>::PositiveImbalance
+			);
+		});
+}
+
+#[test]
+fn buy_hub_asset_should_fail_when_limit_exceeds() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 2000 * ONE),
+			(LP2, 100, 100 * ONE),
+			(LP3, LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				LRNA,
+				Tradability::SELL | Tradability::BUY
+			));
+
+			assert_noop!(
+				Omnipool::buy(RuntimeOrigin::signed(LP2), LRNA, 100, 5 * ONE, ONE),
+				Error::<Test>::SellLimitExceeded
+			);
+			assert_noop!(
+				Omnipool::sell(RuntimeOrigin::signed(LP2), 100, LRNA, 10 * ONE, 10 * ONE),
+				Error::<Test>::BuyLimitNotReached
+			);
+		});
+}
+
+#[test]
+fn sell_asset_for_hub_asset_should_fail_when_asset_cannot_be_sold() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 2000 * ONE),
+			(LP2, 100, 100 * ONE),
+			(LP3, LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				LRNA,
+				Tradability::SELL | Tradability::BUY
+			));
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				100,
+				Tradability::BUY
+			));
+
+			assert_noop!(
+				Omnipool::sell(RuntimeOrigin::signed(LP2), 100, LRNA, 10 * ONE, 0),
+				Error::<Test>::NotAllowed
+			);
+			assert_noop!(
+				Omnipool::buy(RuntimeOrigin::signed(LP2), LRNA, 100, 5 * ONE, 100 * ONE),
+				Error::<Test>::NotAllowed
+			);
+		});
+}
@@ -1,4 +1,5 @@
 use super::*;
+use frame_support::assert_noop;
 use pretty_assertions::assert_eq;
 use sp_runtime::Permill;
 
@@ -210,3 +211,95 @@ fn imbalance_should_approach_zero_when_enough_trades_are_executed() {
 			assert_eq!(HubAssetImbalance::<Test>::get(), SimpleImbalance::default());
 		});
 }
+
+fn imbalance_builder() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 5000 * ONE),
+			(LP3, LRNA, 100 * ONE),
+			(TreasuryAccount::get(), LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+}
+
+#[test]
+fn reduce_imbalance_should_burn_hub_asset_from_treasury_when_imbalance_is_negative() {
+	imbalance_builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+
+		let imbalance = HubAssetImbalance::<Test>::get();
+		assert!(imbalance.negative && imbalance.value > 10 * ONE);
+
+		let hub_issuance = Tokens::total_issuance(LRNA);
+
+		assert_ok!(Omnipool::reduce_imbalance(RuntimeOrigin::root(), 10 * ONE));
+
+		let expected_imbalance = SimpleImbalance {
+			value: imbalance.value - 10 * ONE,
+			negative: true,
+		};
+		assert_eq!(HubAssetImbalance::<Test>::get(), expected_imbalance);
+		assert_balance!(TreasuryAccount::get(), LRNA, 90 * ONE);
+		assert_eq!(Tokens::total_issuance(LRNA), hub_issuance - 10 * ONE);
+		assert_hub_asset!();
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ImbalanceReduced {
+				amount: 10 * ONE,
+				imbalance: expected_imbalance,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn reduce_imbalance_should_fail_when_amount_exceeds_imbalance() {
+	imbalance_builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::reduce_imbalance(RuntimeOrigin::root(), 1),
+			Error::<Test>::ImbalanceExceeded
+		);
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+
+		let imbalance = HubAssetImbalance::<Test>::get();
+
+		assert_noop!(
+			Omnipool::reduce_imbalance(RuntimeOrigin::root(), imbalance.value + 1),
+			Error::<Test>::ImbalanceExceeded
+		);
+	});
+}
+
+#[test]
+fn reduce_imbalance_should_fail_when_treasury_has_insufficient_hub_asset() {
+	imbalance_builder().build().execute_with(|| {
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+
+		assert_ok!(Tokens::withdraw(LRNA, &TreasuryAccount::get(), 95 * ONE));
+
+		assert_noop!(
+			Omnipool::reduce_imbalance(RuntimeOrigin::root(), 10 * ONE),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn reduce_imbalance_should_fail_when_origin_is_not_authority_origin() {
+	imbalance_builder().build().execute_with(|| {
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, 50 * ONE, 0));
+
+		assert_noop!(
+			Omnipool::reduce_imbalance(RuntimeOrigin::signed(LP1), ONE),
+			sp_runtime::traits::BadOrigin
+		);
+	});
+}
@@ -1213,3 +1213,181 @@ proptest! {
 			});
 	}
 }
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(100))]
+	#[test]
+	fn remove_liquidity_invariants_with_merged_positions(amount in trade_amount(),
+		stable_price in price(),
+		stable_reserve in asset_reserve(),
+		native_reserve in asset_reserve(),
+		token_1 in pool_token(100),
+		token_2 in pool_token(200),
+		asset_fee in fee(),
+		protocol_fee in fee(),
+		buy_amount in trade_amount(),
+	) {
+		let lp1: u64 = 100;
+		let lp2: u64 = 200;
+		let seller: u64 = 500;
+		let buyer: u64 = 600;
+
+		ExtBuilder::default()
+			.with_endowed_accounts(vec![
+				(Omnipool::protocol_account(), DAI, stable_reserve ),
+				(Omnipool::protocol_account(), HDX, native_reserve ),
+				(lp1, 100, token_1.amount + 2 * ONE),
+				(lp2, 200, token_2.amount + 2 * ONE),
+				(seller, 200, 2 * amount + 200 * ONE),
+				(buyer, DAI, 200_000_000 * ONE),
+			])
+			.with_registered_asset(100)
+			.with_registered_asset(200)
+			.with_asset_fee(asset_fee)
+			.with_protocol_fee(protocol_fee)
+			.with_initial_pool(
+				stable_price,
+				FixedU128::from(1),
+			)
+			.with_token(token_1.asset_id, token_1.price, lp1, token_1.amount)
+			.with_token(token_2.asset_id, token_2.price, lp2, token_2.amount)
+			.build()
+			.execute_with(|| {
+				let position_id = <NextPositionId<Test>>::get();
+				assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(seller), 200, amount));
+
+				// Let's do a trade so price changes between positions
+				assert_ok!(Omnipool::buy(RuntimeOrigin::signed(buyer), 200, DAI, buy_amount, Balance::max_value()));
+
+				let merged_id = <NextPositionId<Test>>::get();
+				assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(seller), 200, amount));
+
+				let state_before_merge = Omnipool::load_asset_state(200).unwrap();
+				assert_ok!(Omnipool::merge_positions(RuntimeOrigin::signed(seller), position_id, vec![merged_id].try_into().unwrap()));
+				assert_eq!(state_before_merge, Omnipool::load_asset_state(200).unwrap());
+
+				assert_ok!(Omnipool::buy(RuntimeOrigin::signed(buyer), 200, DAI, buy_amount, Balance::max_value()));
+
+				let positions = vec![position_id];
+
+				let old_state_200 = Omnipool::load_asset_state(200).unwrap();
+				let old_imbalance = <HubAssetImbalance<Test>>::get();
+				let old_hub_liquidity = Tokens::free_balance(LRNA, &Omnipool::protocol_account());
+
+				// Positions keep all LP shares of the asset
+				let position_shares: Balance = <Positions<Test>>::iter().filter(|(_, p)| p.asset_id == 200).map(|(_, p)| p.shares).sum();
+				assert_eq!(position_shares, old_state_200.shares - old_state_200.protocol_shares);
+
+				for position_id in positions {
+					let position = <Positions<Test>>::get(position_id).unwrap();
+					assert_ok!(Omnipool::remove_liquidity(RuntimeOrigin::signed(seller), position_id, position.shares));
+				}
+
+				let new_state_200 = Omnipool::load_asset_state(200).unwrap();
+				let new_imbalance = <HubAssetImbalance<Test>>::get();
+				let new_hub_liquidity = Tokens::free_balance(LRNA, &Omnipool::protocol_account());
+
+				// Price should not change
+				assert_eq_approx!(old_state_200.price().unwrap(),
+						new_state_200.price().unwrap(),
+						FixedU128::from_float(0.0000000001),
+						"Price has changed after remove liquidity");
+
+				assert_eq_approx!( FixedU128::from((old_imbalance.value, old_hub_liquidity)),
+								   FixedU128::from((new_imbalance.value, new_hub_liquidity)),
+								   FixedU128::from_float(0.000000001),
+								   "L/Q ratio changed after remove liquidity"
+				);
+
+				assert_eq!(new_hub_liquidity, sum_asset_hub_liquidity());
+			});
+	}
+}
+
+proptest! {
+	#![proptest_config(ProptestConfig::with_cases(100))]
+	#[test]
+	fn remove_liquidity_invariants_with_split_position(amount in trade_amount(),
+		stable_price in price(),
+		stable_reserve in asset_reserve(),
+		native_reserve in asset_reserve(),
+		token_1 in pool_token(100),
+		token_2 in pool_token(200),
+		asset_fee in fee(),
+		protocol_fee in fee(),
+		buy_amount in trade_amount(),
+	) {
+		let lp1: u64 = 100;
+		let lp2: u64 = 200;
+		let seller: u64 = 500;
+		let buyer: u64 = 600;
+
+		ExtBuilder::default()
+			.with_endowed_accounts(vec![
+				(Omnipool::protocol_account(), DAI, stable_reserve ),
+				(Omnipool::protocol_account(), HDX, native_reserve ),
+				(lp1, 100, token_1.amount + 2 * ONE),
+				(lp2, 200, token_2.amount + 2 * ONE),
+				(seller, 200, 2 * amount + 200 * ONE),
+				(buyer, DAI, 200_000_000 * ONE),
+			])
+			.with_registered_asset(100)
+			.with_registered_asset(200)
+			.with_asset_fee(asset_fee)
+			.with_protocol_fee(protocol_fee)
+			.with_initial_pool(
+				stable_price,
+				FixedU128::from(1),
+			)
+			.with_token(token_1.asset_id, token_1.price, lp1, token_1.amount)
+			.with_token(token_2.asset_id, token_2.price, lp2, token_2.amount)
+			.build()
+			.execute_with(|| {
+				let position_id = <NextPositionId<Test>>::get();
+				assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(seller), 200, amount));
+
+				let position = <Positions<Test>>::get(position_id).unwrap();
+
+				let state_before_split = Omnipool::load_asset_state(200).unwrap();
+				let split_id = <NextPositionId<Test>>::get();
+				assert_ok!(Omnipool::split_position(RuntimeOrigin::signed(seller), position_id, position.shares / 3));
+				assert_eq!(state_before_split, Omnipool::load_asset_state(200).unwrap());
+
+				// Let's do a trade so imbalance and price changes
+				assert_ok!(Omnipool::buy(RuntimeOrigin::signed(buyer), 200, DAI, buy_amount, Balance::max_value()));
+
+				let positions = vec![position_id, split_id];
+
+				let old_state_200 = Omnipool::load_asset_state(200).unwrap();
+				let old_imbalance = <HubAssetImbalance<Test>>::get();
+				let old_hub_liquidity = Tokens::free_balance(LRNA, &Omnipool::protocol_account());
+
+				// Positions keep all LP shares of the asset
+				let position_shares: Balance = <Positions<Test>>::iter().filter(|(_, p)| p.asset_id == 200).map(|(_, p)| p.shares).sum();
+				assert_eq!(position_shares, old_state_200.shares - old_state_200.protocol_shares);
+
+				for position_id in positions {
+					let position = <Positions<Test>>::get(position_id).unwrap();
+					assert_ok!(Omnipool::remove_liquidity(RuntimeOrigin::signed(seller), position_id, position.shares));
+				}
+
+				let new_state_200 = Omnipool::load_asset_state(200).unwrap();
+				let new_imbalance = <HubAssetImbalance<Test>>::get();
+				let new_hub_liquidity = Tokens::free_balance(LRNA, &Omnipool::protocol_account());
+
+				// Price should not change
+				assert_eq_approx!(old_state_200.price().unwrap(),
+						new_state_200.price().unwrap(),
+						FixedU128::from_float(0.0000000001),
+						"Price has changed after remove liquidity");
+
+				assert_eq_approx!( FixedU128::from((old_imbalance.value, old_hub_liquidity)),
+								   FixedU128::from((new_imbalance.value, new_hub_liquidity)),
+								   FixedU128::from_float(0.000000001),
+								   "L/Q ratio changed after remove liquidity"
+				);
+
+				assert_eq!(new_hub_liquidity, sum_asset_hub_liquidity());
+			});
+	}
+}
@@ -0,0 +1,309 @@
+use super::*;
+use frame_support::assert_noop;
+use orml_traits::MultiReservableCurrency;
+use pretty_assertions::assert_eq;
+
+const TOKEN: AssetId = 100;
+
+fn builder() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, TOKEN, 5000 * ONE),
+			(LP1, HDX, 1000 * ONE),
+			(LP2, DAI, 1000 * ONE),
+		])
+		.with_registered_asset(TOKEN)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+}
+
+#[test]
+fn propose_token_should_add_restricted_asset_and_reserve_bond() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let position_id = <NextPositionId<Test>>::get();
+
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		assert_balance!(LP1, TOKEN, 3000 * ONE);
+		assert_balance!(LP1, HDX, 900 * ONE);
+		assert_eq!(Tokens::reserved_balance(HDX, &LP1), ListingBond::get());
+
+		assert_asset_state!(
+			TOKEN,
+			AssetReserveState {
+				reserve: 2000 * ONE,
+				hub_reserve: 1300 * ONE,
+				shares: 2000 * ONE,
+				protocol_shares: 0,
+				cap: FixedU128::from(ListingWeightCap::get()).into_inner(),
+				tradable: Tradability::REMOVE_LIQUIDITY,
+			}
+		);
+		assert_eq!(Positions::<Test>::get(position_id).unwrap().shares, 2000 * ONE);
+		assert_hub_asset!();
+
+		let listing = Listing {
+			proposer: LP1,
+			bond: ListingBond::get(),
+			position_id,
+			challenge_end: 1 + ListingChallengePeriod::get(),
+		};
+		assert_eq!(Listings::<Test>::get(TOKEN), Some(listing));
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ListingProposed {
+				asset_id: TOKEN,
+				proposer: LP1,
+				bond: ListingBond::get(),
+				position_id,
+				challenge_end: 1 + ListingChallengePeriod::get(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn propose_token_should_fail_when_hub_reserve_exceeds_listing_weight_cap() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::propose_token(RuntimeOrigin::signed(LP1), TOKEN, 2000 * ONE, FixedU128::from(2)),
+			Error::<Test>::AssetWeightCapExceeded
+		);
+	});
+}
+
+#[test]
+fn propose_token_should_fail_when_proposer_cannot_reserve_bond() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::propose_token(RuntimeOrigin::signed(LP2), TOKEN, 0, FixedU128::from_float(0.65)),
+			orml_tokens::Error::<Test>::BalanceTooLow
+		);
+	});
+}
+
+#[test]
+fn propose_token_should_fail_when_asset_is_already_in_pool() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::propose_token(RuntimeOrigin::signed(LP1), DAI, 100 * ONE, FixedU128::from(1)),
+			Error::<Test>::AssetAlreadyAdded
+		);
+	});
+}
+
+#[test]
+fn propose_token_should_fail_when_asset_is_hub_asset() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::propose_token(RuntimeOrigin::signed(LP1), LRNA, 100 * ONE, FixedU128::from(1)),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn asset_should_not_be_traded_or_liquidity_added_when_listing_is_pending() {
+	builder().build().execute_with(|| {
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		assert_noop!(
+			Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), TOKEN, 100 * ONE),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			Omnipool::sell(RuntimeOrigin::signed(LP2), DAI, TOKEN, 10 * ONE, 0),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			Omnipool::sell(RuntimeOrigin::signed(LP1), TOKEN, DAI, 10 * ONE, 0),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			Omnipool::buy(RuntimeOrigin::signed(LP2), TOKEN, DAI, 10 * ONE, u128::MAX),
+			Error::<Test>::NotAllowed
+		);
+		assert_noop!(
+			Omnipool::sell(RuntimeOrigin::signed(LP1), TOKEN, LRNA, 10 * ONE, 0),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn approve_listing_should_make_asset_fully_tradable_when_challenge_period_passed() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		System::set_block_number(2 + ListingChallengePeriod::get());
+
+		assert_ok!(Omnipool::approve_listing(RuntimeOrigin::signed(LP2), TOKEN));
+
+		assert_eq!(
+			Omnipool::load_asset_state(TOKEN).unwrap().tradable,
+			Tradability::default()
+		);
+		assert!(Listings::<Test>::get(TOKEN).is_none());
+		assert_eq!(Tokens::reserved_balance(HDX, &LP1), 0);
+		assert_balance!(LP1, HDX, 1000 * ONE);
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ListingApproved {
+				asset_id: TOKEN,
+				proposer: LP1,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn approve_listing_should_fail_when_challenge_period_has_not_passed() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		System::set_block_number(1 + ListingChallengePeriod::get());
+
+		assert_noop!(
+			Omnipool::approve_listing(RuntimeOrigin::signed(LP2), TOKEN),
+			Error::<Test>::ChallengePeriodNotOver
+		);
+	});
+}
+
+#[test]
+fn approve_listing_should_fail_when_listing_does_not_exist() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::approve_listing(RuntimeOrigin::signed(LP2), DAI),
+			Error::<Test>::ListingNotFound
+		);
+	});
+}
+
+#[test]
+fn reject_listing_should_remove_asset_refund_liquidity_and_slash_bond() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let position_id = <NextPositionId<Test>>::get();
+
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		assert_ok!(Omnipool::reject_listing(RuntimeOrigin::root(), TOKEN));
+
+		assert!(Assets::<Test>::get(TOKEN).is_none());
+		assert!(Positions::<Test>::get(position_id).is_none());
+		assert!(Listings::<Test>::get(TOKEN).is_none());
+
+		assert_balance!(LP1, TOKEN, 5000 * ONE);
+		assert_balance!(Omnipool::protocol_account(), TOKEN, 0);
+		assert_balance!(LP1, HDX, 900 * ONE);
+		assert_eq!(Tokens::reserved_balance(HDX, &LP1), 0);
+		assert_balance!(TreasuryAccount::get(), HDX, ListingBond::get());
+		assert_balance!(TreasuryAccount::get(), LRNA, 1300 * ONE);
+		assert_hub_asset!();
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ListingRejected {
+				asset_id: TOKEN,
+				proposer: LP1,
+				bond_slashed: ListingBond::get(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn reject_listing_should_refund_reserve_to_treasury_when_position_was_sacrificed() {
+	builder().build().execute_with(|| {
+		let position_id = <NextPositionId<Test>>::get();
+
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP1), position_id));
+
+		assert_ok!(Omnipool::reject_listing(RuntimeOrigin::root(), TOKEN));
+
+		assert_balance!(LP1, TOKEN, 3000 * ONE);
+		assert_balance!(TreasuryAccount::get(), TOKEN, 2000 * ONE);
+		assert_hub_asset!();
+	});
+}
+
+#[test]
+fn reject_listing_should_fail_when_challenge_period_has_passed() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		System::set_block_number(2 + ListingChallengePeriod::get());
+
+		assert_noop!(
+			Omnipool::reject_listing(RuntimeOrigin::root(), TOKEN),
+			Error::<Test>::ChallengePeriodOver
+		);
+	});
+}
+
+#[test]
+fn reject_listing_should_fail_when_origin_is_not_authority() {
+	builder().build().execute_with(|| {
+		assert_ok!(Omnipool::propose_token(
+			RuntimeOrigin::signed(LP1),
+			TOKEN,
+			2000 * ONE,
+			FixedU128::from_float(0.65)
+		));
+
+		assert_noop!(
+			Omnipool::reject_listing(RuntimeOrigin::signed(LP2), TOKEN),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
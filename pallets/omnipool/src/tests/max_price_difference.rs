@@ -0,0 +1,130 @@
+use super::*;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+#[test]
+fn set_asset_max_price_difference_should_work_when_asset_exists() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(Omnipool::set_asset_max_price_difference(
+				RuntimeOrigin::root(),
+				DAI,
+				Some(Permill::from_percent(5))
+			));
+
+			assert_eq!(
+				AssetMaxPriceDifference::<Test>::get(DAI),
+				Some(Permill::from_percent(5))
+			);
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::AssetMaxPriceDifferenceSet {
+					asset_id: DAI,
+					max_difference: Some(Permill::from_percent(5)),
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn set_asset_max_price_difference_should_remove_override_when_none_is_set() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_max_price_difference(
+				RuntimeOrigin::root(),
+				DAI,
+				Some(Permill::from_percent(5))
+			));
+
+			assert_ok!(Omnipool::set_asset_max_price_difference(
+				RuntimeOrigin::root(),
+				DAI,
+				None
+			));
+
+			assert_eq!(AssetMaxPriceDifference::<Test>::get(DAI), None);
+		});
+}
+
+#[test]
+fn set_asset_max_price_difference_should_fail_when_asset_is_not_in_pool() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_max_price_difference(RuntimeOrigin::root(), 100, Some(Permill::from_percent(5))),
+				Error::<Test>::AssetNotFound
+			);
+		});
+}
+
+#[test]
+fn set_asset_max_price_difference_should_fail_when_origin_is_not_technical_origin() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::set_asset_max_price_difference(
+					RuntimeOrigin::signed(LP1),
+					DAI,
+					Some(Permill::from_percent(5))
+				),
+				sp_runtime::traits::BadOrigin
+			);
+		});
+}
+
+#[test]
+fn add_liquidity_should_work_when_price_difference_is_within_asset_max_price_difference() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 1_000, 5000 * ONE))
+		.add_endowed_accounts((LP2, 1_000, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_max_allowed_price_difference(Permill::from_percent(1))
+		.with_external_price_adjustment((3, 100, false))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_max_price_difference(
+				RuntimeOrigin::root(),
+				1_000,
+				Some(Permill::from_percent(5))
+			));
+
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+		});
+}
+
+#[test]
+fn add_liquidity_should_fail_when_price_difference_is_not_within_asset_max_price_difference() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 1_000, 5000 * ONE))
+		.add_endowed_accounts((LP2, 1_000, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_max_allowed_price_difference(Permill::from_percent(10))
+		.with_external_price_adjustment((1, 100, false))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_max_price_difference(
+				RuntimeOrigin::root(),
+				1_000,
+				Some(Permill::from_rational(1u32, 1000u32))
+			));
+
+			assert_noop!(
+				Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE),
+				Error::<Test>::PriceDifferenceTooHigh
+			);
+		});
+}
@@ -155,10 +155,16 @@ parameter_types! {
 	pub const HDXAssetId: AssetId = HDX;
 	pub const LRNAAssetId: AssetId = LRNA;
 	pub const DAIAssetId: AssetId = DAI;
+	pub const ListingBond: Balance = 100 * ONE;
+	pub const ListingChallengePeriod: u64 = 10;
+	pub const ListingWeightCap: Permill = Permill::from_percent(20);
 	pub const PosiitionCollectionId: u32= 1000;
 
 	pub ProtocolFee: Permill = PROTOCOL_FEE.with(|v| *v.borrow());
 	pub AssetFee: Permill = ASSET_FEE.with(|v| *v.borrow());
+	pub DynamicFeeAmplification: FixedU128 = FixedU128::from(2);
+	pub DynamicFeeDecay: Permill = Permill::from_parts(100);
+	pub const TreasuryAccount: AccountId = 1000;
 	pub AssetWeightCap: Permill =ASSET_WEIGHT_CAP.with(|v| *v.borrow());
 	pub MinAddedLiquidity: Balance = MIN_ADDED_LIQUDIITY.with(|v| *v.borrow());
 	pub MinTradeAmount: Balance = MIN_TRADE_AMOUNT.with(|v| *v.borrow());
@@ -176,9 +182,12 @@ impl Config for Test {
 	type PositionItemId = u32;
 	type Currency = Tokens;
 	type AuthorityOrigin = EnsureRoot<Self::AccountId>;
+	type TreasuryAccount = TreasuryAccount;
 	type HubAssetId = LRNAAssetId;
 	type ProtocolFee = ProtocolFee;
 	type AssetFee = AssetFee;
+	type DynamicFeeAmplification = DynamicFeeAmplification;
+	type DynamicFeeDecay = DynamicFeeDecay;
 	type StableCoinAssetId = DAIAssetId;
 	type WeightInfo = ();
 	type HdxAssetId = HDXAssetId;
@@ -190,11 +199,17 @@ impl Config for Test {
 	type TechnicalOrigin = EnsureRoot<Self::AccountId>;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxMergedPositions = ConstU32<10>;
+	type MaxAddLiquidityBatch = ConstU32<10>;
+	type MaxProtocolFeeBeneficiaries = ConstU32<5>;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingWeightCap = ListingWeightCap;
 	type CollectionId = u32;
 	type OmnipoolHooks = ();
 	type PriceBarrier = (
-		EnsurePriceWithin<AccountId, AssetId, MockOracle, FourPercentDiff, ()>,
-		EnsurePriceWithin<AccountId, AssetId, MockOracle, MaxPriceDiff, ()>,
+		EnsurePriceWithin<AccountId, AssetId, MockOracle, FourPercentDiff, MaxPriceDifferenceOf<Test>, ()>,
+		EnsurePriceWithin<AccountId, AssetId, MockOracle, MaxPriceDiff, MaxPriceDifferenceOf<Test>, ()>,
 	);
 	type MinWithdrawalFee = MinWithdrawFee;
 	type ExternalPriceOracle = WithdrawFeePriceOracle;
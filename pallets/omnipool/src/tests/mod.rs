@@ -6,15 +6,28 @@ use sp_runtime::{FixedPointNumber, FixedU128};
 mod add_liquidity;
 mod add_token;
 mod buy;
+mod deadline;
+mod dynamic_fees;
+mod fee_overrides;
+mod holding_period;
+mod hub_asset_trade;
 mod invariants;
+mod listing;
+mod max_price_difference;
 mod remove_liquidity;
+mod remove_token;
 mod sell;
+mod try_state;
 
 mod barrier;
 mod imbalance;
 mod init_pool;
 pub(crate) mod mock;
+mod position_value;
 mod positions;
+mod protocol_fee;
+mod protocol_liquidity;
+mod quote;
 mod refund;
 mod tradability;
 mod tvl;
@@ -0,0 +1,144 @@
+use super::*;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+#[test]
+fn position_value_should_match_remove_liquidity_result_when_price_is_higher() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP3, 1_000, 100 * ONE),
+			(LP1, 1_000, 5000 * ONE),
+			(LP2, DAI, 50000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP3, 100 * ONE)
+		.with_min_withdrawal_fee(Permill::from_float(0.01))
+		.build()
+		.execute_with(|| {
+			let liq_added = 400 * ONE;
+
+			let current_position_id = <NextPositionId<Test>>::get();
+
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, liq_added));
+
+			assert_ok!(Omnipool::buy(
+				RuntimeOrigin::signed(LP2),
+				1_000,
+				DAI,
+				200 * ONE,
+				500000 * ONE
+			));
+
+			let value = Omnipool::position_value(current_position_id).unwrap();
+
+			assert_ok!(Omnipool::remove_liquidity(
+				RuntimeOrigin::signed(LP1),
+				current_position_id,
+				liq_added
+			));
+
+			assert_eq!(value.asset_id, 1_000);
+			assert_eq!(value.amount, Tokens::free_balance(1_000, &LP1) - 4600 * ONE);
+			assert_eq!(value.hub_amount, Tokens::free_balance(LRNA, &LP1));
+			assert_eq!(value.withdrawal_fee, FixedU128::from_rational(1, 100));
+			assert!(value.impermanent_loss > FixedU128::zero());
+		});
+}
+
+#[test]
+fn impermanent_loss_should_be_zero_when_price_has_not_changed() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+			(LP1, 1_000, 5000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_min_withdrawal_fee(Permill::from_float(0.01))
+		.build()
+		.execute_with(|| {
+			let liq_added = 400 * ONE;
+
+			let current_position_id = <NextPositionId<Test>>::get();
+
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, liq_added));
+
+			let value = Omnipool::position_value(current_position_id).unwrap();
+
+			assert_eq!(
+				value,
+				PositionValue {
+					asset_id: 1_000,
+					amount: liq_added - liq_added / 100,
+					hub_amount: 0,
+					withdrawal_fee: FixedU128::from_rational(1, 100),
+					early_removal_fee: FixedU128::zero(),
+					withdrawable: true,
+					impermanent_loss: FixedU128::zero(),
+				}
+			);
+		});
+}
+
+#[test]
+fn impermanent_loss_should_be_calculated_against_entry_price() {
+	// r = 4: 1 - 2 * sqrt(4) / (1 + 4) = 1 / 5
+	assert_eq!(
+		Omnipool::impermanent_loss(FixedU128::from(1), FixedU128::from(4)),
+		FixedU128::from_rational(1, 5)
+	);
+	assert_eq!(
+		Omnipool::impermanent_loss(FixedU128::from(4), FixedU128::from(1)),
+		FixedU128::from_rational(1, 5)
+	);
+	assert_eq!(
+		Omnipool::impermanent_loss(FixedU128::from(2), FixedU128::from(2)),
+		FixedU128::zero()
+	);
+}
+
+#[test]
+fn position_value_should_not_change_state() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+			(LP1, 1_000, 5000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let current_position_id = <NextPositionId<Test>>::get();
+
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+			let asset_state = Omnipool::load_asset_state(1_000).unwrap();
+			let position = Positions::<Test>::get(current_position_id).unwrap();
+
+			assert_ok!(Omnipool::position_value(current_position_id));
+
+			assert_eq!(Omnipool::load_asset_state(1_000).unwrap(), asset_state);
+			assert_eq!(Positions::<Test>::get(current_position_id).unwrap(), position);
+		});
+}
+
+#[test]
+fn position_value_should_fail_when_position_does_not_exist() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(Omnipool::position_value(1_000_000), Error::<Test>::PositionNotFound);
+		});
+}
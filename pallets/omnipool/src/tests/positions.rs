@@ -122,3 +122,239 @@ fn sacrifice_position_should_emit_event_when_succesful() {
 			);
 		});
 }
+
+#[test]
+fn merge_positions_should_work_when_positions_have_same_asset_and_owner() {
+	let asset_id: AssetId = 1_000;
+
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, asset_id, 5000 * ONE))
+		.add_endowed_accounts((LP2, asset_id, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(asset_id, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			// Arrange - create positions with different entry prices
+			let position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), asset_id, 400 * ONE));
+
+			let merged_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), asset_id, 200 * ONE));
+			let merged_position = Positions::<Test>::get(merged_id).unwrap();
+			assert_ok!(Omnipool::set_position(
+				merged_id,
+				&Position {
+					price: (ONE, ONE),
+					..merged_position
+				}
+			));
+
+			let asset_state = Omnipool::load_asset_state(asset_id).unwrap();
+
+			// Act
+			assert_ok!(Omnipool::merge_positions(
+				RuntimeOrigin::signed(LP1),
+				position_id,
+				vec![merged_id].try_into().unwrap()
+			));
+
+			// Assert
+			// - amount and shares are summed up
+			// - price is weighted average: (400 * 0.65 + 200 * 1) / 600
+			// - merged position is destroyed and nft is burned
+			// - asset state is not changed
+			assert_eq!(
+				Positions::<Test>::get(position_id).unwrap(),
+				Position {
+					asset_id,
+					amount: 600 * ONE,
+					shares: 600 * ONE,
+					price: (460 * ONE, 600 * ONE),
+				}
+			);
+			assert_eq!(Positions::<Test>::get(merged_id), None);
+			assert_eq!(POSITIONS.with(|v| v.borrow().get(&merged_id).copied()), None);
+			assert_eq!(Omnipool::load_asset_state(asset_id).unwrap(), asset_state);
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::PositionUpdated {
+					position_id,
+					owner: LP1,
+					asset: asset_id,
+					amount: 600 * ONE,
+					shares: 600 * ONE,
+					price: FixedU128::from_rational(460, 600),
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn merge_positions_should_fail_when_positions_have_different_assets() {
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, 100, 5000 * ONE))
+		.add_endowed_accounts((LP1, 200, 5000 * ONE))
+		.add_endowed_accounts((LP2, 100, 5000 * ONE))
+		.add_endowed_accounts((LP2, 200, 5000 * ONE))
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 100, 400 * ONE));
+			let other_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 200, 400 * ONE));
+
+			assert_noop!(
+				Omnipool::merge_positions(
+					RuntimeOrigin::signed(LP1),
+					position_id,
+					vec![other_id].try_into().unwrap()
+				),
+				Error::<Test>::PositionAssetMismatch
+			);
+		});
+}
+
+#[test]
+fn merge_positions_should_fail_when_caller_is_not_owner_of_all_positions() {
+	let asset_id: AssetId = 1_000;
+
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, asset_id, 5000 * ONE))
+		.add_endowed_accounts((LP2, asset_id, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(asset_id, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), asset_id, 400 * ONE));
+			let other_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP2), asset_id, 400 * ONE));
+
+			assert_noop!(
+				Omnipool::merge_positions(
+					RuntimeOrigin::signed(LP1),
+					position_id,
+					vec![other_id].try_into().unwrap()
+				),
+				Error::<Test>::Forbidden
+			);
+		});
+}
+
+#[test]
+fn merge_positions_should_fail_when_position_is_merged_into_itself() {
+	let asset_id: AssetId = 1_000;
+
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, asset_id, 5000 * ONE))
+		.add_endowed_accounts((LP2, asset_id, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(asset_id, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), asset_id, 400 * ONE));
+
+			assert_noop!(
+				Omnipool::merge_positions(
+					RuntimeOrigin::signed(LP1),
+					position_id,
+					vec![position_id].try_into().unwrap()
+				),
+				Error::<Test>::DuplicatePosition
+			);
+		});
+}
+
+#[test]
+fn split_position_should_work_when_shares_are_less_than_position_shares() {
+	let asset_id: AssetId = 1_000;
+
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, asset_id, 5000 * ONE))
+		.add_endowed_accounts((LP2, asset_id, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(asset_id, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			let position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), asset_id, 400 * ONE));
+
+			let asset_state = Omnipool::load_asset_state(asset_id).unwrap();
+
+			let new_position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::split_position(
+				RuntimeOrigin::signed(LP1),
+				position_id,
+				100 * ONE
+			));
+
+			assert_eq!(
+				Positions::<Test>::get(position_id).unwrap(),
+				Position {
+					asset_id,
+					amount: 300 * ONE,
+					shares: 300 * ONE,
+					price: (1560 * ONE, 2400 * ONE),
+				}
+			);
+			assert_eq!(
+				Positions::<Test>::get(new_position_id).unwrap(),
+				Position {
+					asset_id,
+					amount: 100 * ONE,
+					shares: 100 * ONE,
+					price: (1560 * ONE, 2400 * ONE),
+				}
+			);
+			assert_eq!(POSITIONS.with(|v| v.borrow().get(&new_position_id).copied()), Some(LP1));
+			assert_eq!(Omnipool::load_asset_state(asset_id).unwrap(), asset_state);
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::PositionCreated {
+					position_id: new_position_id,
+					owner: LP1,
+					asset: asset_id,
+					amount: 100 * ONE,
+					shares: 100 * ONE,
+					price: FixedU128::from_rational(65, 100),
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn split_position_should_fail_when_shares_are_not_less_than_position_shares() {
+	let asset_id: AssetId = 1_000;
+
+	ExtBuilder::default()
+		.add_endowed_accounts((LP1, asset_id, 5000 * ONE))
+		.add_endowed_accounts((LP2, asset_id, 5000 * ONE))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(asset_id, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), asset_id, 400 * ONE));
+
+			assert_noop!(
+				Omnipool::split_position(RuntimeOrigin::signed(LP1), position_id, 400 * ONE),
+				Error::<Test>::InsufficientShares
+			);
+			assert_noop!(
+				Omnipool::split_position(RuntimeOrigin::signed(LP1), position_id, 0),
+				Error::<Test>::InvalidSharesAmount
+			);
+		});
+}
@@ -0,0 +1,260 @@
+use super::*;
+use frame_support::assert_noop;
+use frame_support::traits::Hooks;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+const BENEFICIARY: u64 = 500;
+
+fn builder() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP1, 100, 5000 * ONE),
+			(LP1, 200, 5000 * ONE),
+			(LP3, 200, 1000 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_protocol_fee(Permill::from_percent(1))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP1, 2000 * ONE)
+}
+
+fn beneficiaries(
+	list: Vec<(u64, Permill)>,
+) -> BoundedVec<(u64, Permill), <Test as crate::Config>::MaxProtocolFeeBeneficiaries> {
+	list.try_into().unwrap()
+}
+
+fn hdx_hub_reserve() -> Balance {
+	Omnipool::load_asset_state(HDX).unwrap().hub_reserve
+}
+
+#[test]
+fn set_protocol_fee_beneficiaries_should_work_when_shares_are_valid() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		let list = beneficiaries(vec![
+			(TreasuryAccount::get(), Permill::from_percent(50)),
+			(BENEFICIARY, Permill::from_percent(50)),
+		]);
+
+		assert_ok!(Omnipool::set_protocol_fee_beneficiaries(
+			RuntimeOrigin::root(),
+			list.clone()
+		));
+
+		assert_eq!(ProtocolFeeBeneficiaries::<Test>::get(), list);
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ProtocolFeeBeneficiariesSet {
+				beneficiaries: list.into_inner(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn set_protocol_fee_beneficiaries_should_fail_when_shares_exceed_hundred_percent() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::set_protocol_fee_beneficiaries(
+				RuntimeOrigin::root(),
+				beneficiaries(vec![
+					(TreasuryAccount::get(), Permill::from_percent(60)),
+					(BENEFICIARY, Permill::from_percent(50)),
+				])
+			),
+			Error::<Test>::InvalidProtocolFeeBeneficiaries
+		);
+	});
+}
+
+#[test]
+fn set_protocol_fee_beneficiaries_should_fail_when_beneficiary_is_duplicated() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::set_protocol_fee_beneficiaries(
+				RuntimeOrigin::root(),
+				beneficiaries(vec![
+					(BENEFICIARY, Permill::from_percent(10)),
+					(BENEFICIARY, Permill::from_percent(20)),
+				])
+			),
+			Error::<Test>::InvalidProtocolFeeBeneficiaries
+		);
+	});
+}
+
+#[test]
+fn set_protocol_fee_beneficiaries_should_fail_when_origin_is_not_authority() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::set_protocol_fee_beneficiaries(
+				RuntimeOrigin::signed(LP1),
+				beneficiaries(vec![(BENEFICIARY, Permill::from_percent(10))])
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn sell_should_accrue_protocol_fee_to_beneficiaries_when_beneficiaries_are_set() {
+	let total_protocol_fee = builder().build().execute_with(|| {
+		let hdx_hub_reserve_before = hdx_hub_reserve();
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), 200, 100, 100 * ONE, 0));
+		hdx_hub_reserve() - hdx_hub_reserve_before
+	});
+
+	assert!(total_protocol_fee > 0);
+
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::set_protocol_fee_beneficiaries(
+			RuntimeOrigin::root(),
+			beneficiaries(vec![
+				(TreasuryAccount::get(), Permill::from_percent(50)),
+				(BENEFICIARY, Permill::from_percent(25)),
+			])
+		));
+
+		let hdx_hub_reserve_before = hdx_hub_reserve();
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), 200, 100, 100 * ONE, 0));
+
+		let treasury_fee = Permill::from_percent(50).mul_floor(total_protocol_fee);
+		let beneficiary_fee = Permill::from_percent(25).mul_floor(total_protocol_fee);
+
+		assert_eq!(AccruedProtocolFees::<Test>::get(TreasuryAccount::get()), treasury_fee);
+		assert_eq!(AccruedProtocolFees::<Test>::get(BENEFICIARY), beneficiary_fee);
+		assert_balance!(TreasuryAccount::get(), LRNA, 0);
+		assert_balance!(BENEFICIARY, LRNA, 0);
+		assert_eq!(
+			hdx_hub_reserve(),
+			hdx_hub_reserve_before + total_protocol_fee - treasury_fee - beneficiary_fee
+		);
+		assert_hub_asset!();
+
+		assert_eq!(
+			BlockProtocolFees::<Test>::get().into_inner(),
+			vec![(TreasuryAccount::get(), treasury_fee), (BENEFICIARY, beneficiary_fee)]
+		);
+	});
+}
+
+#[test]
+fn accrued_protocol_fees_should_be_accumulated_and_emitted_on_finalize() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::set_protocol_fee_beneficiaries(
+			RuntimeOrigin::root(),
+			beneficiaries(vec![(BENEFICIARY, Permill::from_percent(100))])
+		));
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), 200, 100, 100 * ONE, 0));
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), 200, 100, 100 * ONE, 0));
+
+		let accrued = AccruedProtocolFees::<Test>::get(BENEFICIARY);
+		assert!(accrued > 0);
+		assert_eq!(
+			BlockProtocolFees::<Test>::get().into_inner(),
+			vec![(BENEFICIARY, accrued)]
+		);
+
+		Omnipool::on_finalize(1);
+
+		assert!(BlockProtocolFees::<Test>::get().is_empty());
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ProtocolFeesAccrued {
+				beneficiary: BENEFICIARY,
+				amount: accrued,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn sell_should_add_protocol_fee_to_hdx_subpool_when_no_beneficiaries_are_set() {
+	builder().build().execute_with(|| {
+		let hdx_hub_reserve_before = hdx_hub_reserve();
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), 200, 100, 100 * ONE, 0));
+
+		assert!(hdx_hub_reserve() > hdx_hub_reserve_before);
+		assert!(BlockProtocolFees::<Test>::get().is_empty());
+		assert_hub_asset!();
+	});
+}
+
+#[test]
+fn claim_protocol_fees_should_mint_accrued_fees_to_beneficiary() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::set_protocol_fee_beneficiaries(
+			RuntimeOrigin::root(),
+			beneficiaries(vec![(BENEFICIARY, Permill::from_percent(100))])
+		));
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), 200, 100, 100 * ONE, 0));
+
+		let accrued = AccruedProtocolFees::<Test>::get(BENEFICIARY);
+		assert!(accrued > 0);
+
+		assert_ok!(Omnipool::claim_protocol_fees(RuntimeOrigin::signed(BENEFICIARY)));
+
+		assert_balance!(BENEFICIARY, LRNA, accrued);
+		assert_eq!(AccruedProtocolFees::<Test>::get(BENEFICIARY), 0);
+		assert_hub_asset!();
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ProtocolFeesClaimed {
+				beneficiary: BENEFICIARY,
+				amount: accrued,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn claim_protocol_fees_should_fail_when_no_fees_are_accrued() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::claim_protocol_fees(RuntimeOrigin::signed(BENEFICIARY)),
+			Error::<Test>::NoProtocolFeesToClaim
+		);
+	});
+}
+
+#[test]
+fn accrued_protocol_fees_should_stay_claimable_when_beneficiaries_are_replaced() {
+	builder().build().execute_with(|| {
+		assert_ok!(Omnipool::set_protocol_fee_beneficiaries(
+			RuntimeOrigin::root(),
+			beneficiaries(vec![(BENEFICIARY, Permill::from_percent(100))])
+		));
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), 200, 100, 100 * ONE, 0));
+
+		let accrued = AccruedProtocolFees::<Test>::get(BENEFICIARY);
+
+		assert_ok!(Omnipool::set_protocol_fee_beneficiaries(
+			RuntimeOrigin::root(),
+			beneficiaries(vec![])
+		));
+
+		assert_ok!(Omnipool::claim_protocol_fees(RuntimeOrigin::signed(BENEFICIARY)));
+
+		assert_balance!(BENEFICIARY, LRNA, accrued);
+	});
+}
@@ -0,0 +1,110 @@
+use super::*;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+
+fn builder() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+}
+
+#[test]
+fn withdraw_protocol_liquidity_should_transfer_liquidity_to_treasury_when_protocol_owns_shares() {
+	builder().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP2), 0));
+
+		let hub_liquidity = Tokens::free_balance(LRNA, &Omnipool::protocol_account());
+
+		assert_ok!(Omnipool::withdraw_protocol_liquidity(
+			RuntimeOrigin::root(),
+			1_000,
+			1000 * ONE
+		));
+
+		assert_balance!(TreasuryAccount::get(), 1_000, 1000 * ONE);
+		assert_balance!(TreasuryAccount::get(), LRNA, 0);
+		assert_balance!(Omnipool::protocol_account(), LRNA, hub_liquidity - 650 * ONE);
+
+		assert_asset_state!(
+			1_000,
+			AssetReserveState {
+				reserve: 1000 * ONE,
+				hub_reserve: 650 * ONE,
+				shares: 1000 * ONE,
+				protocol_shares: 1000 * ONE,
+				cap: DEFAULT_WEIGHT_CAP,
+				tradable: Tradability::default(),
+			}
+		);
+		assert_hub_asset!();
+
+		frame_system::Pallet::<Test>::assert_last_event(
+			crate::Event::ProtocolLiquidityRemoved {
+				beneficiary: TreasuryAccount::get(),
+				asset_id: 1_000,
+				amount: 1000 * ONE,
+				hub_amount: 0,
+				shares_removed: 1000 * ONE,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn withdraw_protocol_liquidity_should_fail_when_amount_exceeds_protocol_shares() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::withdraw_protocol_liquidity(RuntimeOrigin::root(), 1_000, ONE),
+			Error::<Test>::InsufficientShares
+		);
+
+		assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP2), 0));
+
+		assert_noop!(
+			Omnipool::withdraw_protocol_liquidity(RuntimeOrigin::root(), 1_000, 2000 * ONE + 1),
+			Error::<Test>::InsufficientShares
+		);
+	});
+}
+
+#[test]
+fn withdraw_protocol_liquidity_should_fail_when_amount_is_zero() {
+	builder().build().execute_with(|| {
+		assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP2), 0));
+
+		assert_noop!(
+			Omnipool::withdraw_protocol_liquidity(RuntimeOrigin::root(), 1_000, 0),
+			Error::<Test>::InvalidSharesAmount
+		);
+	});
+}
+
+#[test]
+fn withdraw_protocol_liquidity_should_fail_when_asset_is_not_in_pool() {
+	builder().build().execute_with(|| {
+		assert_noop!(
+			Omnipool::withdraw_protocol_liquidity(RuntimeOrigin::root(), 2_000, ONE),
+			Error::<Test>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn withdraw_protocol_liquidity_should_fail_when_origin_is_not_authority_origin() {
+	builder().build().execute_with(|| {
+		assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP2), 0));
+
+		assert_noop!(
+			Omnipool::withdraw_protocol_liquidity(RuntimeOrigin::signed(LP2), 1_000, ONE),
+			sp_runtime::traits::BadOrigin
+		);
+	});
+}
@@ -0,0 +1,141 @@
+use super::*;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+#[test]
+fn quote_sell_should_match_sell_result() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 100, 2000 * ONE),
+			(LP3, 200, 2000 * ONE),
+			(LP1, 100, 1000 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_asset_fee(Permill::from_percent(10))
+		.with_protocol_fee(Permill::from_percent(20))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let sell_amount = 50 * ONE;
+
+			let quote = Omnipool::quote_sell(100, 200, sell_amount).unwrap();
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, sell_amount, 0));
+
+			assert_eq!(quote.amount_in, sell_amount);
+			assert_eq!(quote.amount_out, Tokens::free_balance(200, &LP1));
+			assert!(quote.asset_fee > Balance::zero());
+			assert!(quote.protocol_fee > Balance::zero());
+
+			let asset_in_state = Omnipool::load_asset_state(100).unwrap();
+			let asset_out_state = Omnipool::load_asset_state(200).unwrap();
+			let expected_price = FixedU128::checked_from_rational(asset_in_state.reserve, asset_in_state.hub_reserve)
+				.unwrap()
+				.checked_mul(&asset_out_state.price().unwrap())
+				.unwrap();
+			assert_eq!(quote.spot_price, expected_price);
+		});
+}
+
+#[test]
+fn quote_buy_should_match_buy_result() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 100, 2000 * ONE),
+			(LP3, 200, 2000 * ONE),
+			(LP1, 100, 1000 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_asset_fee(Permill::from_percent(10))
+		.with_protocol_fee(Permill::from_percent(20))
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let buy_amount = 50 * ONE;
+
+			let quote = Omnipool::quote_buy(200, 100, buy_amount).unwrap();
+
+			assert_ok!(Omnipool::buy(
+				RuntimeOrigin::signed(LP1),
+				200,
+				100,
+				buy_amount,
+				100 * ONE
+			));
+
+			assert_eq!(quote.amount_out, buy_amount);
+			assert_eq!(quote.amount_in, 1000 * ONE - Tokens::free_balance(100, &LP1));
+			assert!(quote.asset_fee > Balance::zero());
+			assert!(quote.protocol_fee > Balance::zero());
+		});
+}
+
+#[test]
+fn quote_sell_hub_asset_should_match_sell_result() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 100, 2000 * ONE),
+			(LP3, LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			let sell_amount = 50 * ONE;
+
+			let quote = Omnipool::quote_sell(LRNA, 100, sell_amount).unwrap();
+
+			assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP3), LRNA, 100, sell_amount, 0));
+
+			assert_eq!(quote.amount_in, sell_amount);
+			assert_eq!(quote.amount_out, Tokens::free_balance(100, &LP3));
+			assert_eq!(quote.protocol_fee, Balance::zero());
+			assert_eq!(
+				quote.spot_price,
+				Omnipool::load_asset_state(100).unwrap().price().unwrap()
+			);
+		});
+}
+
+#[test]
+fn quote_should_fail_when_trading_same_asset() {
+	ExtBuilder::default()
+		.with_registered_asset(100)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::quote_sell(HDX, HDX, 10 * ONE),
+				Error::<Test>::SameAssetTradeNotAllowed
+			);
+			assert_noop!(
+				Omnipool::quote_buy(HDX, HDX, 10 * ONE),
+				Error::<Test>::SameAssetTradeNotAllowed
+			);
+		});
+}
+
+#[test]
+fn quote_should_fail_when_asset_is_not_in_pool() {
+	ExtBuilder::default()
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(Omnipool::quote_sell(HDX, 1234, 10 * ONE), Error::<Test>::AssetNotFound);
+			assert_noop!(Omnipool::quote_buy(1234, HDX, 10 * ONE), Error::<Test>::AssetNotFound);
+		});
+}
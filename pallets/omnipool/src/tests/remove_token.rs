@@ -0,0 +1,260 @@
+use super::*;
+use crate::types::AssetFees;
+use frame_support::assert_noop;
+use pretty_assertions::assert_eq;
+use sp_runtime::Permill;
+
+#[test]
+fn remove_liquidity_should_work_without_fee_and_price_check_when_asset_is_frozen() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+			(LP1, 1_000, 5000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_max_allowed_price_difference(Permill::from_percent(1))
+		.with_min_withdrawal_fee(Permill::from_percent(1))
+		.build()
+		.execute_with(|| {
+			let current_position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+			EXT_PRICE_ADJUSTMENT.with(|v| {
+				*v.borrow_mut() = (3, 100, false);
+			});
+
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				1_000,
+				Tradability::FROZEN
+			));
+
+			assert_ok!(Omnipool::remove_liquidity(
+				RuntimeOrigin::signed(LP1),
+				current_position_id,
+				200 * ONE
+			));
+
+			assert_balance!(LP1, 1_000, 4800 * ONE);
+			assert_hub_asset!();
+		});
+}
+
+#[test]
+fn settle_position_should_remove_all_liquidity_to_owner_when_asset_is_frozen() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+			(LP1, 1_000, 5000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			let current_position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				1_000,
+				Tradability::FROZEN
+			));
+
+			assert_ok!(Omnipool::settle_position(RuntimeOrigin::root(), current_position_id));
+
+			assert_balance!(LP1, 1_000, 5000 * ONE);
+			assert!(Positions::<Test>::get(current_position_id).is_none());
+			assert_hub_asset!();
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::PositionSettled {
+					position_id: current_position_id,
+					owner: LP1,
+					asset_id: 1_000,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn settle_position_should_fail_when_asset_is_not_frozen() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				1_000,
+				Tradability::SELL | Tradability::BUY
+			));
+
+			assert_noop!(
+				Omnipool::settle_position(RuntimeOrigin::root(), 0),
+				Error::<Test>::AssetNotFrozen
+			);
+		});
+}
+
+#[test]
+fn settle_position_should_fail_when_origin_is_not_authority_origin() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::settle_position(RuntimeOrigin::signed(LP2), 0),
+				sp_runtime::traits::BadOrigin
+			);
+		});
+}
+
+#[test]
+fn remove_token_should_transfer_remaining_liquidity_to_treasury_when_all_positions_are_settled() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+			(LP1, 1_000, 5000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			let current_position_id = <NextPositionId<Test>>::get();
+			assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 1_000, 400 * ONE));
+
+			assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP2), 0));
+
+			assert_ok!(Omnipool::set_asset_fees(
+				RuntimeOrigin::root(),
+				1_000,
+				Some(AssetFees::default())
+			));
+			assert_ok!(Omnipool::set_asset_max_price_difference(
+				RuntimeOrigin::root(),
+				1_000,
+				Some(Permill::from_percent(5))
+			));
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				1_000,
+				Tradability::FROZEN
+			));
+
+			assert_ok!(Omnipool::settle_position(RuntimeOrigin::root(), current_position_id));
+
+			let asset_state = Omnipool::load_asset_state(1_000).unwrap();
+			assert_eq!(asset_state.reserve, 2000 * ONE);
+			assert_eq!(asset_state.hub_reserve, 1300 * ONE);
+
+			assert_ok!(Omnipool::remove_token(RuntimeOrigin::root(), 1_000));
+
+			assert_balance!(TreasuryAccount::get(), 1_000, 2000 * ONE);
+			assert_balance!(TreasuryAccount::get(), LRNA, 1300 * ONE);
+			assert_balance!(Omnipool::protocol_account(), 1_000, 0);
+
+			assert!(Assets::<Test>::get(1_000).is_none());
+			assert!(AssetFeeOverrides::<Test>::get(1_000).is_none());
+			assert!(AssetMaxPriceDifference::<Test>::get(1_000).is_none());
+			assert_hub_asset!();
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				crate::Event::TokenRemoved {
+					asset_id: 1_000,
+					amount: 2000 * ONE,
+					hub_withdrawn: 1300 * ONE,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn remove_token_should_fail_when_lp_shares_remain() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::set_asset_tradable_state(
+				RuntimeOrigin::root(),
+				1_000,
+				Tradability::FROZEN
+			));
+
+			assert_noop!(
+				Omnipool::remove_token(RuntimeOrigin::root(), 1_000),
+				Error::<Test>::SharesRemaining
+			);
+		});
+}
+
+#[test]
+fn remove_token_should_fail_when_asset_is_not_frozen() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 1_000, 2000 * ONE),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(1_000, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.build()
+		.execute_with(|| {
+			assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP2), 0));
+
+			assert_noop!(
+				Omnipool::remove_token(RuntimeOrigin::root(), 1_000),
+				Error::<Test>::AssetNotFrozen
+			);
+		});
+}
+
+#[test]
+fn remove_token_should_fail_when_asset_is_native_or_stable_asset() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+		])
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Omnipool::remove_token(RuntimeOrigin::root(), HDX),
+				Error::<Test>::NotAllowed
+			);
+			assert_noop!(
+				Omnipool::remove_token(RuntimeOrigin::root(), DAI),
+				Error::<Test>::NotAllowed
+			);
+		});
+}
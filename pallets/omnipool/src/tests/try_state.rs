@@ -0,0 +1,120 @@
+use super::*;
+use frame_support::traits::tokens::nonfungibles::Mutate;
+use pretty_assertions::assert_eq;
+
+fn builder() -> ExtBuilder {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![
+			(Omnipool::protocol_account(), DAI, 1000 * ONE),
+			(Omnipool::protocol_account(), HDX, NATIVE_AMOUNT),
+			(LP2, 100, 2000 * ONE),
+			(LP3, 200, 2000 * ONE),
+			(LP1, 100, 1000 * ONE),
+			(LP1, LRNA, 100 * ONE),
+		])
+		.with_registered_asset(100)
+		.with_registered_asset(200)
+		.with_initial_pool(FixedU128::from_float(0.5), FixedU128::from(1))
+		.with_token(100, FixedU128::from_float(0.65), LP2, 2000 * ONE)
+		.with_token(200, FixedU128::from_float(0.65), LP3, 2000 * ONE)
+}
+
+#[test]
+fn try_state_should_pass_when_pool_is_modified_by_liquidity_and_trades() {
+	builder().build().execute_with(|| {
+		assert_ok!(Omnipool::do_try_state());
+
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 100, 400 * ONE));
+		assert_ok!(Omnipool::do_try_state());
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), 100, 200, 50 * ONE, 0));
+		assert_ok!(Omnipool::do_try_state());
+
+		assert_ok!(Omnipool::buy(RuntimeOrigin::signed(LP1), 200, 100, 10 * ONE, 100 * ONE));
+		assert_ok!(Omnipool::do_try_state());
+
+		assert_ok!(Omnipool::sell(RuntimeOrigin::signed(LP1), LRNA, 100, 10 * ONE, 0));
+		assert_ok!(Omnipool::do_try_state());
+
+		assert_ok!(Omnipool::remove_liquidity(
+			RuntimeOrigin::signed(LP1),
+			position_id,
+			200 * ONE
+		));
+		assert_ok!(Omnipool::do_try_state());
+
+		assert_ok!(Omnipool::sacrifice_position(RuntimeOrigin::signed(LP1), position_id));
+		assert_ok!(Omnipool::do_try_state());
+	});
+}
+
+#[test]
+fn try_state_should_fail_when_hub_asset_balance_does_not_match_hub_reserves() {
+	builder().build().execute_with(|| {
+		assert_ok!(Tokens::deposit(LRNA, &Omnipool::protocol_account(), ONE));
+
+		assert_eq!(
+			Omnipool::do_try_state(),
+			Err("Sum of hub reserves does not match hub asset balance of protocol account")
+		);
+	});
+}
+
+#[test]
+fn try_state_should_fail_when_asset_with_shares_has_no_reserve() {
+	builder().build().execute_with(|| {
+		let reserve = Tokens::free_balance(100, &Omnipool::protocol_account());
+		assert_ok!(Tokens::withdraw(100, &Omnipool::protocol_account(), reserve));
+
+		assert_eq!(
+			Omnipool::do_try_state(),
+			Err("Asset with shares has no reserve in protocol account")
+		);
+	});
+}
+
+#[test]
+fn try_state_should_fail_when_protocol_shares_exceed_shares() {
+	builder().build().execute_with(|| {
+		Assets::<Test>::mutate(100, |maybe_state| {
+			let state = maybe_state.as_mut().unwrap();
+			state.protocol_shares = state.shares + 1;
+		});
+
+		assert_eq!(
+			Omnipool::do_try_state(),
+			Err("Asset protocol shares exceed asset shares")
+		);
+	});
+}
+
+#[test]
+fn try_state_should_fail_when_position_asset_is_not_in_pool() {
+	builder().build().execute_with(|| {
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 100, 400 * ONE));
+
+		Positions::<Test>::mutate(position_id, |maybe_position| {
+			maybe_position.as_mut().unwrap().asset_id = 1_000;
+		});
+
+		assert_eq!(Omnipool::do_try_state(), Err("Position of asset which is not in pool"));
+	});
+}
+
+#[test]
+fn try_state_should_fail_when_position_has_no_nft() {
+	builder().build().execute_with(|| {
+		let position_id = <NextPositionId<Test>>::get();
+		assert_ok!(Omnipool::add_liquidity(RuntimeOrigin::signed(LP1), 100, 400 * ONE));
+
+		assert_ok!(<DummyNFT as Mutate<AccountId>>::burn(
+			&PosiitionCollectionId::get(),
+			&position_id,
+			None
+		));
+
+		assert_eq!(Omnipool::do_try_state(), Err("Position without NFT instance"));
+	});
+}
@@ -5,6 +5,7 @@ use frame_support::traits::Contains;
 use frame_support::weights::Weight;
 use hydra_dx_math::ema::EmaPrice;
 use hydra_dx_math::omnipool::types::AssetStateChange;
+use orml_traits::GetByKey;
 use sp_runtime::traits::{CheckedAdd, CheckedMul, Get, Saturating};
 use sp_runtime::{DispatchError, FixedPointNumber, FixedU128, Permill};
 
@@ -127,15 +128,27 @@ where
 	}
 }
 
-pub struct EnsurePriceWithin<AccountId, AssetId, ExternalOracle, MaxAllowed, WhitelistedAccounts>(
-	sp_std::marker::PhantomData<(AccountId, AssetId, ExternalOracle, MaxAllowed, WhitelistedAccounts)>,
+/// Ensures that current price is within max allowed difference from the price provided by `ExternalOracle`.
+///
+/// Max allowed difference is taken from `AssetMaxAllowed` for `asset_b`. `MaxAllowed` is used if none is set.
+pub struct EnsurePriceWithin<AccountId, AssetId, ExternalOracle, MaxAllowed, AssetMaxAllowed, WhitelistedAccounts>(
+	sp_std::marker::PhantomData<(
+		AccountId,
+		AssetId,
+		ExternalOracle,
+		MaxAllowed,
+		AssetMaxAllowed,
+		WhitelistedAccounts,
+	)>,
 );
 
-impl<AccountId, AssetId, ExternalOracle, MaxAllowed, WhitelistedAccounts> ShouldAllow<AccountId, AssetId, EmaPrice>
-	for EnsurePriceWithin<AccountId, AssetId, ExternalOracle, MaxAllowed, WhitelistedAccounts>
+impl<AccountId, AssetId, ExternalOracle, MaxAllowed, AssetMaxAllowed, WhitelistedAccounts>
+	ShouldAllow<AccountId, AssetId, EmaPrice>
+	for EnsurePriceWithin<AccountId, AssetId, ExternalOracle, MaxAllowed, AssetMaxAllowed, WhitelistedAccounts>
 where
 	ExternalOracle: ExternalPriceProvider<AssetId, EmaPrice>,
 	MaxAllowed: Get<Permill>,
+	AssetMaxAllowed: GetByKey<AssetId, Option<Permill>>,
 	WhitelistedAccounts: Contains<AccountId>,
 {
 	fn ensure_price(who: &AccountId, asset_a: AssetId, asset_b: AssetId, current_price: EmaPrice) -> Result<(), ()> {
@@ -143,7 +156,7 @@ where
 			return Ok(());
 		}
 
-		let max_allowed = FixedU128::from(MaxAllowed::get());
+		let max_allowed = FixedU128::from(AssetMaxAllowed::get(&asset_b).unwrap_or_else(MaxAllowed::get));
 
 		let oracle_price = ExternalOracle::get_price(asset_a, asset_b).map_err(|_| ())?;
 		let external_price = FixedU128::checked_from_rational(oracle_price.n, oracle_price.d).ok_or(())?;
@@ -2,6 +2,8 @@ use super::*;
 use codec::MaxEncodedLen;
 use frame_support::pallet_prelude::*;
 use hydra_dx_math::omnipool::types::{AssetReserveState as MathReserveState, AssetStateChange, BalanceUpdate};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{FixedPointNumber, FixedU128};
 use sp_std::ops::{Add, Sub};
 
@@ -82,6 +84,8 @@ where
 
 /// Position in Omnipool represents a moment when LP provided liquidity of an asset at that moment’s price.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct Position<Balance, AssetId> {
 	/// Provided Asset
 	pub asset_id: AssetId,
@@ -311,3 +315,130 @@ where
 		})
 	}
 }
+
+/// Result of a simulated sell or buy trade.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TradeQuote<Balance> {
+	/// Amount of asset in sold to the pool
+	pub amount_in: Balance,
+	/// Amount of asset out received from the pool
+	pub amount_out: Balance,
+	/// Asset fee amount, denominated in asset out
+	pub asset_fee: Balance,
+	/// Protocol fee amount, denominated in hub asset
+	pub protocol_fee: Balance,
+	/// Spot price of asset out denominated in asset in after the trade
+	pub spot_price: Price,
+}
+
+/// Amounts an LP would receive if all liquidity of a position was removed at current pool state.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PositionValue<AssetId, Balance> {
+	/// Asset of the position
+	pub asset_id: AssetId,
+	/// Amount of asset received, withdrawal fee deducted
+	pub amount: Balance,
+	/// Amount of hub asset received, withdrawal fee deducted
+	pub hub_amount: Balance,
+	/// Current withdrawal fee, including early removal fee
+	pub withdrawal_fee: FixedU128,
+	/// Part of withdrawal fee charged because minimum holding period of the position has not passed yet
+	pub early_removal_fee: FixedU128,
+	/// Whether liquidity can be removed now. False if minimum holding period has not passed and early removal
+	/// is not allowed - amounts are then calculated without early removal fee.
+	pub withdrawable: bool,
+	/// Impermanent loss between the entry price of the position and the current spot price, excluding withdrawal fee.
+	/// Expressed as a fraction of the value of holding the provided amount.
+	pub impermanent_loss: FixedU128,
+}
+
+/// Omnipool position of an account, held either directly or through a liquidity mining deposit.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct OwnedPosition<PositionItemId, AssetId, Balance> {
+	/// Id of the position
+	pub position_id: PositionItemId,
+	/// Id of the liquidity mining deposit which holds the position, if the position is deposited
+	pub deposit_id: Option<PositionItemId>,
+	/// Position details
+	pub position: Position<Balance, AssetId>,
+}
+
+/// Asset fee and protocol fee set by governance must be lower than this value.
+pub const MAX_FEE: Permill = Permill::from_percent(50);
+
+/// Fees of an asset set by governance.
+#[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct AssetFees {
+	/// Fee charged on amount of asset bought from the pool.
+	pub asset_fee: Permill,
+	/// Fee charged on hub asset amount when asset is sold to the pool.
+	pub protocol_fee: Permill,
+}
+
+impl AssetFees {
+	pub(crate) fn is_valid(&self) -> bool {
+		self.asset_fee < MAX_FEE && self.protocol_fee < MAX_FEE
+	}
+}
+
+/// Range within which dynamic fees of an asset can move.
+#[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct FeeBounds {
+	/// Minimum asset fee. Asset fee decays back to this value.
+	pub min_asset_fee: Permill,
+	/// Maximum asset fee.
+	pub max_asset_fee: Permill,
+	/// Minimum protocol fee. Protocol fee decays back to this value.
+	pub min_protocol_fee: Permill,
+	/// Maximum protocol fee.
+	pub max_protocol_fee: Permill,
+}
+
+impl FeeBounds {
+	pub(crate) fn is_valid(&self) -> bool {
+		self.min_asset_fee <= self.max_asset_fee
+			&& self.min_protocol_fee <= self.max_protocol_fee
+			&& self.max_asset_fee < MAX_FEE
+			&& self.max_protocol_fee < MAX_FEE
+	}
+}
+
+/// Dynamic fees of an asset at the time of last update.
+#[derive(Clone, Copy, Default, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct DynamicFee<BlockNumber> {
+	/// Fee charged on amount of asset bought from the pool.
+	pub asset_fee: Permill,
+	/// Fee charged on hub asset amount when asset is sold to the pool.
+	pub protocol_fee: Permill,
+	/// Block number of last update.
+	pub updated_at: BlockNumber,
+}
+
+/// Permissionless listing of an asset which can still be rejected by governance.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Listing<AccountId, PositionItemId, BlockNumber> {
+	/// Account which proposed the asset and provided initial liquidity
+	pub proposer: AccountId,
+	/// Amount of native asset reserved from proposer
+	pub bond: Balance,
+	/// Position created for initial liquidity of the asset
+	pub position_id: PositionItemId,
+	/// Last block in which the listing can be rejected
+	pub challenge_end: BlockNumber,
+}
+
+/// Minimum number of blocks liquidity added to Omnipool must be held before it can be freely removed.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct MinHoldingPeriod<BlockNumber> {
+	/// Number of blocks since position creation
+	pub blocks: BlockNumber,
+	/// Additional withdrawal fee left in the pool when liquidity is removed earlier.
+	/// If not set, removing liquidity earlier is not allowed.
+	pub early_removal_fee: Option<Permill>,
+}
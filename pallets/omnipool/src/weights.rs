@@ -59,6 +59,22 @@ pub trait WeightInfo {
 	fn refund_refused_asset() -> Weight;
 	fn sacrifice_position() -> Weight;
 	fn set_asset_weight_cap() -> Weight;
+	fn set_asset_fee_bounds() -> Weight;
+	fn set_asset_fees() -> Weight;
+	fn set_asset_max_price_difference() -> Weight;
+	fn withdraw_protocol_liquidity() -> Weight;
+	fn reduce_imbalance() -> Weight;
+	fn set_protocol_fee_beneficiaries() -> Weight;
+	fn propose_token() -> Weight;
+	fn approve_listing() -> Weight;
+	fn reject_listing() -> Weight;
+	fn set_asset_min_holding_period() -> Weight;
+	fn settle_position() -> Weight;
+	fn remove_token() -> Weight;
+	fn merge_positions(n: u32) -> Weight;
+	fn split_position() -> Weight;
+	fn add_liquidity_batch(n: u32) -> Weight;
+	fn claim_protocol_fees() -> Weight;
 }
 
 /// Weights for pallet_omnipool using the hydraDX node and recommended hardware.
@@ -86,14 +102,14 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(15 as u64))
 	}
 	fn sell() -> Weight {
-		Weight::from_ref_time(121_641_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(14 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+		Weight::from_ref_time(148_641_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(26 as u64))
+			.saturating_add(T::DbWeight::get().writes(19 as u64))
 	}
 	fn buy() -> Weight {
-		Weight::from_ref_time(121_521_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(14 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+		Weight::from_ref_time(148_521_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(26 as u64))
+			.saturating_add(T::DbWeight::get().writes(19 as u64))
 	}
 	fn set_asset_tradable_state() -> Weight {
 		Weight::from_ref_time(21_030_000 as u64)
@@ -115,6 +131,91 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	fn set_asset_fee_bounds() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn set_asset_fees() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_asset_max_price_difference() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn withdraw_protocol_liquidity() -> Weight {
+		Weight::from_ref_time(175_741_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
+	}
+	fn reduce_imbalance() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn set_protocol_fee_beneficiaries() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn propose_token() -> Weight {
+		Weight::from_ref_time(153_310_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(17 as u64))
+			.saturating_add(T::DbWeight::get().writes(13 as u64))
+	}
+	fn approve_listing() -> Weight {
+		Weight::from_ref_time(41_030_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn reject_listing() -> Weight {
+		Weight::from_ref_time(128_351_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(14 as u64))
+	}
+	fn set_asset_min_holding_period() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn settle_position() -> Weight {
+		Weight::from_ref_time(185_741_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(15 as u64))
+	}
+	fn remove_token() -> Weight {
+		Weight::from_ref_time(78_351_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	fn merge_positions(n: u32) -> Weight {
+		Weight::from_ref_time(28_372_000 as u64) // Standard Error: 12_000
+			.saturating_add(Weight::from_ref_time(31_146_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
+	}
+	fn split_position() -> Weight {
+		Weight::from_ref_time(58_254_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	fn add_liquidity_batch(n: u32) -> Weight {
+		Weight::from_ref_time(18_627_000 as u64) // Standard Error: 41_000
+			.saturating_add(Weight::from_ref_time(98_412_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((11 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((10 as u64).saturating_mul(n as u64)))
+	}
+	fn claim_protocol_fees() -> Weight {
+		Weight::from_ref_time(31_420_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -140,14 +241,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(15 as u64))
 	}
 	fn sell() -> Weight {
-		Weight::from_ref_time(121_641_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(14 as u64))
-			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+		Weight::from_ref_time(148_641_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(26 as u64))
+			.saturating_add(RocksDbWeight::get().writes(19 as u64))
 	}
 	fn buy() -> Weight {
-		Weight::from_ref_time(121_521_000 as u64)
-			.saturating_add(RocksDbWeight::get().reads(14 as u64))
-			.saturating_add(RocksDbWeight::get().writes(10 as u64))
+		Weight::from_ref_time(148_521_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(26 as u64))
+			.saturating_add(RocksDbWeight::get().writes(19 as u64))
 	}
 	fn set_asset_tradable_state() -> Weight {
 		Weight::from_ref_time(21_030_000 as u64)
@@ -169,4 +270,89 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as u64))
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
+	fn set_asset_fee_bounds() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn set_asset_fees() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn set_asset_max_price_difference() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn withdraw_protocol_liquidity() -> Weight {
+		Weight::from_ref_time(175_741_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(18 as u64))
+			.saturating_add(RocksDbWeight::get().writes(15 as u64))
+	}
+	fn reduce_imbalance() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn set_protocol_fee_beneficiaries() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn propose_token() -> Weight {
+		Weight::from_ref_time(153_310_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(17 as u64))
+			.saturating_add(RocksDbWeight::get().writes(13 as u64))
+	}
+	fn approve_listing() -> Weight {
+		Weight::from_ref_time(41_030_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn reject_listing() -> Weight {
+		Weight::from_ref_time(128_351_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(11 as u64))
+			.saturating_add(RocksDbWeight::get().writes(14 as u64))
+	}
+	fn set_asset_min_holding_period() -> Weight {
+		Weight::from_ref_time(20_790_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+	}
+	fn settle_position() -> Weight {
+		Weight::from_ref_time(185_741_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(20 as u64))
+			.saturating_add(RocksDbWeight::get().writes(15 as u64))
+	}
+	fn remove_token() -> Weight {
+		Weight::from_ref_time(78_351_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(6 as u64))
+			.saturating_add(RocksDbWeight::get().writes(8 as u64))
+	}
+	fn merge_positions(n: u32) -> Weight {
+		Weight::from_ref_time(28_372_000 as u64) // Standard Error: 12_000
+			.saturating_add(Weight::from_ref_time(31_146_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
+	}
+	fn split_position() -> Weight {
+		Weight::from_ref_time(58_254_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(5 as u64))
+			.saturating_add(RocksDbWeight::get().writes(7 as u64))
+	}
+	fn add_liquidity_batch(n: u32) -> Weight {
+		Weight::from_ref_time(18_627_000 as u64) // Standard Error: 41_000
+			.saturating_add(Weight::from_ref_time(98_412_000 as u64).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().reads((11 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((10 as u64).saturating_mul(n as u64)))
+	}
+	fn claim_protocol_fees() -> Weight {
+		Weight::from_ref_time(31_420_000 as u64)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
 }
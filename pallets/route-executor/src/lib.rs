@@ -0,0 +1,689 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Route executor pallet
+//!
+//! ## Overview
+//!
+//! Executes a route of trades in a single transaction.
+//!
+//! Each trade of a route swaps `asset_in` for `asset_out` in the pool (Omnipool). A trade can optionally
+//! reference an order of the order book (OTC). The order is filled first if its price is better than
+//! the spot price of the pool, and the rest of the trade is executed in the pool.
+//!
+//! Only one overall limit is applied - minimum amount out for sell and maximum amount in for buy.
+//! Intermediate trades are executed without limits (zero minimum amount out for sell and unlimited
+//! amount in for buy), so the overall limit is the only protection of the trader against price movements.
+//!
+//! If no route is provided, the route is discovered by quoting the trades in the pool. All routes of up to
+//! `MaxRouteLength` trades going through `IntermediateAssets` are quoted and the one with the highest amount out
+//! (sell) or the lowest amount in (buy) is used. Of routes with the same quote, the shortest one is used.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable functions
+//!
+//! * `sell` - Sells given amount of asset in along the route and ensures that at least `min_amount_out` is received.
+//! * `buy` - Buys given amount of asset out along the route and ensures that at most `max_amount_in` is spent.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::*;
+use frame_support::storage::{with_transaction, TransactionOutcome};
+use frame_support::{ensure, transactional};
+use frame_system::ensure_signed;
+use frame_system::pallet_prelude::OriginFor;
+use hydradx_traits::pools::SpotPriceProvider;
+use orml_traits::MultiCurrency;
+use scale_info::TypeInfo;
+use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_runtime::traits::Zero;
+use sp_runtime::{ArithmeticError, DispatchError, FixedPointNumber, FixedU128, Rounding, RuntimeDebug};
+use sp_std::vec::Vec;
+
+#[cfg(test)]
+mod tests;
+
+pub mod traits;
+
+use crate::traits::{OrderBook, OrderInfo, TradeExecution};
+
+// Re-export pallet items so that they can be accessed from the crate namespace.
+pub use pallet::*;
+
+pub type Balance = u128;
+
+/// Single trade of a route.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, PartialEq, Eq)]
+pub struct Trade<AssetId, OrderId> {
+	/// Asset sold in this trade.
+	pub asset_in: AssetId,
+	/// Asset bought in this trade.
+	pub asset_out: AssetId,
+	/// Order to fill before trading in the pool, if its price is better.
+	pub order: Option<OrderId>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(crate) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Asset identifier.
+		type AssetId: Member + Parameter + Copy + MaybeSerializeDeserialize + MaxEncodedLen + TypeInfo;
+
+		/// Order identifier of the order book.
+		type OrderId: Member + Parameter + Copy + MaxEncodedLen + TypeInfo;
+
+		/// Multi currency mechanism, used to determine amounts received and spent by a trade.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = Self::AssetId, Balance = Balance>;
+
+		/// Pool used to execute trades.
+		type AMM: TradeExecution<OriginFor<Self>, Self::AssetId, Balance>;
+
+		/// Order book whose orders can be filled along the route.
+		type OrderBook: OrderBook<OriginFor<Self>, Self::OrderId, Self::AssetId, Balance>;
+
+		/// Spot price provider of the pool. Used to compare prices of orders with the pool.
+		type SpotPriceProvider: SpotPriceProvider<Self::AssetId, Price = FixedU128>;
+
+		/// Maximum number of trades in a route.
+		#[pallet::constant]
+		type MaxRouteLength: Get<u32>;
+
+		/// Assets which can be traded through in a discovered route.
+		type IntermediateAssets: Get<Vec<Self::AssetId>>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Route was executed.
+		RouteExecuted {
+			who: T::AccountId,
+			asset_in: T::AssetId,
+			asset_out: T::AssetId,
+			amount_in: Balance,
+			amount_out: Balance,
+			trades: u32,
+		},
+	}
+
+	#[pallet::error]
+	#[cfg_attr(test, derive(PartialEq, Eq))]
+	pub enum Error<T> {
+		/// Route is empty or its trades do not connect asset in with asset out.
+		InvalidRoute,
+		/// No route found between asset in and asset out.
+		RouteNotFound,
+		/// Order referenced by a trade does not exist.
+		OrderNotFound,
+		/// Assets of the order do not match the assets of the trade.
+		OrderAssetMismatch,
+		/// Minimum amount out was not reached.
+		MinAmountOutNotReached,
+		/// Maximum amount in was exceeded.
+		MaxAmountInExceeded,
+		/// Trade amount is zero.
+		ZeroAmount,
+		/// Trade of a buy route spent more of an intermediate asset than the previous trades bought.
+		InsufficientIntermediateAmount,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Sell `amount_in` of `asset_in` along the route and receive at least `min_amount_out` of `asset_out`.
+		///
+		/// If `route` is empty, the route with the highest quoted amount out is discovered.
+		///
+		/// `min_amount_out` is the only limit of the route. Intermediate trades are executed without limits.
+		///
+		/// Parameters:
+		/// - `origin`: signed origin of the trader
+		/// - `asset_in`: ID of asset sold
+		/// - `asset_out`: ID of asset bought
+		/// - `amount_in`: Amount of asset in to sell
+		/// - `min_amount_out`: Minimum amount of asset out to receive
+		/// - `route`: Trades to execute
+		///
+		/// Emits `RouteExecuted` event when successful.
+		///
+		#[pallet::call_index(0)]
+		#[pallet::weight(Pallet::<T>::route_weight(route, T::AMM::sell_weight()))]
+		#[transactional]
+		pub fn sell(
+			origin: OriginFor<T>,
+			asset_in: T::AssetId,
+			asset_out: T::AssetId,
+			amount_in: Balance,
+			min_amount_out: Balance,
+			route: BoundedVec<Trade<T::AssetId, T::OrderId>, T::MaxRouteLength>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+
+			ensure!(!amount_in.is_zero(), Error::<T>::ZeroAmount);
+
+			let route = if route.is_empty() {
+				Self::discover_sell_route(asset_in, asset_out, amount_in)?
+			} else {
+				Self::ensure_route(asset_in, asset_out, route.into_inner())?
+			};
+
+			let balance_before = T::Currency::free_balance(asset_out, &who);
+
+			let mut amount = amount_in;
+			for trade in route.iter() {
+				amount = Self::execute_sell_trade(origin.clone(), &who, trade, amount)?;
+			}
+
+			let amount_out = T::Currency::free_balance(asset_out, &who)
+				.checked_sub(balance_before)
+				.ok_or(ArithmeticError::Underflow)?;
+
+			ensure!(amount_out >= min_amount_out, Error::<T>::MinAmountOutNotReached);
+
+			Self::deposit_event(Event::RouteExecuted {
+				who,
+				asset_in,
+				asset_out,
+				amount_in,
+				amount_out,
+				trades: route.len() as u32,
+			});
+
+			Ok(())
+		}
+
+		/// Buy `amount_out` of `asset_out` along the route and spend at most `max_amount_in` of `asset_in`.
+		///
+		/// Amounts of intermediate assets are calculated backwards from `amount_out` before the route is executed.
+		/// Trades touching the same pool move each other's prices, so a trade may need less of an intermediate asset
+		/// than the previous trade bought. What is left of intermediate assets is sold back along the route,
+		/// so that only `asset_in` and `asset_out` balances of the trader change.
+		///
+		/// If `route` is empty, the route with the lowest quoted amount in is discovered.
+		///
+		/// `max_amount_in` is the only limit of the route. Intermediate trades are executed without limits.
+		///
+		/// Parameters:
+		/// - `origin`: signed origin of the trader
+		/// - `asset_in`: ID of asset sold
+		/// - `asset_out`: ID of asset bought
+		/// - `amount_out`: Amount of asset out to buy
+		/// - `max_amount_in`: Maximum amount of asset in to spend
+		/// - `route`: Trades to execute
+		///
+		/// Emits `RouteExecuted` event when successful.
+		///
+		#[pallet::call_index(1)]
+		#[pallet::weight(Pallet::<T>::route_weight(route, T::AMM::buy_weight().saturating_add(T::AMM::sell_weight())))]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			asset_in: T::AssetId,
+			asset_out: T::AssetId,
+			amount_out: Balance,
+			max_amount_in: Balance,
+			route: BoundedVec<Trade<T::AssetId, T::OrderId>, T::MaxRouteLength>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin.clone())?;
+
+			ensure!(!amount_out.is_zero(), Error::<T>::ZeroAmount);
+
+			let route = if route.is_empty() {
+				Self::discover_buy_route(asset_in, asset_out, amount_out)?
+			} else {
+				Self::ensure_route(asset_in, asset_out, route.into_inner())?
+			};
+
+			// amounts[i] is the amount bought by trade i
+			let (_, amounts) = Self::calculate_buy_amounts(&route, amount_out)?;
+
+			let balance_before = T::Currency::free_balance(asset_in, &who);
+
+			let mut intermediate_balances: Vec<(T::AssetId, Balance)> = Vec::new();
+			for trade in route.iter().rev().skip(1) {
+				let asset = trade.asset_out;
+				if asset != asset_in
+					&& asset != asset_out
+					&& !intermediate_balances
+						.iter()
+						.any(|(intermediate, _)| *intermediate == asset)
+				{
+					intermediate_balances.push((asset, T::Currency::free_balance(asset, &who)));
+				}
+			}
+
+			for (trade, amount) in route.iter().zip(amounts) {
+				Self::execute_buy_trade(origin.clone(), &who, trade, amount)?;
+			}
+
+			Self::sell_back_intermediate_leftovers(origin, &who, &route, &intermediate_balances)?;
+
+			let amount_in = balance_before
+				.checked_sub(T::Currency::free_balance(asset_in, &who))
+				.ok_or(ArithmeticError::Underflow)?;
+
+			ensure!(amount_in <= max_amount_in, Error::<T>::MaxAmountInExceeded);
+
+			Self::deposit_event(Event::RouteExecuted {
+				who,
+				asset_in,
+				asset_out,
+				amount_in,
+				amount_out,
+				trades: route.len() as u32,
+			});
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Find the route selling `amount_in` of `asset_in` for the highest quoted amount of `asset_out`.
+	///
+	/// All routes of up to `MaxRouteLength` trades going through `IntermediateAssets` are quoted.
+	/// Of routes with the same quote, the shortest one is returned.
+	pub fn discover_sell_route(
+		asset_in: T::AssetId,
+		asset_out: T::AssetId,
+		amount_in: Balance,
+	) -> Result<Vec<Trade<T::AssetId, T::OrderId>>, DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::InvalidRoute);
+
+		let mut best: Option<(Vec<Trade<T::AssetId, T::OrderId>>, Balance)> = None;
+		for route in Self::candidate_routes(asset_in, asset_out) {
+			let amount_out = match Self::calculate_sell_amount(&route, amount_in) {
+				Ok(amount_out) => amount_out,
+				Err(_) => continue,
+			};
+			if best
+				.as_ref()
+				.map_or(true, |(_, best_amount_out)| amount_out > *best_amount_out)
+			{
+				best = Some((route, amount_out));
+			}
+		}
+
+		best.map(|(route, _)| route)
+			.ok_or_else(|| Error::<T>::RouteNotFound.into())
+	}
+
+	/// Find the route buying `amount_out` of `asset_out` for the lowest quoted amount of `asset_in`.
+	///
+	/// All routes of up to `MaxRouteLength` trades going through `IntermediateAssets` are quoted.
+	/// Of routes with the same quote, the shortest one is returned.
+	pub fn discover_buy_route(
+		asset_in: T::AssetId,
+		asset_out: T::AssetId,
+		amount_out: Balance,
+	) -> Result<Vec<Trade<T::AssetId, T::OrderId>>, DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::InvalidRoute);
+
+		let mut best: Option<(Vec<Trade<T::AssetId, T::OrderId>>, Balance)> = None;
+		for route in Self::candidate_routes(asset_in, asset_out) {
+			let amount_in = match Self::calculate_buy_amounts(&route, amount_out) {
+				Ok((amount_in, _)) => amount_in,
+				Err(_) => continue,
+			};
+			if best
+				.as_ref()
+				.map_or(true, |(_, best_amount_in)| amount_in < *best_amount_in)
+			{
+				best = Some((route, amount_in));
+			}
+		}
+
+		best.map(|(route, _)| route)
+			.ok_or_else(|| Error::<T>::RouteNotFound.into())
+	}
+
+	/// All routes between `asset_in` and `asset_out` of up to `MaxRouteLength` trades going through
+	/// distinct `IntermediateAssets`, from the shortest to the longest.
+	fn candidate_routes(asset_in: T::AssetId, asset_out: T::AssetId) -> Vec<Vec<Trade<T::AssetId, T::OrderId>>> {
+		let intermediates: Vec<T::AssetId> = T::IntermediateAssets::get()
+			.into_iter()
+			.filter(|asset| *asset != asset_in && *asset != asset_out)
+			.collect();
+
+		let mut routes = Vec::new();
+		// intermediate assets of routes with the same number of trades
+		let mut paths: Vec<Vec<T::AssetId>> = sp_std::vec![Vec::new()];
+		for _ in 0..T::MaxRouteLength::get() {
+			let mut next_paths = Vec::new();
+			for path in paths.iter() {
+				let mut assets = sp_std::vec![asset_in];
+				assets.extend(path.iter().copied());
+				assets.push(asset_out);
+				routes.push(
+					assets
+						.windows(2)
+						.map(|pair| Trade {
+							asset_in: pair[0],
+							asset_out: pair[1],
+							order: None,
+						})
+						.collect(),
+				);
+
+				for asset in intermediates.iter().filter(|asset| !path.contains(asset)) {
+					let mut next = path.clone();
+					next.push(*asset);
+					next_paths.push(next);
+				}
+			}
+			paths = next_paths;
+		}
+
+		routes
+	}
+
+	/// Number of trades quoted when discovering a route.
+	fn candidate_trades_count() -> u64 {
+		let intermediates = T::IntermediateAssets::get().len() as u64;
+
+		// routes with `i` intermediate assets are the ordered selections of `i` of them
+		let mut routes: u64 = 1;
+		let mut trades: u64 = 0;
+		for i in 0..T::MaxRouteLength::get() as u64 {
+			trades = trades.saturating_add(routes.saturating_mul(i.saturating_add(1)));
+			routes = routes.saturating_mul(intermediates.saturating_sub(i));
+		}
+
+		trades
+	}
+
+	/// Weight of a route.
+	///
+	/// Empty route is discovered and is accounted as the longest route, including reads of the search.
+	fn route_weight(route: &[Trade<T::AssetId, T::OrderId>], trade_weight: Weight) -> Weight {
+		let orders = route.iter().filter(|trade| trade.order.is_some()).count() as u64;
+
+		let (trades, discovery) = if route.is_empty() {
+			// Every trade of every candidate route is quoted.
+			// A quote reads the states of both assets and the state of the pool.
+			(
+				T::MaxRouteLength::get() as u64,
+				T::DbWeight::get().reads(Self::candidate_trades_count().saturating_mul(4)),
+			)
+		} else {
+			(route.len() as u64, Weight::zero())
+		};
+
+		trade_weight
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_mul(trades)
+			.saturating_add(T::OrderBook::fill_weight().saturating_mul(orders))
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(discovery)
+	}
+
+	fn ensure_route(
+		asset_in: T::AssetId,
+		asset_out: T::AssetId,
+		route: Vec<Trade<T::AssetId, T::OrderId>>,
+	) -> Result<Vec<Trade<T::AssetId, T::OrderId>>, DispatchError> {
+		ensure!(asset_in != asset_out, Error::<T>::InvalidRoute);
+
+		ensure!(
+			route.first().map(|trade| trade.asset_in) == Some(asset_in),
+			Error::<T>::InvalidRoute
+		);
+		ensure!(
+			route.last().map(|trade| trade.asset_out) == Some(asset_out),
+			Error::<T>::InvalidRoute
+		);
+		ensure!(
+			route.iter().all(|trade| trade.asset_in != trade.asset_out),
+			Error::<T>::InvalidRoute
+		);
+		ensure!(
+			route.windows(2).all(|pair| pair[0].asset_out == pair[1].asset_in),
+			Error::<T>::InvalidRoute
+		);
+
+		Ok(route)
+	}
+
+	/// Load the order of a trade if it gives a better price than the pool.
+	fn load_better_order(
+		trade: &Trade<T::AssetId, T::OrderId>,
+	) -> Result<Option<(T::OrderId, OrderInfo<T::AssetId, Balance>)>, DispatchError> {
+		let order_id = match trade.order {
+			Some(order_id) => order_id,
+			None => return Ok(None),
+		};
+
+		let order = T::OrderBook::order(order_id).ok_or(Error::<T>::OrderNotFound)?;
+		ensure!(
+			order.asset_in == trade.asset_in && order.asset_out == trade.asset_out,
+			Error::<T>::OrderAssetMismatch
+		);
+
+		// Both prices are amounts of asset in paid for one unit of asset out
+		let order_price = match FixedU128::checked_from_rational(order.amount_in, order.amount_out) {
+			Some(price) => price,
+			None => return Ok(None),
+		};
+
+		let is_better = match T::SpotPriceProvider::spot_price(trade.asset_in, trade.asset_out) {
+			Some(pool_price) => order_price < pool_price,
+			None => true,
+		};
+
+		Ok(is_better.then_some((order_id, order)))
+	}
+
+	/// Amount of asset in to fill an order with when selling `amount_in`.
+	fn order_fill_for_sell(order: &OrderInfo<T::AssetId, Balance>, amount_in: Balance) -> Balance {
+		if order.partially_fillable {
+			amount_in.min(order.amount_in)
+		} else if amount_in >= order.amount_in {
+			order.amount_in
+		} else {
+			Balance::zero()
+		}
+	}
+
+	/// Amounts of asset in and asset out to fill an order with when buying `amount_out`.
+	fn order_fill_for_buy(
+		order: &OrderInfo<T::AssetId, Balance>,
+		amount_out: Balance,
+	) -> Result<(Balance, Balance), DispatchError> {
+		if amount_out >= order.amount_out {
+			return Ok((order.amount_in, order.amount_out));
+		}
+		if !order.partially_fillable {
+			return Ok((Balance::zero(), Balance::zero()));
+		}
+
+		let fill_in = multiply_by_rational_with_rounding(amount_out, order.amount_in, order.amount_out, Rounding::Down)
+			.ok_or(ArithmeticError::Overflow)?;
+		let fill_out = multiply_by_rational_with_rounding(fill_in, order.amount_out, order.amount_in, Rounding::Down)
+			.ok_or(ArithmeticError::Overflow)?;
+
+		Ok((fill_in, fill_out))
+	}
+
+	/// Execute a sell trade and return the amount of asset out received.
+	///
+	/// The pool trade is executed without a limit of amount out. The amount received is only checked against
+	/// `min_amount_out` once the whole route is executed.
+	fn execute_sell_trade(
+		origin: OriginFor<T>,
+		who: &T::AccountId,
+		trade: &Trade<T::AssetId, T::OrderId>,
+		amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		let balance_before = T::Currency::free_balance(trade.asset_out, who);
+
+		let mut remaining = amount_in;
+
+		if let Some((order_id, order)) = Self::load_better_order(trade)? {
+			let fill_in = Self::order_fill_for_sell(&order, remaining);
+			if !fill_in.is_zero() {
+				T::OrderBook::fill(origin.clone(), order_id, fill_in)?;
+				remaining = remaining.checked_sub(fill_in).ok_or(ArithmeticError::Underflow)?;
+			}
+		}
+
+		if !remaining.is_zero() {
+			T::AMM::execute_sell(origin, trade.asset_in, trade.asset_out, remaining, Balance::zero())?;
+		}
+
+		T::Currency::free_balance(trade.asset_out, who)
+			.checked_sub(balance_before)
+			.ok_or_else(|| ArithmeticError::Underflow.into())
+	}
+
+	/// Calculate the amount of asset out received when selling `amount_in` along a route without orders.
+	fn calculate_sell_amount(
+		route: &[Trade<T::AssetId, T::OrderId>],
+		amount_in: Balance,
+	) -> Result<Balance, DispatchError> {
+		route.iter().try_fold(amount_in, |amount, trade| {
+			T::AMM::calculate_sell(trade.asset_in, trade.asset_out, amount)
+		})
+	}
+
+	/// Calculate the amount of asset in required to buy `amount_out` along a route, together with the amounts
+	/// bought by its trades. Amounts are calculated backwards from the last trade.
+	fn calculate_buy_amounts(
+		route: &[Trade<T::AssetId, T::OrderId>],
+		amount_out: Balance,
+	) -> Result<(Balance, Vec<Balance>), DispatchError> {
+		let mut amounts = Vec::with_capacity(route.len());
+		let mut amount = amount_out;
+		for trade in route.iter().rev() {
+			amounts.push(amount);
+			amount = Self::calculate_buy_trade(trade, amount)?;
+		}
+		amounts.reverse();
+
+		Ok((amount, amounts))
+	}
+
+	/// Sell what is left of intermediate assets of a buy route back to the asset in of the trade which bought them,
+	/// from the last trade to the first one, so that intermediate balances end where they were before the route.
+	///
+	/// Leftovers which cannot be traded in the pool, eg. below its minimum trading amount, stay with the trader.
+	fn sell_back_intermediate_leftovers(
+		origin: OriginFor<T>,
+		who: &T::AccountId,
+		route: &[Trade<T::AssetId, T::OrderId>],
+		intermediate_balances: &[(T::AssetId, Balance)],
+	) -> DispatchResult {
+		for trade in route.iter().rev().skip(1) {
+			let balance_before = match intermediate_balances
+				.iter()
+				.find(|(asset, _)| *asset == trade.asset_out)
+			{
+				Some((_, balance)) => *balance,
+				None => continue,
+			};
+
+			let leftover = T::Currency::free_balance(trade.asset_out, who)
+				.checked_sub(balance_before)
+				.ok_or(Error::<T>::InsufficientIntermediateAmount)?;
+			if leftover.is_zero() {
+				continue;
+			}
+
+			let _ = with_transaction(|| {
+				let result = T::AMM::execute_sell(
+					origin.clone(),
+					trade.asset_out,
+					trade.asset_in,
+					leftover,
+					Balance::zero(),
+				);
+				if result.is_ok() {
+					TransactionOutcome::Commit(result)
+				} else {
+					TransactionOutcome::Rollback(result)
+				}
+			});
+		}
+
+		Ok(())
+	}
+
+	/// Calculate the amount of asset in required to buy `amount_out` in a trade.
+	fn calculate_buy_trade(
+		trade: &Trade<T::AssetId, T::OrderId>,
+		amount_out: Balance,
+	) -> Result<Balance, DispatchError> {
+		let (fill_in, fill_out) = match Self::load_better_order(trade)? {
+			Some((_, order)) => Self::order_fill_for_buy(&order, amount_out)?,
+			None => (Balance::zero(), Balance::zero()),
+		};
+
+		let remaining = amount_out.checked_sub(fill_out).ok_or(ArithmeticError::Underflow)?;
+
+		let pool_in = if remaining.is_zero() {
+			Balance::zero()
+		} else {
+			T::AMM::calculate_buy(trade.asset_in, trade.asset_out, remaining)?
+		};
+
+		fill_in
+			.checked_add(pool_in)
+			.ok_or_else(|| ArithmeticError::Overflow.into())
+	}
+
+	/// Execute a buy trade of `amount_out`.
+	///
+	/// The pool trade is executed without a limit of amount in. The amount spent is only checked against
+	/// `max_amount_in` once the whole route is executed.
+	fn execute_buy_trade(
+		origin: OriginFor<T>,
+		who: &T::AccountId,
+		trade: &Trade<T::AssetId, T::OrderId>,
+		amount_out: Balance,
+	) -> DispatchResult {
+		let mut remaining = amount_out;
+
+		if let Some((order_id, order)) = Self::load_better_order(trade)? {
+			let (fill_in, _) = Self::order_fill_for_buy(&order, remaining)?;
+			if !fill_in.is_zero() {
+				let balance_before = T::Currency::free_balance(trade.asset_out, who);
+				T::OrderBook::fill(origin.clone(), order_id, fill_in)?;
+				let received = T::Currency::free_balance(trade.asset_out, who)
+					.checked_sub(balance_before)
+					.ok_or(ArithmeticError::Underflow)?;
+				remaining = remaining.saturating_sub(received);
+			}
+		}
+
+		if !remaining.is_zero() {
+			T::AMM::execute_buy(origin, trade.asset_in, trade.asset_out, remaining, Balance::MAX)?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,298 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::traits::OrderBook;
+use crate::{Error, Event};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::FixedU128;
+
+#[test]
+fn buy_should_work_when_route_has_multiple_trades() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 200 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				DOT,
+				10 * ONE,
+				100 * ONE,
+				route(vec![trade(HDX, DAI, None), trade(DAI, DOT, None)])
+			));
+
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 100 * ONE);
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				Event::RouteExecuted {
+					who: ALICE,
+					asset_in: HDX,
+					asset_out: DOT,
+					amount_in: 100 * ONE,
+					amount_out: 10 * ONE,
+					trades: 2,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn buy_should_discover_route_when_route_is_empty() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 200 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				DAI,
+				50 * ONE,
+				100 * ONE,
+				route(vec![])
+			));
+
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 100 * ONE);
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 50 * ONE);
+		});
+}
+
+#[test]
+fn buy_should_discover_route_through_intermediate_asset() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 200 * ONE)])
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				10 * ONE,
+				100 * ONE,
+				route(vec![])
+			));
+
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 100 * ONE);
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+		});
+}
+
+#[test]
+fn buy_should_discover_two_hop_route_when_it_quotes_better_than_direct_route() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 200 * ONE)])
+		.with_price(DAI, DOT, FixedU128::from(20))
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Router::discover_buy_route(DAI, DOT, 10 * ONE),
+				Ok(vec![trade(DAI, HDX, None), trade(HDX, DOT, None)])
+			);
+
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				10 * ONE,
+				100 * ONE,
+				route(vec![])
+			));
+
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 100 * ONE);
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+		});
+}
+
+#[test]
+fn buy_should_sell_back_leftover_of_intermediate_asset_when_trade_moves_price_of_next_trade() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 300 * ONE)])
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.with_price_after_trade(DAI, HDX, HDX, DOT, FixedU128::from(4))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				10 * ONE,
+				100 * ONE,
+				route(vec![trade(DAI, HDX, None), trade(HDX, DOT, None)])
+			));
+
+			// 50 HDX are bought for 100 DAI, 10 DOT cost 40 HDX and 10 HDX are sold back for 20 DAI
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 220 * ONE);
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				Event::RouteExecuted {
+					who: ALICE,
+					asset_in: DAI,
+					asset_out: DOT,
+					amount_in: 80 * ONE,
+					amount_out: 10 * ONE,
+					trades: 2,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn buy_should_fail_when_trade_spends_more_of_intermediate_asset_than_previous_trade_bought() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 300 * ONE), (ALICE, HDX, 100 * ONE)])
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.with_price_after_trade(DAI, HDX, HDX, DOT, FixedU128::from(6))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::buy(
+					RuntimeOrigin::signed(ALICE),
+					DAI,
+					DOT,
+					10 * ONE,
+					300 * ONE,
+					route(vec![trade(DAI, HDX, None), trade(HDX, DOT, None)])
+				),
+				Error::<Test>::InsufficientIntermediateAmount
+			);
+		});
+}
+
+#[test]
+fn buy_should_fail_when_max_amount_in_is_exceeded() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 200 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::buy(
+					RuntimeOrigin::signed(ALICE),
+					HDX,
+					DOT,
+					10 * ONE,
+					100 * ONE - 1,
+					route(vec![trade(HDX, DAI, None), trade(DAI, DOT, None)])
+				),
+				Error::<Test>::MaxAmountInExceeded
+			);
+		});
+}
+
+#[test]
+fn buy_should_fail_when_amount_is_zero() {
+	ExtBuilder::default()
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::buy(RuntimeOrigin::signed(ALICE), HDX, DAI, 0, 100 * ONE, route(vec![])),
+				Error::<Test>::ZeroAmount
+			);
+		});
+}
+
+#[test]
+fn buy_should_fill_order_when_order_price_is_better() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 200 * ONE), (BOB, DOT, 5 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.with_order(0, BOB, dai_dot_order(20 * ONE, 5 * ONE, false))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				DOT,
+				10 * ONE,
+				90 * ONE,
+				route(vec![trade(HDX, DAI, None), trade(DAI, DOT, Some(0))])
+			));
+
+			// 5 DOT bought from the order for 20 DAI, remaining 5 DOT bought in the pool for 25 DAI
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 110 * ONE);
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+			assert_eq!(Tokens::free_balance(DAI, &BOB), 20 * ONE);
+			assert!(MockOrderBook::order(0).is_none());
+		});
+}
+
+#[test]
+fn buy_should_fill_order_partially_when_order_is_partially_fillable() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 100 * ONE), (BOB, DOT, 25 * ONE)])
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.with_order(0, BOB, dai_dot_order(100 * ONE, 25 * ONE, true))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				10 * ONE,
+				40 * ONE,
+				route(vec![trade(DAI, DOT, Some(0))])
+			));
+
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 60 * ONE);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+			assert_eq!(MockOrderBook::order(0), Some(dai_dot_order(60 * ONE, 15 * ONE, true)));
+		});
+}
+
+#[test]
+fn buy_should_not_fill_order_when_order_price_is_worse() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 100 * ONE), (BOB, DOT, 5 * ONE)])
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.with_order(0, BOB, dai_dot_order(30 * ONE, 5 * ONE, true))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::buy(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				10 * ONE,
+				50 * ONE,
+				route(vec![trade(DAI, DOT, Some(0))])
+			));
+
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 50 * ONE);
+			assert_eq!(MockOrderBook::order(0), Some(dai_dot_order(30 * ONE, 5 * ONE, true)));
+		});
+}
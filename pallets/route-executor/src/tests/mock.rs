@@ -0,0 +1,395 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use crate as pallet_route_executor;
+use crate::traits::{OrderBook, OrderInfo, TradeExecution};
+use crate::{Balance, Trade};
+use frame_support::traits::{ConstU32, Everything, GenesisBuild};
+use frame_support::weights::Weight;
+pub use frame_support::{assert_noop, assert_ok, parameter_types, BoundedVec};
+use frame_system::ensure_signed;
+use hydradx_traits::pools::SpotPriceProvider;
+use orml_traits::{parameter_type_with_key, MultiCurrency};
+use sp_core::H256;
+use sp_runtime::helpers_128bit::multiply_by_rational_with_rounding;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, FixedU128, Rounding,
+};
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type AssetId = u32;
+pub type OrderId = u32;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const POOL: AccountId = 100;
+
+pub const HDX: AssetId = 0;
+pub const DAI: AssetId = 2;
+pub const DOT: AssetId = 3;
+pub const ACA: AssetId = 4;
+
+pub const ONE: Balance = 1_000_000_000_000;
+
+thread_local! {
+	pub static PRICES: RefCell<HashMap<(AssetId, AssetId), FixedU128>> = RefCell::new(HashMap::default());
+	pub static ORDERS: RefCell<HashMap<OrderId, (AccountId, OrderInfo<AssetId, Balance>)>> = RefCell::new(HashMap::default());
+	pub static PRICES_AFTER_TRADE: RefCell<HashMap<(AssetId, AssetId), (AssetId, AssetId, FixedU128)>> = RefCell::new(HashMap::default());
+}
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Tokens: orml_tokens,
+		Router: pallet_route_executor,
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+	pub const MaxRouteLength: u32 = 5;
+	pub IntermediateAssets: Vec<AssetId> = vec![HDX, ACA];
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = SS58Prefix;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: AssetId| -> Balance {
+		0
+	};
+}
+
+impl orml_tokens::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type Amount = i128;
+	type CurrencyId = AssetId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type MaxLocks = ();
+	type DustRemovalWhitelist = Everything;
+	type MaxReserves = ();
+	type ReserveIdentifier = ();
+	type CurrencyHooks = ();
+}
+
+impl pallet_route_executor::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = AssetId;
+	type OrderId = OrderId;
+	type Currency = Tokens;
+	type AMM = MockPool;
+	type OrderBook = MockOrderBook;
+	type SpotPriceProvider = MockPool;
+	type MaxRouteLength = MaxRouteLength;
+	type IntermediateAssets = IntermediateAssets;
+}
+
+pub fn route(trades: Vec<Trade<AssetId, OrderId>>) -> BoundedVec<Trade<AssetId, OrderId>, MaxRouteLength> {
+	BoundedVec::truncate_from(trades)
+}
+
+pub fn trade(asset_in: AssetId, asset_out: AssetId, order: Option<OrderId>) -> Trade<AssetId, OrderId> {
+	Trade {
+		asset_in,
+		asset_out,
+		order,
+	}
+}
+
+/// Order selling DOT for DAI.
+pub fn dai_dot_order(amount_in: Balance, amount_out: Balance, partially_fillable: bool) -> OrderInfo<AssetId, Balance> {
+	OrderInfo {
+		asset_in: DAI,
+		asset_out: DOT,
+		amount_in,
+		amount_out,
+		partially_fillable,
+	}
+}
+
+/// Pool trading at fixed prices without fees.
+pub struct MockPool;
+
+impl MockPool {
+	fn price(asset_in: AssetId, asset_out: AssetId) -> Result<FixedU128, DispatchError> {
+		PRICES
+			.with(|v| v.borrow().get(&(asset_in, asset_out)).copied())
+			.ok_or(DispatchError::Other("Pair does not exist"))
+	}
+
+	/// Apply the price change registered for a trade of `asset_in` for `asset_out`.
+	fn on_trade(asset_in: AssetId, asset_out: AssetId) {
+		if let Some((asset_a, asset_b, price)) =
+			PRICES_AFTER_TRADE.with(|v| v.borrow_mut().remove(&(asset_in, asset_out)))
+		{
+			set_price(asset_a, asset_b, price);
+		}
+	}
+}
+
+fn set_price(asset_a: AssetId, asset_b: AssetId, price: FixedU128) {
+	PRICES.with(|v| {
+		v.borrow_mut().insert((asset_a, asset_b), price);
+		v.borrow_mut()
+			.insert((asset_b, asset_a), price.reciprocal().expect("price is not zero"));
+	});
+}
+
+impl SpotPriceProvider<AssetId> for MockPool {
+	type Price = FixedU128;
+
+	fn pair_exists(asset_a: AssetId, asset_b: AssetId) -> bool {
+		Self::price(asset_a, asset_b).is_ok()
+	}
+
+	fn spot_price(asset_a: AssetId, asset_b: AssetId) -> Option<Self::Price> {
+		Self::price(asset_a, asset_b).ok()
+	}
+}
+
+impl TradeExecution<RuntimeOrigin, AssetId, Balance> for MockPool {
+	fn calculate_sell(asset_in: AssetId, asset_out: AssetId, amount_in: Balance) -> Result<Balance, DispatchError> {
+		let price = Self::price(asset_in, asset_out)?;
+		multiply_by_rational_with_rounding(amount_in, FixedU128::DIV, price.into_inner(), Rounding::Down)
+			.ok_or_else(|| ArithmeticError::Overflow.into())
+	}
+
+	fn calculate_buy(asset_in: AssetId, asset_out: AssetId, amount_out: Balance) -> Result<Balance, DispatchError> {
+		let price = Self::price(asset_in, asset_out)?;
+		multiply_by_rational_with_rounding(amount_out, price.into_inner(), FixedU128::DIV, Rounding::Up)
+			.ok_or_else(|| ArithmeticError::Overflow.into())
+	}
+
+	fn execute_sell(
+		origin: RuntimeOrigin,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		min_limit: Balance,
+	) -> DispatchResult {
+		let who = ensure_signed(origin)?;
+		let amount_out = Self::calculate_sell(asset_in, asset_out, amount_in)?;
+		if amount_out < min_limit {
+			return Err(DispatchError::Other("Buy limit not reached"));
+		}
+		<Tokens as MultiCurrency<AccountId>>::transfer(asset_in, &who, &POOL, amount_in)?;
+		<Tokens as MultiCurrency<AccountId>>::transfer(asset_out, &POOL, &who, amount_out)?;
+		Self::on_trade(asset_in, asset_out);
+		Ok(())
+	}
+
+	fn execute_buy(
+		origin: RuntimeOrigin,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		max_limit: Balance,
+	) -> DispatchResult {
+		let who = ensure_signed(origin)?;
+		let amount_in = Self::calculate_buy(asset_in, asset_out, amount_out)?;
+		if amount_in > max_limit {
+			return Err(DispatchError::Other("Sell limit exceeded"));
+		}
+		<Tokens as MultiCurrency<AccountId>>::transfer(asset_in, &who, &POOL, amount_in)?;
+		<Tokens as MultiCurrency<AccountId>>::transfer(asset_out, &POOL, &who, amount_out)?;
+		Self::on_trade(asset_in, asset_out);
+		Ok(())
+	}
+
+	fn sell_weight() -> Weight {
+		Weight::zero()
+	}
+
+	fn buy_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Order book keeping offered amounts in the free balance of order owners.
+pub struct MockOrderBook;
+
+impl OrderBook<RuntimeOrigin, OrderId, AssetId, Balance> for MockOrderBook {
+	fn order(order_id: OrderId) -> Option<OrderInfo<AssetId, Balance>> {
+		ORDERS.with(|v| v.borrow().get(&order_id).map(|(_, order)| *order))
+	}
+
+	fn fill(origin: RuntimeOrigin, order_id: OrderId, amount_in: Balance) -> DispatchResult {
+		let who = ensure_signed(origin)?;
+		let (owner, mut order) = ORDERS
+			.with(|v| v.borrow().get(&order_id).copied())
+			.ok_or(DispatchError::Other("Order not found"))?;
+
+		let amount_out =
+			multiply_by_rational_with_rounding(amount_in, order.amount_out, order.amount_in, Rounding::Down)
+				.ok_or(ArithmeticError::Overflow)?;
+
+		<Tokens as MultiCurrency<AccountId>>::transfer(order.asset_in, &who, &owner, amount_in)?;
+		<Tokens as MultiCurrency<AccountId>>::transfer(order.asset_out, &owner, &who, amount_out)?;
+
+		order.amount_in -= amount_in;
+		order.amount_out -= amount_out;
+
+		ORDERS.with(|v| {
+			if order.amount_in == 0 {
+				v.borrow_mut().remove(&order_id);
+			} else {
+				v.borrow_mut().insert(order_id, (owner, order));
+			}
+		});
+
+		Ok(())
+	}
+
+	fn fill_weight() -> Weight {
+		Weight::zero()
+	}
+}
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, AssetId, Balance)>,
+	prices: Vec<(AssetId, AssetId, FixedU128)>,
+	prices_after_trade: Vec<((AssetId, AssetId), (AssetId, AssetId, FixedU128))>,
+	orders: Vec<(OrderId, AccountId, OrderInfo<AssetId, Balance>)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		// If eg. tests running on one thread only, this thread local is shared.
+		// let's make sure that it is empty for each test case
+		PRICES.with(|v| {
+			v.borrow_mut().clear();
+		});
+		ORDERS.with(|v| {
+			v.borrow_mut().clear();
+		});
+		PRICES_AFTER_TRADE.with(|v| {
+			v.borrow_mut().clear();
+		});
+
+		Self {
+			endowed_accounts: vec![
+				(POOL, HDX, 1_000_000 * ONE),
+				(POOL, DAI, 1_000_000 * ONE),
+				(POOL, DOT, 1_000_000 * ONE),
+				(POOL, ACA, 1_000_000 * ONE),
+			],
+			prices: vec![],
+			prices_after_trade: vec![],
+			orders: vec![],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn with_endowed_accounts(mut self, accounts: Vec<(AccountId, AssetId, Balance)>) -> Self {
+		self.endowed_accounts.extend(accounts);
+		self
+	}
+
+	/// Set pool price of `asset_b` denominated in `asset_a`.
+	pub fn with_price(mut self, asset_a: AssetId, asset_b: AssetId, price: FixedU128) -> Self {
+		self.prices.push((asset_a, asset_b, price));
+		self
+	}
+
+	/// Set pool price of `asset_b` denominated in `asset_a` after the first trade of `asset_in` for `asset_out`.
+	pub fn with_price_after_trade(
+		mut self,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		asset_a: AssetId,
+		asset_b: AssetId,
+		price: FixedU128,
+	) -> Self {
+		self.prices_after_trade
+			.push(((asset_in, asset_out), (asset_a, asset_b, price)));
+		self
+	}
+
+	pub fn with_order(mut self, order_id: OrderId, owner: AccountId, order: OrderInfo<AssetId, Balance>) -> Self {
+		self.orders.push((order_id, owner, order));
+		self
+	}
+
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+		orml_tokens::GenesisConfig::<Test> {
+			balances: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		for (asset_a, asset_b, price) in self.prices.iter() {
+			set_price(*asset_a, *asset_b, *price);
+		}
+
+		PRICES_AFTER_TRADE.with(|v| {
+			for (trade, price) in self.prices_after_trade.iter() {
+				v.borrow_mut().insert(*trade, *price);
+			}
+		});
+
+		ORDERS.with(|v| {
+			for (order_id, owner, order) in self.orders.iter() {
+				v.borrow_mut().insert(*order_id, (*owner, *order));
+			}
+		});
+
+		let mut r: sp_io::TestExternalities = t.into();
+		r.execute_with(|| System::set_block_number(1));
+		r
+	}
+}
@@ -0,0 +1,3 @@
+mod buy;
+pub(crate) mod mock;
+mod sell;
@@ -0,0 +1,423 @@
+// This file is part of HydraDX.
+
+// Copyright (C) 2020-2023  Intergalactic, Limited (GIB).
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tests::mock::*;
+use crate::traits::OrderBook;
+use crate::{Error, Event};
+use orml_traits::MultiCurrency;
+use pretty_assertions::assert_eq;
+use sp_runtime::FixedU128;
+
+#[test]
+fn sell_should_work_when_route_has_multiple_trades() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				DOT,
+				100 * ONE,
+				10 * ONE,
+				route(vec![trade(HDX, DAI, None), trade(DAI, DOT, None)])
+			));
+
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				Event::RouteExecuted {
+					who: ALICE,
+					asset_in: HDX,
+					asset_out: DOT,
+					amount_in: 100 * ONE,
+					amount_out: 10 * ONE,
+					trades: 2,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn sell_should_discover_route_when_route_is_empty() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				DAI,
+				100 * ONE,
+				0,
+				route(vec![])
+			));
+
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 50 * ONE);
+		});
+}
+
+#[test]
+fn sell_should_discover_route_through_intermediate_asset() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 100 * ONE)])
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				100 * ONE,
+				10 * ONE,
+				route(vec![])
+			));
+
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+
+			frame_system::Pallet::<Test>::assert_last_event(
+				Event::RouteExecuted {
+					who: ALICE,
+					asset_in: DAI,
+					asset_out: DOT,
+					amount_in: 100 * ONE,
+					amount_out: 10 * ONE,
+					trades: 2,
+				}
+				.into(),
+			);
+		});
+}
+
+#[test]
+fn sell_should_discover_two_hop_route_when_it_quotes_better_than_direct_route() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 100 * ONE)])
+		.with_price(DAI, DOT, FixedU128::from(20))
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				100 * ONE,
+				10 * ONE,
+				route(vec![])
+			));
+
+			assert_eq!(Tokens::free_balance(DAI, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(HDX, &ALICE), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+		});
+}
+
+#[test]
+fn discover_sell_route_should_find_route_through_multiple_intermediate_assets() {
+	ExtBuilder::default()
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, ACA, FixedU128::from(3))
+		.with_price(ACA, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Router::discover_sell_route(DAI, DOT, 100 * ONE),
+				Ok(vec![
+					trade(DAI, HDX, None),
+					trade(HDX, ACA, None),
+					trade(ACA, DOT, None)
+				])
+			);
+		});
+}
+
+#[test]
+fn discover_sell_route_should_prefer_route_with_higher_amount_out_when_routes_have_same_length() {
+	ExtBuilder::default()
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.with_price(DAI, ACA, FixedU128::from(4))
+		.with_price(ACA, DOT, FixedU128::from(2))
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Router::discover_sell_route(DAI, DOT, 100 * ONE),
+				Ok(vec![trade(DAI, ACA, None), trade(ACA, DOT, None)])
+			);
+		});
+}
+
+#[test]
+fn discover_sell_route_should_prefer_longer_route_when_it_quotes_better() {
+	ExtBuilder::default()
+		.with_price(DAI, DOT, FixedU128::from(20))
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Router::discover_sell_route(DAI, DOT, 100 * ONE),
+				Ok(vec![trade(DAI, HDX, None), trade(HDX, DOT, None)])
+			);
+		});
+}
+
+#[test]
+fn discover_sell_route_should_prefer_shorter_route_when_quotes_are_equal() {
+	ExtBuilder::default()
+		.with_price(DAI, DOT, FixedU128::from(10))
+		.with_price(DAI, HDX, FixedU128::from(2))
+		.with_price(HDX, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				Router::discover_sell_route(DAI, DOT, 100 * ONE),
+				Ok(vec![trade(DAI, DOT, None)])
+			);
+		});
+}
+
+#[test]
+fn sell_should_fail_when_route_cannot_be_discovered() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::sell(RuntimeOrigin::signed(ALICE), HDX, DOT, 100 * ONE, 0, route(vec![])),
+				Error::<Test>::RouteNotFound
+			);
+		});
+}
+
+#[test]
+fn sell_should_fail_when_trades_are_not_connected() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::sell(
+					RuntimeOrigin::signed(ALICE),
+					HDX,
+					DOT,
+					100 * ONE,
+					0,
+					route(vec![trade(HDX, DAI, None), trade(HDX, DOT, None)])
+				),
+				Error::<Test>::InvalidRoute
+			);
+			assert_noop!(
+				Router::sell(
+					RuntimeOrigin::signed(ALICE),
+					HDX,
+					DOT,
+					100 * ONE,
+					0,
+					route(vec![trade(HDX, DAI, None)])
+				),
+				Error::<Test>::InvalidRoute
+			);
+		});
+}
+
+#[test]
+fn sell_should_fail_when_min_amount_out_is_not_reached() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::sell(
+					RuntimeOrigin::signed(ALICE),
+					HDX,
+					DOT,
+					100 * ONE,
+					10 * ONE + 1,
+					route(vec![trade(HDX, DAI, None), trade(DAI, DOT, None)])
+				),
+				Error::<Test>::MinAmountOutNotReached
+			);
+		});
+}
+
+#[test]
+fn sell_should_fail_when_amount_is_zero() {
+	ExtBuilder::default()
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::sell(RuntimeOrigin::signed(ALICE), HDX, DAI, 0, 0, route(vec![])),
+				Error::<Test>::ZeroAmount
+			);
+		});
+}
+
+#[test]
+fn sell_should_fill_order_when_order_price_is_better() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE), (BOB, DOT, 5 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.with_order(0, BOB, dai_dot_order(20 * ONE, 5 * ONE, false))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				DOT,
+				100 * ONE,
+				11 * ONE,
+				route(vec![trade(HDX, DAI, None), trade(DAI, DOT, Some(0))])
+			));
+
+			// 20 DAI filled the order for 5 DOT, remaining 30 DAI sold in the pool for 6 DOT
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 11 * ONE);
+			assert_eq!(Tokens::free_balance(DAI, &BOB), 20 * ONE);
+			assert!(MockOrderBook::order(0).is_none());
+		});
+}
+
+#[test]
+fn sell_should_not_fill_order_when_order_price_is_worse() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE), (BOB, DOT, 5 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.with_order(0, BOB, dai_dot_order(30 * ONE, 5 * ONE, false))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				HDX,
+				DOT,
+				100 * ONE,
+				0,
+				route(vec![trade(HDX, DAI, None), trade(DAI, DOT, Some(0))])
+			));
+
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+			assert_eq!(MockOrderBook::order(0), Some(dai_dot_order(30 * ONE, 5 * ONE, false)));
+		});
+}
+
+#[test]
+fn sell_should_fill_order_partially_when_order_is_partially_fillable() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 50 * ONE), (BOB, DOT, 25 * ONE)])
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.with_order(0, BOB, dai_dot_order(100 * ONE, 25 * ONE, true))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				50 * ONE,
+				0,
+				route(vec![trade(DAI, DOT, Some(0))])
+			));
+
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 12_500_000_000_000);
+			assert_eq!(
+				MockOrderBook::order(0),
+				Some(dai_dot_order(50 * ONE, 12_500_000_000_000, true))
+			);
+		});
+}
+
+#[test]
+fn sell_should_skip_order_when_order_is_not_partially_fillable_and_amount_is_lower() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 50 * ONE), (BOB, DOT, 25 * ONE)])
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.with_order(0, BOB, dai_dot_order(100 * ONE, 25 * ONE, false))
+		.build()
+		.execute_with(|| {
+			assert_ok!(Router::sell(
+				RuntimeOrigin::signed(ALICE),
+				DAI,
+				DOT,
+				50 * ONE,
+				0,
+				route(vec![trade(DAI, DOT, Some(0))])
+			));
+
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 10 * ONE);
+			assert_eq!(MockOrderBook::order(0), Some(dai_dot_order(100 * ONE, 25 * ONE, false)));
+		});
+}
+
+#[test]
+fn sell_should_fail_when_order_assets_do_not_match_trade() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, HDX, 100 * ONE), (BOB, DOT, 5 * ONE)])
+		.with_price(HDX, DAI, FixedU128::from(2))
+		.with_order(0, BOB, dai_dot_order(20 * ONE, 5 * ONE, false))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::sell(
+					RuntimeOrigin::signed(ALICE),
+					HDX,
+					DAI,
+					100 * ONE,
+					0,
+					route(vec![trade(HDX, DAI, Some(0))])
+				),
+				Error::<Test>::OrderAssetMismatch
+			);
+		});
+}
+
+#[test]
+fn sell_should_fail_when_order_does_not_exist() {
+	ExtBuilder::default()
+		.with_endowed_accounts(vec![(ALICE, DAI, 50 * ONE)])
+		.with_price(DAI, DOT, FixedU128::from(5))
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				Router::sell(
+					RuntimeOrigin::signed(ALICE),
+					DAI,
+					DOT,
+					50 * ONE,
+					0,
+					route(vec![trade(DAI, DOT, Some(0))])
+				),
+				Error::<Test>::OrderNotFound
+			);
+		});
+}
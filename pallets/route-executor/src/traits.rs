@@ -0,0 +1,80 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::weights::Weight;
+use scale_info::TypeInfo;
+use sp_runtime::{DispatchError, DispatchResult, RuntimeDebug};
+
+/// Executes trades in a pool on behalf of the router.
+pub trait TradeExecution<Origin, AssetId, Balance> {
+	/// Calculate amount of `asset_out` received when selling `amount_in` of `asset_in`.
+	fn calculate_sell(asset_in: AssetId, asset_out: AssetId, amount_in: Balance) -> Result<Balance, DispatchError>;
+
+	/// Calculate amount of `asset_in` required to buy `amount_out` of `asset_out`.
+	fn calculate_buy(asset_in: AssetId, asset_out: AssetId, amount_out: Balance) -> Result<Balance, DispatchError>;
+
+	/// Sell `amount_in` of `asset_in` for at least `min_limit` of `asset_out`.
+	fn execute_sell(
+		origin: Origin,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		min_limit: Balance,
+	) -> DispatchResult;
+
+	/// Buy `amount_out` of `asset_out` for at most `max_limit` of `asset_in`.
+	fn execute_buy(
+		origin: Origin,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		max_limit: Balance,
+	) -> DispatchResult;
+
+	/// Weight of `execute_sell`.
+	fn sell_weight() -> Weight;
+
+	/// Weight of `execute_buy`.
+	fn buy_weight() -> Weight;
+}
+
+/// Details of an order, seen from the side of the account filling it.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo, PartialEq, Eq)]
+pub struct OrderInfo<AssetId, Balance> {
+	/// Asset the order owner wants to receive. Sold by the filler.
+	pub asset_in: AssetId,
+	/// Asset the order owner offers. Received by the filler.
+	pub asset_out: AssetId,
+	/// Remaining amount of `asset_in` the order owner wants to receive.
+	pub amount_in: Balance,
+	/// Remaining amount of `asset_out` the order owner offers.
+	pub amount_out: Balance,
+	/// Whether the order can be filled partially.
+	pub partially_fillable: bool,
+}
+
+/// Source of orders which can be filled by the router.
+pub trait OrderBook<Origin, OrderId, AssetId, Balance> {
+	/// Load order details. Returns None if the order does not exist.
+	fn order(order_id: OrderId) -> Option<OrderInfo<AssetId, Balance>>;
+
+	/// Fill an order by providing `amount_in` of order's asset in.
+	///
+	/// Order is filled completely when `amount_in` equals the remaining amount in of the order.
+	fn fill(origin: Origin, order_id: OrderId, amount_in: Balance) -> DispatchResult;
+
+	/// Weight of `fill`.
+	fn fill_weight() -> Weight;
+}
+
+impl<Origin, OrderId, AssetId, Balance> OrderBook<Origin, OrderId, AssetId, Balance> for () {
+	fn order(_order_id: OrderId) -> Option<OrderInfo<AssetId, Balance>> {
+		None
+	}
+
+	fn fill(_origin: Origin, _order_id: OrderId, _amount_in: Balance) -> DispatchResult {
+		Err(DispatchError::Other("No order book"))
+	}
+
+	fn fill_weight() -> Weight {
+		Weight::zero()
+	}
+}
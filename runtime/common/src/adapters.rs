@@ -2,9 +2,12 @@ use core::marker::PhantomData;
 
 use codec::FullCodec;
 use frame_support::{
-	traits::{Contains, Get},
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+	traits::{Contains, Get, IsSubType},
 	weights::Weight,
 };
+use frame_system::{pallet_prelude::OriginFor, RawOrigin};
 use hydra_dx_math::ema::EmaPrice;
 use hydra_dx_math::omnipool::types::BalanceUpdate;
 use hydra_dx_math::support::rational::round_to_rational;
@@ -12,23 +15,30 @@ use hydra_dx_math::support::rational::Rounding;
 use hydradx_traits::AggregatedPriceOracle;
 use hydradx_traits::PriceOracle;
 use hydradx_traits::{liquidity_mining::PriceAdjustment, OnLiquidityChangedHandler, OnTradeHandler, OraclePeriod};
+use orml_traits::MultiCurrency as _;
 use orml_xcm_support::OnDepositFail;
 use orml_xcm_support::UnknownAsset as UnknownAssetT;
+use pallet_circuit_breaker::traits::{AssetHaltHandler, LimitedAssets};
 use pallet_circuit_breaker::WeightInfo;
 use pallet_ema_oracle::Price;
 use pallet_ema_oracle::{OnActivityHandler, OracleError};
-use pallet_omnipool::traits::{AssetInfo, ExternalPriceProvider, OmnipoolHooks};
+use pallet_omnipool::traits::{AssetInfo, ExternalPriceProvider, OmnipoolHooks, ShouldAllow};
+use pallet_omnipool::types::Tradability;
+use pallet_omnipool::weights::WeightInfo as OmnipoolWeightInfo;
+use pallet_otc::weights::WeightInfo as OtcWeightInfo;
+use pallet_otc::OrderId;
+use pallet_route_executor::traits::{OrderBook, OrderInfo, TradeExecution};
 use primitive_types::U128;
 use primitives::{AssetId, Balance, BlockNumber};
 use sp_runtime::traits::MaybeSerializeDeserialize;
 use sp_runtime::traits::{Convert, Zero};
 use sp_runtime::SaturatedConversion;
-use sp_runtime::{ArithmeticError, DispatchError, FixedPointNumber, FixedU128};
+use sp_runtime::{ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, FixedU128};
 use sp_std::fmt::Debug;
 use warehouse_liquidity_mining::GlobalFarmData;
 use xcm::latest::prelude::*;
 use xcm_executor::{
-	traits::{Convert as MoreConvert, MatchesFungible, TransactAsset},
+	traits::{AssetExchange, Convert as MoreConvert, MatchesFungible, TransactAsset, WeightBounds},
 	Assets,
 };
 
@@ -116,31 +126,61 @@ where
 			amount_out.into(),
 		)?;
 
+		Self::ensure_price_change_limit(&asset_in)?;
+		Self::ensure_price_change_limit(&asset_out)?;
+
 		Ok(Self::on_trade_weight())
 	}
 
 	fn on_hub_asset_trade(_origin: Origin, asset: AssetInfo<AssetId, Balance>) -> Result<Weight, Self::Error> {
-		OnActivityHandler::<Runtime>::on_trade(
-			OMNIPOOL_SOURCE,
-			Lrna::get(),
-			asset.asset_id,
-			*asset.delta_changes.delta_hub_reserve,
-			*asset.delta_changes.delta_reserve,
-			asset.after.hub_reserve,
-			asset.after.reserve,
-		)
-		.map_err(|(_, e)| e)?;
-
-		let amount_out = *asset.delta_changes.delta_reserve;
+		match asset.delta_changes.delta_reserve {
+			// hub asset sold to the pool for the asset
+			BalanceUpdate::Decrease(amount_out) => {
+				OnActivityHandler::<Runtime>::on_trade(
+					OMNIPOOL_SOURCE,
+					Lrna::get(),
+					asset.asset_id,
+					*asset.delta_changes.delta_hub_reserve,
+					amount_out,
+					asset.after.hub_reserve,
+					asset.after.reserve,
+				)
+				.map_err(|(_, e)| e)?;
+
+				pallet_circuit_breaker::Pallet::<Runtime>::ensure_pool_state_change_limit(
+					Lrna::get().into(),
+					Balance::zero().into(),
+					Balance::zero().into(),
+					asset.asset_id.into(),
+					asset.before.reserve.into(),
+					amount_out.into(),
+				)?;
+			}
+			// asset sold to the pool for the hub asset
+			BalanceUpdate::Increase(amount_in) => {
+				OnActivityHandler::<Runtime>::on_trade(
+					OMNIPOOL_SOURCE,
+					asset.asset_id,
+					Lrna::get(),
+					amount_in,
+					*asset.delta_changes.delta_hub_reserve,
+					asset.after.reserve,
+					asset.after.hub_reserve,
+				)
+				.map_err(|(_, e)| e)?;
+
+				pallet_circuit_breaker::Pallet::<Runtime>::ensure_pool_state_change_limit(
+					asset.asset_id.into(),
+					asset.before.reserve.into(),
+					amount_in.into(),
+					Lrna::get().into(),
+					Balance::zero().into(),
+					Balance::zero().into(),
+				)?;
+			}
+		}
 
-		pallet_circuit_breaker::Pallet::<Runtime>::ensure_pool_state_change_limit(
-			Lrna::get().into(),
-			Balance::zero().into(),
-			Balance::zero().into(),
-			asset.asset_id.into(),
-			asset.before.reserve.into(),
-			amount_out.into(),
-		)?;
+		Self::ensure_price_change_limit(&asset)?;
 
 		Ok(Self::on_trade_weight())
 	}
@@ -157,7 +197,119 @@ where
 		let w1 = OnActivityHandler::<Runtime>::on_trade_weight().saturating_mul(2);
 		let w2 = <Runtime as pallet_circuit_breaker::Config>::WeightInfo::ensure_pool_state_change_limit();
 		let w3 = <Runtime as pallet_circuit_breaker::Config>::WeightInfo::on_finalize_single(); // TODO: implement and use on_finalize_single_trade_limit_entry benchmark
-		w1.saturating_add(w2).saturating_add(w3)
+		let w4 = <Runtime as pallet_circuit_breaker::Config>::WeightInfo::ensure_price_change_limit().saturating_mul(2);
+		w1.saturating_add(w2).saturating_add(w3).saturating_add(w4)
+	}
+}
+
+impl<Origin, Lrna, Runtime> OmnipoolHookAdapter<Origin, Lrna, Runtime>
+where
+	Runtime: pallet_circuit_breaker::Config,
+{
+	fn ensure_price_change_limit(asset: &AssetInfo<AssetId, Balance>) -> Result<Weight, DispatchError> {
+		pallet_circuit_breaker::Pallet::<Runtime>::ensure_price_change_limit(
+			asset.asset_id.into(),
+			asset.before.reserve.into(),
+			asset.before.hub_reserve.into(),
+			asset.after.reserve.into(),
+			asset.after.hub_reserve.into(),
+		)
+	}
+}
+
+/// Restricts Omnipool trading of assets halted by the circuit breaker.
+///
+/// Halted assets can only be withdrawn from the Omnipool. Resuming an asset restores its tradable state
+/// from before the halt.
+pub struct OmnipoolHaltAdapter<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> AssetHaltHandler<AssetId> for OmnipoolHaltAdapter<Runtime>
+where
+	Runtime: pallet_omnipool::Config<AssetId = AssetId>,
+{
+	type State = Tradability;
+
+	fn on_halt(asset_id: AssetId) -> Result<Tradability, DispatchError> {
+		let previous_state = pallet_omnipool::Pallet::<Runtime>::load_asset_state(asset_id)?.tradable;
+
+		// frozen assets are being removed from the omnipool and must stay frozen
+		ensure!(
+			previous_state != Tradability::FROZEN,
+			pallet_omnipool::Error::<Runtime>::NotAllowed
+		);
+
+		pallet_omnipool::Pallet::<Runtime>::do_set_asset_tradable_state(asset_id, Tradability::REMOVE_LIQUIDITY)?;
+
+		Ok(previous_state)
+	}
+
+	fn on_resume(asset_id: AssetId, previous_state: Tradability) -> DispatchResult {
+		// asset could have been removed from the omnipool in the meantime
+		if !pallet_omnipool::Pallet::<Runtime>::exists(asset_id) {
+			return Ok(());
+		}
+
+		pallet_omnipool::Pallet::<Runtime>::do_set_asset_tradable_state(asset_id, previous_state)
+	}
+
+	fn on_halt_weight() -> Weight {
+		<Runtime as pallet_omnipool::Config>::WeightInfo::set_asset_tradable_state()
+			.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads(1))
+	}
+
+	fn on_resume_weight() -> Weight {
+		<Runtime as pallet_omnipool::Config>::WeightInfo::set_asset_tradable_state()
+	}
+}
+
+/// Provides assets of Omnipool calls to the circuit breaker, so that limit hits of failed calls can be counted.
+///
+/// For `add_liquidity_batch`, the asset is the first one of the batch whose amount exceeds its remaining
+/// add liquidity limit of the block. Rolling window limit hits of a batch are not counted.
+pub struct OmnipoolLimitedAssets<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> LimitedAssets<<Runtime as frame_system::Config>::RuntimeCall, AssetId> for OmnipoolLimitedAssets<Runtime>
+where
+	Runtime: pallet_omnipool::Config<AssetId = AssetId>
+		+ pallet_circuit_breaker::Config<AssetId = AssetId, Balance = Balance>,
+	<Runtime as frame_system::Config>::RuntimeCall: IsSubType<pallet_omnipool::Call<Runtime>>,
+{
+	fn limited_assets(call: &<Runtime as frame_system::Config>::RuntimeCall) -> Option<(AssetId, AssetId)> {
+		match call.is_sub_type()? {
+			pallet_omnipool::Call::sell {
+				asset_in, asset_out, ..
+			}
+			| pallet_omnipool::Call::buy {
+				asset_in, asset_out, ..
+			}
+			| pallet_omnipool::Call::sell_with_deadline {
+				asset_in, asset_out, ..
+			}
+			| pallet_omnipool::Call::buy_with_deadline {
+				asset_in, asset_out, ..
+			} => Some((*asset_in, *asset_out)),
+			pallet_omnipool::Call::add_liquidity { asset, .. } => Some((*asset, *asset)),
+			pallet_omnipool::Call::add_liquidity_batch { assets } => assets
+				.iter()
+				.find(|(asset, amount)| {
+					pallet_omnipool::Pallet::<Runtime>::load_asset_state(*asset)
+						.and_then(|state| {
+							pallet_circuit_breaker::Pallet::<Runtime>::remaining_allowance_in_block(
+								*asset,
+								state.reserve,
+							)
+						})
+						.map(|allowance| allowance.add_liquidity.map_or(false, |remaining| *amount > remaining))
+						.unwrap_or(false)
+				})
+				.map(|(asset, _)| (*asset, *asset)),
+			pallet_omnipool::Call::remove_liquidity { position_id, .. }
+			| pallet_omnipool::Call::remove_liquidity_with_limit { position_id, .. } => {
+				pallet_omnipool::Pallet::<Runtime>::positions(position_id)
+					.map(|position| (position.asset_id, position.asset_id))
+			}
+			_ => None,
+		}
 	}
 }
 
@@ -400,3 +552,237 @@ impl<
 		Ok(asset.clone().into())
 	}
 }
+
+/// Executes trades of the route executor in the omnipool.
+pub struct OmnipoolTradeAdapter<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> TradeExecution<OriginFor<Runtime>, AssetId, Balance> for OmnipoolTradeAdapter<Runtime>
+where
+	Runtime: pallet_omnipool::Config<AssetId = AssetId>,
+{
+	fn calculate_sell(asset_in: AssetId, asset_out: AssetId, amount_in: Balance) -> Result<Balance, DispatchError> {
+		pallet_omnipool::Pallet::<Runtime>::quote_sell(asset_in, asset_out, amount_in).map(|quote| quote.amount_out)
+	}
+
+	fn calculate_buy(asset_in: AssetId, asset_out: AssetId, amount_out: Balance) -> Result<Balance, DispatchError> {
+		pallet_omnipool::Pallet::<Runtime>::quote_buy(asset_out, asset_in, amount_out).map(|quote| quote.amount_in)
+	}
+
+	fn execute_sell(
+		origin: OriginFor<Runtime>,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_in: Balance,
+		min_limit: Balance,
+	) -> DispatchResult {
+		pallet_omnipool::Pallet::<Runtime>::sell(origin, asset_in, asset_out, amount_in, min_limit)
+	}
+
+	fn execute_buy(
+		origin: OriginFor<Runtime>,
+		asset_in: AssetId,
+		asset_out: AssetId,
+		amount_out: Balance,
+		max_limit: Balance,
+	) -> DispatchResult {
+		pallet_omnipool::Pallet::<Runtime>::buy(origin, asset_out, asset_in, amount_out, max_limit)
+	}
+
+	fn sell_weight() -> Weight {
+		<Runtime as pallet_omnipool::Config>::WeightInfo::sell()
+			.saturating_add(<Runtime as pallet_omnipool::Config>::OmnipoolHooks::on_trade_weight())
+			.saturating_add(<Runtime as pallet_omnipool::Config>::OmnipoolHooks::on_liquidity_changed_weight())
+	}
+
+	fn buy_weight() -> Weight {
+		<Runtime as pallet_omnipool::Config>::WeightInfo::buy()
+			.saturating_add(<Runtime as pallet_omnipool::Config>::OmnipoolHooks::on_trade_weight())
+			.saturating_add(<Runtime as pallet_omnipool::Config>::OmnipoolHooks::on_liquidity_changed_weight())
+	}
+}
+
+/// Fills OTC orders on behalf of the route executor.
+pub struct OtcOrderBookAdapter<Runtime>(PhantomData<Runtime>);
+
+impl<Runtime> OrderBook<OriginFor<Runtime>, OrderId, AssetId, Balance> for OtcOrderBookAdapter<Runtime>
+where
+	Runtime: pallet_otc::Config<AssetId = AssetId>,
+{
+	fn order(order_id: OrderId) -> Option<OrderInfo<AssetId, Balance>> {
+		pallet_otc::Pallet::<Runtime>::orders(order_id).map(|order| OrderInfo {
+			asset_in: order.asset_in,
+			asset_out: order.asset_out,
+			amount_in: order.amount_in,
+			amount_out: order.amount_out,
+			partially_fillable: order.partially_fillable,
+		})
+	}
+
+	fn fill(origin: OriginFor<Runtime>, order_id: OrderId, amount_in: Balance) -> DispatchResult {
+		let order =
+			pallet_otc::Pallet::<Runtime>::orders(order_id).ok_or(pallet_otc::Error::<Runtime>::OrderNotFound)?;
+
+		if amount_in == order.amount_in {
+			pallet_otc::Pallet::<Runtime>::fill_order(origin, order_id)
+		} else {
+			pallet_otc::Pallet::<Runtime>::partial_fill_order(origin, order_id, amount_in)
+		}
+	}
+
+	fn fill_weight() -> Weight {
+		<Runtime as pallet_otc::Config>::WeightInfo::fill_order()
+			.max(<Runtime as pallet_otc::Config>::WeightInfo::partial_fill_order())
+	}
+}
+
+/// Exchanges assets in the XCM holding register by trading them in the omnipool.
+///
+/// The given asset is deposited to `TempAccount`, which trades it via the omnipool `sell` or `buy` extrinsic, and
+/// the result is withdrawn back to holding. Going through the extrinsics makes the circuit breaker trade limits
+/// apply; the price barrier is checked for both assets before trading.
+///
+/// Only an exchange of one fungible asset for another, both known to `CurrencyIdConvert`, is supported.
+/// If `maximal` is true, all of the given asset is sold for at least the wanted amount. Otherwise exactly the wanted
+/// amount is bought and the unspent part of the given asset is returned together with it.
+pub struct OmnipoolXcmExchanger<Runtime, TempAccount, CurrencyIdConvert>(
+	PhantomData<(Runtime, TempAccount, CurrencyIdConvert)>,
+);
+
+impl<Runtime, TempAccount, CurrencyIdConvert> AssetExchange
+	for OmnipoolXcmExchanger<Runtime, TempAccount, CurrencyIdConvert>
+where
+	Runtime: pallet_omnipool::Config<AssetId = AssetId>,
+	TempAccount: Get<Runtime::AccountId>,
+	CurrencyIdConvert: Convert<MultiAsset, Option<AssetId>>,
+{
+	fn exchange_asset(
+		_origin: Option<&MultiLocation>,
+		give: Assets,
+		want: &MultiAssets,
+		maximal: bool,
+	) -> Result<Assets, Assets> {
+		if give.len() != 1 || want.len() != 1 {
+			return Err(give);
+		}
+
+		let (given, wanted) = match (give.fungible_assets_iter().next(), want.get(0)) {
+			(Some(given), Some(wanted)) => (given, wanted.clone()),
+			_ => return Err(give),
+		};
+
+		with_transaction(|| match Self::do_exchange(given, wanted, maximal) {
+			Ok(assets) => TransactionOutcome::Commit(Ok(assets)),
+			Err(e) => TransactionOutcome::Rollback(Err(e)),
+		})
+		.map_err(|_: DispatchError| give)
+	}
+}
+
+impl<Runtime, TempAccount, CurrencyIdConvert> OmnipoolXcmExchanger<Runtime, TempAccount, CurrencyIdConvert>
+where
+	Runtime: pallet_omnipool::Config<AssetId = AssetId>,
+	TempAccount: Get<Runtime::AccountId>,
+	CurrencyIdConvert: Convert<MultiAsset, Option<AssetId>>,
+{
+	fn do_exchange(given: MultiAsset, wanted: MultiAsset, maximal: bool) -> Result<Assets, DispatchError> {
+		let (asset_in, amount_in) = Self::convert_fungible(given.clone())?;
+		let (asset_out, amount_out) = Self::convert_fungible(wanted.clone())?;
+
+		Self::ensure_price(asset_in)?;
+		Self::ensure_price(asset_out)?;
+
+		let who = TempAccount::get();
+		let in_before = Runtime::Currency::free_balance(asset_in, &who);
+		let out_before = Runtime::Currency::free_balance(asset_out, &who);
+
+		Runtime::Currency::deposit(asset_in, &who, amount_in)?;
+
+		let origin: OriginFor<Runtime> = RawOrigin::Signed(who.clone()).into();
+		if maximal {
+			pallet_omnipool::Pallet::<Runtime>::sell(origin, asset_in, asset_out, amount_in, amount_out)?;
+		} else {
+			pallet_omnipool::Pallet::<Runtime>::buy(origin, asset_out, asset_in, amount_out, amount_in)?;
+		}
+
+		let received = Runtime::Currency::free_balance(asset_out, &who).saturating_sub(out_before);
+		let unspent = Runtime::Currency::free_balance(asset_in, &who).saturating_sub(in_before);
+
+		Runtime::Currency::withdraw(asset_out, &who, received)?;
+		Runtime::Currency::withdraw(asset_in, &who, unspent)?;
+
+		let mut assets = Assets::new();
+		assets.subsume(MultiAsset {
+			id: wanted.id,
+			fun: Fungible(received),
+		});
+		if !unspent.is_zero() {
+			assets.subsume(MultiAsset {
+				id: given.id,
+				fun: Fungible(unspent),
+			});
+		}
+
+		Ok(assets)
+	}
+
+	fn convert_fungible(asset: MultiAsset) -> Result<(AssetId, Balance), DispatchError> {
+		match asset.fun {
+			Fungible(amount) => CurrencyIdConvert::convert(asset)
+				.map(|asset_id| (asset_id, amount))
+				.ok_or(DispatchError::Other("Unknown asset")),
+			NonFungible(_) => Err(DispatchError::Other("Non-fungible asset")),
+		}
+	}
+
+	fn ensure_price(asset_id: AssetId) -> DispatchResult {
+		if asset_id == <Runtime as pallet_omnipool::Config>::HubAssetId::get() {
+			return Ok(());
+		}
+
+		let state = pallet_omnipool::Pallet::<Runtime>::load_asset_state(asset_id)?;
+
+		<Runtime as pallet_omnipool::Config>::PriceBarrier::ensure_price(
+			&TempAccount::get(),
+			<Runtime as pallet_omnipool::Config>::HubAssetId::get(),
+			asset_id,
+			EmaPrice::new(state.hub_reserve, state.reserve),
+		)
+		.map_err(|_| pallet_omnipool::Error::<Runtime>::PriceDifferenceTooHigh.into())
+	}
+}
+
+/// Weighs XCM messages with `Weigher` and adds `ExchangeWeight` for every `ExchangeAsset` instruction,
+/// including instructions nested in error handlers and appendices.
+pub struct WeighExchangeAsset<Weigher, ExchangeWeight>(PhantomData<(Weigher, ExchangeWeight)>);
+
+impl<Weigher, ExchangeWeight> WeighExchangeAsset<Weigher, ExchangeWeight>
+where
+	ExchangeWeight: Get<Weight>,
+{
+	fn exchange_weight<Call>(instruction: &Instruction<Call>) -> Weight {
+		match instruction {
+			ExchangeAsset { .. } => ExchangeWeight::get(),
+			SetErrorHandler(xcm) | SetAppendix(xcm) => xcm.0.iter().fold(Weight::zero(), |acc, instruction| {
+				acc.saturating_add(Self::exchange_weight(instruction))
+			}),
+			_ => Weight::zero(),
+		}
+	}
+}
+
+impl<Call, Weigher, ExchangeWeight> WeightBounds<Call> for WeighExchangeAsset<Weigher, ExchangeWeight>
+where
+	Weigher: WeightBounds<Call>,
+	ExchangeWeight: Get<Weight>,
+{
+	fn weight(message: &mut Xcm<Call>) -> Result<Weight, ()> {
+		let weight = Weigher::weight(message)?;
+		Ok(message.0.iter().fold(weight, |acc, instruction| {
+			acc.saturating_add(Self::exchange_weight(instruction))
+		}))
+	}
+
+	fn instr_weight(instruction: &Instruction<Call>) -> Result<Weight, ()> {
+		Weigher::instr_weight(instruction).map(|weight| weight.saturating_add(Self::exchange_weight(instruction)))
+	}
+}
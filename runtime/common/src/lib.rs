@@ -22,19 +22,21 @@ pub mod weights;
 
 use codec::alloc::vec;
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_support::traits::{Contains, EitherOfDiverse, LockIdentifier};
+use frame_support::traits::{tokens::nonfungibles::InspectEnumerable, Contains, EitherOfDiverse, Get, LockIdentifier};
 use frame_support::{parameter_types, PalletId, RuntimeDebug};
 use frame_system::EnsureRoot;
 use hydradx_traits::oracle::{OraclePeriod, Source};
+use pallet_omnipool::types::OwnedPosition;
 pub use pallet_transaction_payment::Multiplier;
 pub use primitives::constants::{chain::*, currency::*, time::*};
-pub use primitives::{Amount, AssetId, Balance, BlockNumber, CollectionId};
+pub use primitives::{Amount, AssetId, Balance, BlockNumber, CollectionId, ItemId};
 use scale_info::TypeInfo;
 use sp_runtime::{
 	generic,
 	traits::{AccountIdConversion, BlakeTwo256, Bounded, IdentifyAccount, Verify},
 	FixedPointNumber, MultiSignature, Perbill, Percent, Permill, Perquintill,
 };
+use sp_std::marker::PhantomData;
 use sp_std::prelude::*;
 
 /// Alias to 512-bit hash when used in the context of a transaction signature on the chain.
@@ -117,14 +119,57 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 	]
 }
 
-pub struct CircuitBreakerWhitelist;
+/// Accounts bypassing the circuit breaker liquidity limits and the Omnipool price barrier.
+///
+/// The treasury is always whitelisted, other accounts are managed by governance in the circuit breaker pallet.
+pub struct CircuitBreakerWhitelist<Runtime>(PhantomData<Runtime>);
 
-impl Contains<AccountId> for CircuitBreakerWhitelist {
+impl<Runtime> Contains<AccountId> for CircuitBreakerWhitelist<Runtime>
+where
+	Runtime: pallet_circuit_breaker::Config + frame_system::Config<AccountId = AccountId>,
+{
 	fn contains(a: &AccountId) -> bool {
 		<PalletId as AccountIdConversion<AccountId>>::into_account_truncating(&TreasuryPalletId::get()) == *a
+			|| pallet_circuit_breaker::Pallet::<Runtime>::contains(a)
 	}
 }
 
+/// Omnipool positions of `owner`.
+///
+/// Positions deposited to omnipool liquidity mining are owned by the liquidity mining pallet, so they are
+/// looked up through the deposit NFTs of `owner` instead.
+pub fn omnipool_positions_of<Runtime, NFTHandler>(owner: &AccountId) -> Vec<OwnedPosition<ItemId, AssetId, Balance>>
+where
+	Runtime: pallet_omnipool_liquidity_mining::Config
+		+ pallet_omnipool::Config<AssetId = AssetId, PositionItemId = ItemId, CollectionId = CollectionId>
+		+ frame_system::Config<AccountId = AccountId>,
+	NFTHandler: InspectEnumerable<AccountId, CollectionId = CollectionId, ItemId = ItemId>,
+{
+	let owned = NFTHandler::owned_in_collection(&<Runtime as pallet_omnipool::Config>::NFTCollectionId::get(), owner)
+		.filter_map(|position_id| {
+			pallet_omnipool::Pallet::<Runtime>::positions(position_id).map(|position| OwnedPosition {
+				position_id,
+				deposit_id: None,
+				position,
+			})
+		});
+
+	let deposited = NFTHandler::owned_in_collection(
+		&<Runtime as pallet_omnipool_liquidity_mining::Config>::NFTCollectionId::get(),
+		owner,
+	)
+	.filter_map(|deposit_id| {
+		let position_id = pallet_omnipool_liquidity_mining::Pallet::<Runtime>::omni_position_id(deposit_id)?;
+		pallet_omnipool::Pallet::<Runtime>::positions(position_id).map(|position| OwnedPosition {
+			position_id,
+			deposit_id: Some(deposit_id),
+			position,
+		})
+	});
+
+	owned.chain(deposited).collect()
+}
+
 // frame system
 parameter_types! {
 	pub const BlockHashCount: BlockNumber = 2400;
@@ -322,6 +367,10 @@ parameter_types! {
 parameter_types! {
 	pub const DefaultMaxNetTradeVolumeLimitPerBlock: (u32, u32) = (5_000, 10_000);	// 50%
 	pub const DefaultMaxLiquidityLimitPerBlock: Option<(u32, u32)> = Some((500, 10_000));	// 5%
+	pub const DefaultMaxPriceChangePerBlock: Option<(u32, u32)> = None;	// set per asset by governance
+	pub const CircuitBreakerMaxLimitHits: u32 = 5;
+	pub const CircuitBreakerLimitHitsPeriod: BlockNumber = 10 * MINUTES;
+	pub const CircuitBreakerLimitHitFee: Balance = 100 * DOLLARS;	// charged on top of transaction fee for each counted limit hit
 }
 
 // pallet duster
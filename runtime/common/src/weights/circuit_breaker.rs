@@ -71,6 +71,35 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 	fn set_remove_liquidity_limit() -> Weight {
 		Weight::from_ref_time(22_618_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	fn set_window_limits() -> Weight {
+		Weight::from_ref_time(24_374_000 as u64).saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn resume_asset() -> Weight {
+		Weight::from_ref_time(25_913_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn note_limit_hit() -> Weight {
+		Weight::from_ref_time(71_245_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	fn set_price_change_limit() -> Weight {
+		Weight::from_ref_time(22_337_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn ensure_price_change_limit() -> Weight {
+		Weight::from_ref_time(19_854_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn add_to_whitelist() -> Weight {
+		Weight::from_ref_time(21_506_000 as u64).saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn remove_from_whitelist() -> Weight {
+		Weight::from_ref_time(23_187_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
 	fn ensure_add_liquidity_limit() -> Weight {
 		Weight::from_ref_time(21_922_000 as u64)
 			.saturating_add(T::DbWeight::get().reads(4 as u64))
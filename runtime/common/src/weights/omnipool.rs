@@ -71,14 +71,14 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(14 as u64))
 	}
 	fn sell() -> Weight {
-		Weight::from_ref_time(164_610_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(14 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+		Weight::from_ref_time(191_610_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(26 as u64))
+			.saturating_add(T::DbWeight::get().writes(19 as u64))
 	}
 	fn buy() -> Weight {
-		Weight::from_ref_time(165_125_000 as u64)
-			.saturating_add(T::DbWeight::get().reads(14 as u64))
-			.saturating_add(T::DbWeight::get().writes(10 as u64))
+		Weight::from_ref_time(192_125_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(26 as u64))
+			.saturating_add(T::DbWeight::get().writes(19 as u64))
 	}
 	fn set_asset_tradable_state() -> Weight {
 		Weight::from_ref_time(34_641_000 as u64)
@@ -100,4 +100,89 @@ impl<T: frame_system::Config> WeightInfo for HydraWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as u64))
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
+	fn set_asset_fee_bounds() -> Weight {
+		Weight::from_ref_time(34_751_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn set_asset_fees() -> Weight {
+		Weight::from_ref_time(34_751_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn set_asset_max_price_difference() -> Weight {
+		Weight::from_ref_time(34_751_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn withdraw_protocol_liquidity() -> Weight {
+		Weight::from_ref_time(226_896_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(18 as u64))
+			.saturating_add(T::DbWeight::get().writes(14 as u64))
+	}
+	fn reduce_imbalance() -> Weight {
+		Weight::from_ref_time(34_751_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	fn set_protocol_fee_beneficiaries() -> Weight {
+		Weight::from_ref_time(34_751_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	fn propose_token() -> Weight {
+		Weight::from_ref_time(197_216_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(16 as u64))
+			.saturating_add(T::DbWeight::get().writes(12 as u64))
+	}
+	fn approve_listing() -> Weight {
+		Weight::from_ref_time(60_641_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	fn reject_listing() -> Weight {
+		Weight::from_ref_time(177_634_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(11 as u64))
+			.saturating_add(T::DbWeight::get().writes(14 as u64))
+	}
+	fn set_asset_min_holding_period() -> Weight {
+		Weight::from_ref_time(34_751_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+	}
+	fn settle_position() -> Weight {
+		Weight::from_ref_time(238_412_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(20 as u64))
+			.saturating_add(T::DbWeight::get().writes(14 as u64))
+	}
+	fn remove_token() -> Weight {
+		Weight::from_ref_time(112_634_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(6 as u64))
+			.saturating_add(T::DbWeight::get().writes(8 as u64))
+	}
+	fn merge_positions(n: u32) -> Weight {
+		Weight::from_ref_time(40_718_000 as u64) // Standard Error: 18_000
+			.saturating_add(Weight::from_ref_time(44_523_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((5 as u64).saturating_mul(n as u64)))
+	}
+	fn split_position() -> Weight {
+		Weight::from_ref_time(83_912_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(5 as u64))
+			.saturating_add(T::DbWeight::get().writes(7 as u64))
+	}
+	fn add_liquidity_batch(n: u32) -> Weight {
+		Weight::from_ref_time(27_318_000 as u64) // Standard Error: 57_000
+			.saturating_add(Weight::from_ref_time(141_226_000 as u64).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().reads((11 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((10 as u64).saturating_mul(n as u64)))
+	}
+	fn claim_protocol_fees() -> Weight {
+		Weight::from_ref_time(31_420_000 as u64)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
 }
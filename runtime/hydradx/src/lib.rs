@@ -33,7 +33,7 @@ use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdConversion, BlakeTwo256, Block as BlockT, IdentityLookup},
 	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, Perbill, Permill,
+	ApplyExtrinsicResult, DispatchError, FixedU128, Perbill, Permill,
 };
 use sp_std::cmp::Ordering;
 use sp_std::convert::From;
@@ -65,7 +65,10 @@ use primitives::{CollectionId, ItemId};
 pub use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_runtime::traits::BlockNumberProvider;
 
-use common_runtime::adapters::{EmaOraclePriceAdapter, OmnipoolHookAdapter};
+use common_runtime::adapters::{
+	EmaOraclePriceAdapter, OmnipoolHaltAdapter, OmnipoolHookAdapter, OmnipoolLimitedAssets, OmnipoolTradeAdapter,
+	OtcOrderBookAdapter,
+};
 pub use common_runtime::*;
 use pallet_currencies::BasicCurrencyAdapter;
 
@@ -105,10 +108,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("hydradx"),
 	impl_name: create_runtime_str!("hydradx"),
 	authoring_version: 1,
-	spec_version: 152,
+	spec_version: 187,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 0,
 };
 
@@ -840,6 +843,8 @@ parameter_types! {
 	pub const StableAssetId: AssetId = 2;
 	pub ProtocolFee: Permill = Permill::from_rational(5u32,10000u32);
 	pub AssetFee: Permill = Permill::from_rational(25u32,10000u32);
+	pub DynamicFeeAmplification: FixedU128 = FixedU128::from(2);
+	pub DynamicFeeDecay: Permill = Permill::from_parts(10);
 	pub const MinTradingLimit : Balance = 1_000_000u128;
 	pub const MinPoolLiquidity: Balance = 1_000_000u128;
 	pub const MaxInRatio: Balance = 3u128;
@@ -848,6 +853,9 @@ parameter_types! {
 	pub const EmaOracleSpotPriceLastBlock: OraclePeriod = OraclePeriod::LastBlock;
 	pub const EmaOracleSpotPriceShort: OraclePeriod = OraclePeriod::Short;
 	pub const OmnipoolMaxAllowedPriceDifference: Permill = Permill::from_percent(1);
+	pub const OmnipoolListingBond: Balance = 100_000 * UNITS;
+	pub const OmnipoolListingChallengePeriod: BlockNumber = 7 * DAYS;
+	pub const OmnipoolListingWeightCap: Permill = Permill::from_percent(1);
 	pub MinimumWithdrawalFee: Permill = Permill::from_rational(1u32,10000);
 }
 
@@ -857,17 +865,26 @@ impl pallet_omnipool::Config for Runtime {
 	type Currency = Currencies;
 	type AuthorityOrigin = EnsureRoot<AccountId>;
 	type TechnicalOrigin = SuperMajorityTechCommittee;
+	type TreasuryAccount = TreasuryAccount;
 	type AssetRegistry = AssetRegistry;
 	type HdxAssetId = NativeAssetId;
 	type HubAssetId = LRNA;
 	type StableCoinAssetId = StableAssetId;
 	type ProtocolFee = ProtocolFee;
 	type AssetFee = AssetFee;
+	type DynamicFeeAmplification = DynamicFeeAmplification;
+	type DynamicFeeDecay = DynamicFeeDecay;
 	type MinWithdrawalFee = MinimumWithdrawalFee;
 	type MinimumTradingLimit = MinTradingLimit;
 	type MinimumPoolLiquidity = MinPoolLiquidity;
 	type MaxInRatio = MaxInRatio;
 	type MaxOutRatio = MaxOutRatio;
+	type MaxMergedPositions = ConstU32<10>;
+	type MaxAddLiquidityBatch = ConstU32<10>;
+	type MaxProtocolFeeBeneficiaries = ConstU32<5>;
+	type ListingBond = OmnipoolListingBond;
+	type ListingChallengePeriod = OmnipoolListingChallengePeriod;
+	type ListingWeightCap = OmnipoolListingWeightCap;
 	type PositionItemId = ItemId;
 	type CollectionId = CollectionId;
 	type NFTCollectionId = OmnipoolCollectionId;
@@ -880,14 +897,16 @@ impl pallet_omnipool::Config for Runtime {
 			AssetId,
 			EmaOraclePriceAdapter<EmaOracleSpotPriceLastBlock, Runtime>,
 			OmnipoolMaxAllowedPriceDifference,
-			CircuitBreakerWhitelist,
+			MaxPriceDifferenceOf<Runtime>,
+			CircuitBreakerWhitelist<Runtime>,
 		>,
 		EnsurePriceWithin<
 			AccountId,
 			AssetId,
 			EmaOraclePriceAdapter<EmaOracleSpotPriceShort, Runtime>,
 			OmnipoolMaxAllowedPriceDifference,
-			CircuitBreakerWhitelist,
+			MaxPriceDifferenceOf<Runtime>,
+			CircuitBreakerWhitelist<Runtime>,
 		>,
 	);
 	type ExternalPriceOracle = EmaOraclePriceAdapter<EmaOracleSpotPriceShort, Runtime>;
@@ -904,17 +923,26 @@ impl pallet_circuit_breaker::Config for Runtime {
 	type AssetId = AssetId;
 	type Balance = Balance;
 	type TechnicalOrigin = SuperMajorityTechCommittee;
-	type WhitelistedAccounts = CircuitBreakerWhitelist;
+	type WhitelistedAccounts = CircuitBreakerWhitelist<Runtime>;
 	type DefaultMaxNetTradeVolumeLimitPerBlock = DefaultMaxNetTradeVolumeLimitPerBlock;
 	type DefaultMaxAddLiquidityLimitPerBlock = DefaultMaxLiquidityLimitPerBlock;
 	type DefaultMaxRemoveLiquidityLimitPerBlock = DefaultMaxLiquidityLimitPerBlock;
+	type DefaultMaxPriceChangePerBlock = DefaultMaxPriceChangePerBlock;
 	type OmnipoolHubAsset = LRNA;
+	type AssetHaltHandler = OmnipoolHaltAdapter<Runtime>;
+	type LimitedAssets = OmnipoolLimitedAssets<Runtime>;
+	type MaxLimitHits = CircuitBreakerMaxLimitHits;
+	type LimitHitsPeriod = CircuitBreakerLimitHitsPeriod;
+	type Currency = Balances;
+	type LimitHitFee = CircuitBreakerLimitHitFee;
+	type LimitHitFeeReceiver = TreasuryAccount;
 	type WeightInfo = weights::circuit_breaker::HydraWeight<Runtime>;
 }
 
 // constants need to be in scope to use as types
 use pallet_ema_oracle::MAX_PERIODS;
 use pallet_omnipool::traits::EnsurePriceWithin;
+use pallet_omnipool::MaxPriceDifferenceOf;
 
 parameter_types! {
 	pub SupportedPeriods: BoundedVec<OraclePeriod, ConstU32<MAX_PERIODS>> = BoundedVec::truncate_from(vec![
@@ -992,6 +1020,23 @@ impl pallet_otc::Config for Runtime {
 	type WeightInfo = weights::otc::HydraWeight<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxRouteLength: u32 = 5;
+	pub RouteIntermediateAssets: Vec<AssetId> = vec![NativeAssetId::get(), StableAssetId::get()];
+}
+
+impl pallet_route_executor::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = AssetId;
+	type OrderId = pallet_otc::OrderId;
+	type Currency = Currencies;
+	type AMM = OmnipoolTradeAdapter<Runtime>;
+	type OrderBook = OtcOrderBookAdapter<Runtime>;
+	type SpotPriceProvider = Omnipool;
+	type MaxRouteLength = MaxRouteLength;
+	type IntermediateAssets = RouteIntermediateAssets;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -1029,6 +1074,7 @@ construct_runtime!(
 		OmnipoolLiquidityMining: pallet_omnipool_liquidity_mining = 63,
 		OTC: pallet_otc = 64,
 		CircuitBreaker: pallet_circuit_breaker = 65,
+		Router: pallet_route_executor = 66,
 
 		// ORML related modules
 		Tokens: orml_tokens = 77,
@@ -1087,6 +1133,7 @@ pub type SignedExtra = (
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 	pallet_claims::ValidateClaim<Runtime>,
+	pallet_circuit_breaker::TrackLimitHits<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
@@ -1245,6 +1292,47 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_omnipool_runtime_api::OmnipoolApi<Block, AccountId, AssetId, Balance, ItemId> for Runtime {
+		fn quote_sell(
+			asset_in: AssetId,
+			asset_out: AssetId,
+			amount: Balance,
+		) -> Result<pallet_omnipool_runtime_api::TradeQuote<Balance>, DispatchError> {
+			Omnipool::quote_sell(asset_in, asset_out, amount)
+		}
+
+		fn quote_buy(
+			asset_out: AssetId,
+			asset_in: AssetId,
+			amount: Balance,
+		) -> Result<pallet_omnipool_runtime_api::TradeQuote<Balance>, DispatchError> {
+			Omnipool::quote_buy(asset_out, asset_in, amount)
+		}
+
+		fn position_value(
+			position_id: ItemId,
+		) -> Result<pallet_omnipool_runtime_api::PositionValue<AssetId, Balance>, DispatchError> {
+			Omnipool::position_value(position_id)
+		}
+
+		fn positions(
+			owner: AccountId,
+		) -> Vec<pallet_omnipool_runtime_api::OwnedPosition<ItemId, AssetId, Balance>> {
+			omnipool_positions_of::<Runtime, Uniques>(&owner)
+		}
+	}
+
+	impl pallet_circuit_breaker_runtime_api::CircuitBreakerApi<Block, AssetId, Balance> for Runtime {
+		fn remaining_allowance(
+			asset_id: AssetId,
+		) -> Result<pallet_circuit_breaker_runtime_api::RemainingAllowance<Balance>, DispatchError> {
+			let asset_reserve = Omnipool::load_asset_state(asset_id)
+				.map(|state| state.reserve)
+				.unwrap_or_default();
+			CircuitBreaker::remaining_allowance(asset_id, asset_reserve)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
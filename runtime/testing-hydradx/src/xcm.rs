@@ -1,5 +1,6 @@
 use super::{AssetId, *};
 
+use common_runtime::adapters::{OmnipoolXcmExchanger, WeighExchangeAsset};
 use cumulus_primitives_core::ParaId;
 use frame_support::{
 	traits::{Everything, Nothing},
@@ -8,6 +9,7 @@ use frame_support::{
 use hydradx_adapters::{MultiCurrencyTrader, ToFeeReceiver};
 use orml_traits::{location::AbsoluteReserveProvider, parameter_type_with_key};
 pub use orml_xcm_support::{DepositToAlternative, IsNativeConcrete, MultiCurrencyAdapter, MultiNativeAsset};
+use pallet_route_executor::traits::TradeExecution;
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
 use polkadot_xcm::v3::prelude::*;
@@ -73,6 +75,13 @@ parameter_types! {
 	pub const MaxAssetsForTransfer: usize = 2;
 
 	pub UniversalLocation: InteriorMultiLocation = X2(GlobalConsensus(RelayNetwork::get()), Parachain(ParachainInfo::parachain_id().into()));
+
+	/// Account which trades in the omnipool on behalf of the `ExchangeAsset` instruction.
+	pub TempAccountForXcmAssetExchange: AccountId = PalletId(*b"xcm/exch").into_account_truncating();
+	/// Weight of an omnipool trade plus moving the assets in and out of the temporary account.
+	pub ExchangeAssetWeight: XcmWeight = OmnipoolTradeAdapter::<Runtime>::sell_weight()
+		.max(OmnipoolTradeAdapter::<Runtime>::buy_weight())
+		.saturating_add(<Runtime as frame_system::Config>::DbWeight::get().reads_writes(10, 6));
 }
 
 pub struct XcmConfig;
@@ -88,7 +97,8 @@ impl Config for XcmConfig {
 	type UniversalLocation = UniversalLocation;
 
 	type Barrier = Barrier;
-	type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
+	type Weigher =
+		WeighExchangeAsset<FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>, ExchangeAssetWeight>;
 	// We calculate weight fees the same way as for regular extrinsics and use the prices and choice
 	// of accepted currencies of the transaction payment pallet. Fees go to the same fee receiver as
 	// configured in `MultiTransactionPayment`.
@@ -105,7 +115,7 @@ impl Config for XcmConfig {
 	type ResponseHandler = PolkadotXcm;
 	type AssetTrap = PolkadotXcm;
 	type AssetLocker = ();
-	type AssetExchanger = ();
+	type AssetExchanger = OmnipoolXcmExchanger<Runtime, TempAccountForXcmAssetExchange, CurrencyIdConvert>;
 	type AssetClaims = PolkadotXcm;
 	type SubscriptionService = PolkadotXcm;
 	type PalletInstancesInfo = AllPalletsWithSystem;